@@ -34,13 +34,13 @@ fn generate_facts(n: usize) -> Vec<MettaValue> {
 fn generate_rules(n: usize) -> Vec<Rule> {
     let mut rules = Vec::new();
     for i in 0..n {
-        rules.push(Rule {
-            lhs: MettaValue::SExpr(vec![
+        rules.push(Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("rule".to_string()),
                 MettaValue::Long(i as i64),
             ]),
-            rhs: MettaValue::Atom(format!("result-{}", i)),
-        });
+            MettaValue::Atom(format!("result-{}", i)),
+        ));
     }
     rules
 }
@@ -233,13 +233,13 @@ fn bench_rule_matching(c: &mut Criterion) {
 
         // Add fibonacci-like rules
         for i in 0..*rule_count {
-            env.add_rule(Rule {
-                lhs: MettaValue::SExpr(vec![
+            env.add_rule(Rule::new(
+                MettaValue::SExpr(vec![
                     MettaValue::Atom("fib".to_string()),
                     MettaValue::Long(i as i64),
                 ]),
-                rhs: MettaValue::Long((i * 2) as i64),
-            });
+                MettaValue::Long((i * 2) as i64),
+            ));
         }
 
         // Benchmark lookup
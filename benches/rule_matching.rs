@@ -2,6 +2,7 @@ use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criteri
 use mettatron::backend::compile::compile;
 use mettatron::backend::environment::Environment;
 use mettatron::backend::eval::eval;
+use mettatron::backend::{eval_source, RunConfig};
 
 /// Generate N fibonacci rules for benchmarking
 fn generate_fibonacci_rules(n: usize) -> String {
@@ -162,19 +163,9 @@ fn bench_full_evaluation(c: &mut Criterion) {
 
     group.bench_function("fibonacci_10", |b| {
         b.iter(|| {
-            let env = Environment::new();
-            let lines: Vec<&str> = fib_program
-                .lines()
-                .map(|l| l.trim())
-                .filter(|l| !l.is_empty())
-                .collect();
-
-            for line in lines {
-                let state = compile(line).expect("Failed to compile");
-                for expr in state.source {
-                    let _ = eval(black_box(expr), env.clone());
-                }
-            }
+            let result = eval_source(black_box(fib_program), RunConfig::default())
+                .expect("Failed to compile");
+            black_box(result)
         });
     });
 
@@ -206,19 +197,9 @@ fn bench_full_evaluation(c: &mut Criterion) {
 
     group.bench_function("type_inference", |b| {
         b.iter(|| {
-            let env = Environment::new();
-            let lines: Vec<&str> = type_program
-                .lines()
-                .map(|l| l.trim())
-                .filter(|l| !l.is_empty())
-                .collect();
-
-            for line in lines {
-                let state = compile(line).expect("Failed to compile");
-                for expr in state.source {
-                    let _ = eval(black_box(expr), env.clone());
-                }
-            }
+            let result = eval_source(black_box(type_program), RunConfig::default())
+                .expect("Failed to compile");
+            black_box(result)
         });
     });
 
@@ -7,8 +7,13 @@
 //
 // Operator symbols like +, -, * are preserved as-is (not normalized to add, sub, mul)
 
+use crate::backend::environment::Environment;
+use crate::backend::eval::special_form_arity;
 use crate::backend::models::{MettaState, MettaValue};
-use crate::tree_sitter_parser::{SyntaxError, SyntaxErrorKind, TreeSitterMettaParser};
+use crate::ir::{SExpr, Span};
+use crate::tree_sitter_parser::{
+    LineDirectives, SyntaxError, SyntaxErrorKind, TreeSitterMettaParser,
+};
 
 use tracing::{debug, error, info, instrument, warn};
 
@@ -16,6 +21,163 @@ use tracing::{debug, error, info, instrument, warn};
 /// Returns a compiled state with pending expressions and empty environment
 #[instrument(level = "info", skip(src))]
 pub fn compile(src: &str) -> Result<MettaState, SyntaxError> {
+    let source = parse_source(src)?;
+    Ok(MettaState::new_compiled(source))
+}
+
+/// Compile MeTTa source code against an existing environment
+/// Returns a state holding only the newly parsed expressions together with `env`, so
+/// evaluating them sees every rule and fact defined before, without unioning
+/// environments. A syntax error leaves nothing to evaluate and drops `env`; pass a
+/// clone to keep using it.
+#[instrument(level = "info", skip(src, env))]
+pub fn compile_incremental(src: &str, env: Environment) -> Result<MettaState, SyntaxError> {
+    let source = parse_source(src)?;
+    Ok(MettaState {
+        source,
+        environment: env,
+        output: Vec::new(),
+    })
+}
+
+/// Error found while compiling, before anything is evaluated
+#[derive(Debug, Clone)]
+pub enum CompileError {
+    /// The source could not be parsed
+    Syntax(SyntaxError),
+    /// A special form was written with too few arguments
+    InvalidArity {
+        op: String,
+        expected: usize,
+        got: usize,
+        /// Span of the offending form
+        span: Span,
+        /// Original source file named by a `;#line` directive, if any
+        file: Option<String>,
+        /// Line of the offending form, after applying `;#line` directives (1-indexed)
+        line: usize,
+    },
+}
+
+impl CompileError {
+    /// Span of the offending form, if the error has one
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            CompileError::Syntax(_) => None,
+            CompileError::InvalidArity { span, .. } => Some(*span),
+        }
+    }
+}
+
+impl std::fmt::Display for CompileError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CompileError::Syntax(e) => write!(f, "{}", e),
+            CompileError::InvalidArity {
+                op,
+                expected,
+                got,
+                span,
+                file,
+                line,
+            } => {
+                if let Some(file) = file {
+                    write!(f, "{}:", file)?;
+                }
+                write!(
+                    f,
+                    "{}:{}: '{}' requires exactly {} argument{}, got {}",
+                    line,
+                    span.start.column + 1,
+                    op,
+                    expected,
+                    if *expected == 1 { "" } else { "s" },
+                    got
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CompileError {}
+
+impl From<SyntaxError> for CompileError {
+    fn from(e: SyntaxError) -> Self {
+        CompileError::Syntax(e)
+    }
+}
+
+/// Compile like `compile_incremental`, additionally rejecting special forms written with
+/// fewer arguments than they require
+/// Such forms would otherwise only fail when evaluated, as an `Error` value that does
+/// not say where in the source the call was. The required counts are the evaluator's
+/// own, so a program is rejected here exactly when evaluating the call would fail with
+/// an `ArityError`. The first offending form in source order is reported, together
+/// with its span and its line as remapped by `;#line` directives.
+#[instrument(level = "info", skip(src, env))]
+pub fn compile_checked(src: &str, env: Environment) -> Result<MettaState, CompileError> {
+    let sexprs = parse_sexprs(src)?;
+    let directives = LineDirectives::scan(src);
+    for sexpr in &sexprs {
+        check_arity(sexpr, &directives)?;
+    }
+    Ok(MettaState {
+        source: convert_sexprs(src, &sexprs)?,
+        environment: env,
+        output: Vec::new(),
+    })
+}
+
+/// Check the arity of every special form in `sexpr`, outermost first
+/// Quoted code, the left-hand side of `=` and `match` patterns are data rather than
+/// calls and are not checked.
+fn check_arity(sexpr: &SExpr, directives: &LineDirectives) -> Result<(), CompileError> {
+    let SExpr::List(items, span) = sexpr else {
+        return Ok(());
+    };
+    let mut pattern = None;
+    if let Some(SExpr::Atom(op, _)) = items.first() {
+        if let Some(expected) = special_form_arity(op) {
+            let got = items.len() - 1;
+            if got < expected {
+                let span = span.unwrap_or_else(Span::zero);
+                let (file, line) = directives.resolve(span.start.row);
+                return Err(CompileError::InvalidArity {
+                    op: op.clone(),
+                    expected,
+                    got,
+                    span,
+                    file,
+                    line,
+                });
+            }
+        }
+        match op.as_str() {
+            "quote" => return Ok(()),
+            "=" => pattern = Some(1),
+            // The space is either one argument or `&` followed by its name
+            "match" => match items.get(1) {
+                Some(SExpr::Atom(amp, _)) if amp == "&" => pattern = Some(3),
+                _ => pattern = Some(2),
+            },
+            _ => {}
+        }
+    }
+    items
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| Some(*i) != pattern)
+        .try_for_each(|(_, item)| check_arity(item, directives))
+}
+
+/// Parse source text into the values of its top-level expressions
+fn parse_source(src: &str) -> Result<Vec<MettaValue>, SyntaxError> {
+    let sexprs = parse_sexprs(src)?;
+    convert_sexprs(src, &sexprs)
+}
+
+/// Parse source text into its top-level expressions, keeping their spans
+fn parse_sexprs(src: &str) -> Result<Vec<SExpr>, SyntaxError> {
     info!(
         line_count = src.lines().count(),
         char_count = src.chars().count(),
@@ -28,9 +190,10 @@ pub fn compile(src: &str) -> Result<MettaState, SyntaxError> {
         line: 0,
         column: 0,
         text: String::new(),
+        file: None,
     })?;
 
-    let sexprs = parser.parse(src).map_err(|e| {
+    parser.parse(src).map_err(|e| {
         error!(
             kind = ?e.kind,
             text = %e,
@@ -38,8 +201,11 @@ pub fn compile(src: &str) -> Result<MettaState, SyntaxError> {
         );
         debug!(src, %e);
         e
-    })?;
+    })
+}
 
+/// Convert parsed expressions into values, desugaring top-level queries
+fn convert_sexprs(src: &str, sexprs: &[SExpr]) -> Result<Vec<MettaValue>, SyntaxError> {
     let metta_values: Result<Vec<_>, _> = sexprs.iter().map(MettaValue::try_from).collect();
     let metta_values = metta_values.map_err(|e| {
         error!(
@@ -52,10 +218,35 @@ pub fn compile(src: &str) -> Result<MettaState, SyntaxError> {
             line: 1,
             column: 1,
             text: e,
+            file: None,
         }
     })?;
 
-    Ok(MettaState::new_compiled(metta_values))
+    Ok(metta_values.into_iter().map(desugar_query).collect())
+}
+
+/// Rewrite a top-level query `?pattern` into `!(match & self pattern pattern)`,
+/// which evaluates to every atom in the space matching the pattern. The query is
+/// wrapped in `!` so its results are output like any other evaluation.
+fn desugar_query(value: MettaValue) -> MettaValue {
+    match value {
+        MettaValue::SExpr(items)
+            if items.len() == 2 && matches!(&items[0], MettaValue::Atom(op) if op == "?") =>
+        {
+            let pattern = items[1].clone();
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("!".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("match".to_string()),
+                    MettaValue::Atom("&".to_string()),
+                    MettaValue::Atom("self".to_string()),
+                    pattern.clone(),
+                    pattern,
+                ]),
+            ])
+        }
+        other => other,
+    }
 }
 
 #[cfg(test)]
@@ -300,6 +491,138 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_compile_question_query() {
+        let state = compile("?(parent Tom $x)").unwrap();
+        let pattern = MettaValue::SExpr(vec![
+            MettaValue::Atom("parent".to_string()),
+            MettaValue::Atom("Tom".to_string()),
+            MettaValue::Atom("$x".to_string()),
+        ]);
+        assert_eq!(
+            state.source[0],
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("!".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("match".to_string()),
+                    MettaValue::Atom("&".to_string()),
+                    MettaValue::Atom("self".to_string()),
+                    pattern.clone(),
+                    pattern,
+                ]),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_question_query_returns_matching_facts() {
+        let (forms, _) = crate::backend::run::eval_forms(
+            r#"
+            (parent Tom Bob)
+            (parent Tom Liz)
+            (parent Bob Ann)
+            ?(parent Tom $x)
+            "#,
+            Environment::new(),
+        );
+        let last = &forms[3];
+
+        let fact = |child: &str| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("parent".to_string()),
+                MettaValue::Atom("Tom".to_string()),
+                MettaValue::Atom(child.to_string()),
+            ])
+        };
+        assert_eq!(last.len(), 2, "{:?}", last);
+        assert!(last.contains(&fact("Bob")));
+        assert!(last.contains(&fact("Liz")));
+    }
+
+    #[test]
+    fn test_compile_incremental_sees_earlier_definitions() {
+        use crate::backend::run::eval_forms;
+
+        let first = compile_incremental("(= (double $x) (* $x 2))", Environment::new()).unwrap();
+        assert_eq!(first.source.len(), 1);
+        let (_, env) = eval_forms("(= (double $x) (* $x 2))", Environment::new());
+
+        // Only the new form is returned, and it runs against the existing rules
+        let second = compile_incremental("!(double 21)", env.clone()).unwrap();
+        assert_eq!(second.source.len(), 1);
+        assert_eq!(second.environment.rule_count(), 1);
+        let (forms, _) = eval_forms("!(double 21)", env);
+        assert_eq!(forms, vec![vec![MettaValue::Long(42)]]);
+
+        assert!(compile_incremental("(double", Environment::new()).is_err());
+    }
+
+    #[test]
+    fn test_compile_checked_reports_arity_location() {
+        let err = compile_checked("!(+ 1\n   (if True 1))", Environment::new()).unwrap_err();
+        match &err {
+            CompileError::InvalidArity {
+                op, expected, got, ..
+            } => {
+                assert_eq!(op, "if");
+                assert_eq!((*expected, *got), (3, 2));
+            }
+            other => panic!("expected InvalidArity, got {:?}", other),
+        }
+        let span = err.span().unwrap();
+        assert_eq!((span.start.row, span.start.column), (1, 3));
+        assert_eq!(
+            err.to_string(),
+            "2:4: 'if' requires exactly 3 arguments, got 2"
+        );
+
+        // Well-formed programs compile as with compile_incremental
+        let state = compile_checked("!(if True (quote (if)) 0)", Environment::new()).unwrap();
+        assert_eq!(state.source.len(), 1);
+
+        // Rule heads and match patterns are data, not calls
+        let src = "(= (wrap (if $c)) $c)\n!(match &self (car-atom $x) $x)";
+        assert_eq!(
+            compile_checked(src, Environment::new())
+                .unwrap()
+                .source
+                .len(),
+            2
+        );
+        assert!(compile_checked("(= (f $x) (if $x))", Environment::new()).is_err());
+
+        // Every form in the evaluator's arity table is checked
+        let err = compile_checked("!(if-empty ())", Environment::new()).unwrap_err();
+        assert!(matches!(
+            err,
+            CompileError::InvalidArity {
+                expected: 3,
+                got: 1,
+                ..
+            }
+        ));
+        assert!(compile_checked("!(assertEqual 1)", Environment::new()).is_err());
+
+        // Extra arguments are ignored by the evaluator and accepted here
+        assert!(compile_checked("!(if True 1 2 3)", Environment::new()).is_ok());
+
+        // Lines follow ;#line directives
+        let err = compile_checked(
+            "!(+ 1 2)\n;#line 40 \"orig.metta\"\n!(let $x 1)",
+            Environment::new(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "orig.metta:40:2: 'let' requires exactly 3 arguments, got 2"
+        );
+
+        assert!(matches!(
+            compile_checked("(if", Environment::new()),
+            Err(CompileError::Syntax(_))
+        ));
+    }
+
     #[test]
     fn test_compile_deeply_nested() {
         let src = "(+ 1 (+ 2 (+ 3 (+ 4 5))))";
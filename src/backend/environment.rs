@@ -2,14 +2,427 @@ use lru::LruCache;
 use mork::space::Space;
 use mork_interning::SharedMappingHandle;
 use pathmap::{zipper::*, PathMap};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
 use tracing::{trace, warn};
 
 use super::fuzzy_match::FuzzyMatcher;
-use super::{MettaValue, Rule};
+use super::metrics::{EvalMetrics, MetricsCollector};
+use super::random::Rng;
+use super::{Bindings, HashedValue, MettaValue, Rule};
+use crate::ir::{SExpr, Span};
+
+/// Secondary index key derived from the first argument of a rule LHS or call
+///
+/// Rules whose first argument is a literal are keyed by that literal, and rules whose
+/// first argument is an expression with a symbol head are keyed by (head, length).
+/// A call can only match rules with the same key, plus rules whose first argument is
+/// a variable or has no clear shape (those are never keyed and are always tried).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum FirstArgKey {
+    Literal(MettaValue),
+    Shape(String, usize),
+}
+
+impl FirstArgKey {
+    /// Key for the first argument of a rule LHS pattern (None = must always be tried)
+    fn for_pattern(lhs: &MettaValue) -> Option<Self> {
+        match lhs {
+            MettaValue::SExpr(items) => items.get(1).and_then(Self::for_arg),
+            _ => None,
+        }
+    }
+
+    /// Key for a single argument value or pattern
+    fn for_arg(arg: &MettaValue) -> Option<Self> {
+        match arg {
+            MettaValue::Atom(s) if s.starts_with('$') || s.starts_with('\'') || s == "_" => None,
+            MettaValue::Atom(_)
+            | MettaValue::Bool(_)
+            | MettaValue::Long(_)
+            | MettaValue::Float(_)
+            | MettaValue::String(_)
+            | MettaValue::Nil => Some(FirstArgKey::Literal(arg.clone())),
+            MettaValue::SExpr(items) => arg
+                .get_head_symbol()
+                .map(|head| FirstArgKey::Shape(head.to_owned(), items.len())),
+            _ => None,
+        }
+    }
+}
+
+/// Rules sharing a (head_symbol, arity) key, partitioned by their first argument
+///
+/// `rules` keeps definition order; `by_first_arg` and `unkeyed` hold positions into it,
+/// so a lookup with a constant first argument only visits the rules that can match.
+#[derive(Debug, Clone, Default)]
+struct RuleBucket {
+    rules: Vec<Rule>,
+    by_first_arg: HashMap<FirstArgKey, Vec<usize>>,
+    unkeyed: Vec<usize>,
+}
+
+impl RuleBucket {
+    fn push(&mut self, rule: Rule) {
+        let pos = self.rules.len();
+        match FirstArgKey::for_pattern(&rule.lhs) {
+            Some(key) => self.by_first_arg.entry(key).or_default().push(pos),
+            None => self.unkeyed.push(pos),
+        }
+        self.rules.push(rule);
+    }
+
+    fn append(&mut self, rules: &mut Vec<Rule>) {
+        for rule in rules.drain(..) {
+            self.push(rule);
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.rules.len()
+    }
+
+    fn iter(&self) -> std::slice::Iter<'_, Rule> {
+        self.rules.iter()
+    }
+
+    /// Rules that may match a call whose first argument is `first_arg`, in definition order
+    fn candidates(&self, first_arg: Option<&MettaValue>) -> Vec<&Rule> {
+        let Some(arg) = first_arg else {
+            return self.rules.iter().collect();
+        };
+        let keyed: &[usize] = FirstArgKey::for_arg(arg)
+            .and_then(|key| self.by_first_arg.get(&key))
+            .map_or(&[], |positions| positions.as_slice());
+
+        // Merge the two sorted position lists to preserve definition order
+        let mut result = Vec::with_capacity(keyed.len() + self.unkeyed.len());
+        let (mut i, mut j) = (0, 0);
+        while i < keyed.len() || j < self.unkeyed.len() {
+            let take_keyed =
+                j >= self.unkeyed.len() || (i < keyed.len() && keyed[i] < self.unkeyed[j]);
+            if take_keyed {
+                result.push(&self.rules[keyed[i]]);
+                i += 1;
+            } else {
+                result.push(&self.rules[self.unkeyed[j]]);
+                j += 1;
+            }
+        }
+        result
+    }
+}
+
+/// Number of `collapse` results kept by the collapse cache
+const COLLAPSE_CACHE_SIZE: usize = 256;
+
+/// Collapse cache key: the collapsed expression, the addresses of the space and rule
+/// data (so environments that have diverged never share entries), and the call and
+/// `eval` depth left before the limits (so results are only reused where evaluating
+/// again would run into the same limits)
+type CollapseKey = (HashedValue, usize, usize, usize, usize, usize);
+
+/// Default limit on nested `eval` calls, chosen to stay well within the stack of a
+/// default-sized thread
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 512;
+
+/// Default limit on nested `(eval ...)` forms
+pub const DEFAULT_MAX_EVAL_DEPTH: usize = 64;
+
+/// Receives rule diagnostics one line at a time: rule applications when rule tracing
+/// is on (see `Environment::set_rule_trace`) and duplicate rule definitions (see
+/// `Environment::set_duplicate_rule_warnings`)
+pub type RuleTraceSink = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// Mutable state cells created by `new-state`
+///
+/// Each cell remembers the type its value had when it was created; with strict typing
+/// (the default) `change-state!` may only store values of that same type.
+#[derive(Debug, Clone)]
+struct StateStore {
+    cells: HashMap<u64, (MettaValue, &'static str)>,
+    strict_types: bool,
+}
+
+impl Default for StateStore {
+    fn default() -> Self {
+        StateStore {
+            cells: HashMap::new(),
+            strict_types: true,
+        }
+    }
+}
+
+/// Id of the next state cell created by `Environment::new_state`, in any environment
+static NEXT_STATE_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Spaces created by `new-space`, each holding its atoms in insertion order
+#[derive(Debug, Clone, Default)]
+struct SpaceStore {
+    atoms: HashMap<u64, Vec<MettaValue>>,
+    next_id: u64,
+}
+
+/// State cells as they stood when evaluation forked into several branches
+///
+/// The branches of a nondeterministic fork run one after another against the same
+/// state cells. Ending a branch records its changes and puts the cells back, so each
+/// sibling starts from the state at the fork; joining then applies the recorded
+/// changes in branch order, a later branch winning where several changed one cell.
+#[derive(Debug)]
+pub(crate) struct StateFork {
+    origin: HashMap<u64, (MettaValue, &'static str)>,
+    changes: HashMap<u64, (MettaValue, &'static str)>,
+}
+
+/// Saved contents of an environment, taken by `Environment::snapshot` and put back
+/// by `Environment::restore`
+///
+/// The snapshot shares the environment's data stores rather than copying them; the
+/// environment copies a store the next time it writes to it. State cells are small
+/// and mutated in place by every clone, so they are copied eagerly.
+#[derive(Clone)]
+pub struct EnvSnapshot {
+    btm: Arc<RwLock<PathMap<()>>>,
+    #[allow(clippy::type_complexity)]
+    rule_index: Arc<RwLock<HashMap<(String, usize), RuleBucket>>>,
+    wildcard_rules: Arc<RwLock<Vec<Rule>>>,
+    multiplicities: Arc<RwLock<HashMap<String, usize>>>,
+    pattern_cache: Arc<RwLock<LruCache<MettaValue, Vec<u8>>>>,
+    fuzzy_matcher: FuzzyMatcher,
+    type_index: Arc<RwLock<Option<PathMap<()>>>>,
+    type_index_dirty: Arc<RwLock<bool>>,
+    module_path: Arc<RwLock<Option<PathBuf>>>,
+    loaded_modules: Arc<RwLock<HashMap<PathBuf, ModuleLoadState>>>,
+    docs: Arc<RwLock<HashMap<String, String>>>,
+    tokens: Arc<RwLock<HashMap<String, MettaValue>>>,
+    states: StateStore,
+    spaces: SpaceStore,
+}
+
+impl std::fmt::Debug for EnvSnapshot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EnvSnapshot")
+            .field("space", &"<MORK Space>")
+            .field("states", &self.states.cells.len())
+            .field("spaces", &self.spaces.atoms.len())
+            .finish()
+    }
+}
+
+/// Type name used to keep state cells consistently typed, mirroring hyperon's `State`
+/// (Number, String, Bool, Symbol, Expression, ...)
+pub fn state_value_type(value: &MettaValue) -> &'static str {
+    match value {
+        MettaValue::Bool(_) => "Bool",
+        MettaValue::Long(_) | MettaValue::Float(_) => "Number",
+        MettaValue::String(_) => "String",
+        MettaValue::Atom(_) => "Symbol",
+        MettaValue::SExpr(_) | MettaValue::Nil | MettaValue::Conjunction(_) => "Expression",
+        MettaValue::Error(_, _) => "Error",
+        MettaValue::Type(_) => "Type",
+    }
+}
+
+/// Call that does not fit its function's `(-> ...)` signature, found by
+/// `Environment::type_check`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeMismatch {
+    /// Function whose signature the call violates
+    pub function: String,
+    /// What is wrong with the call
+    pub message: String,
+    /// Span of the offending call, or of the offending argument
+    pub span: Span,
+}
+
+impl std::fmt::Display for TypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.span.start, self.message)
+    }
+}
+
+/// Check the calls in `expr` against `signature`, outermost first, for `type_check`
+fn check_call_types(
+    expr: &SExpr,
+    signature: &dyn Fn(&str) -> Option<MettaValue>,
+    mismatches: &mut Vec<TypeMismatch>,
+) {
+    let SExpr::List(items, span) = expr else {
+        return;
+    };
+    let Some(SExpr::Atom(op, _)) = items.first() else {
+        items
+            .iter()
+            .for_each(|item| check_call_types(item, signature, mismatches));
+        return;
+    };
+    match (op.as_str(), items.len()) {
+        ("quote", _) | (":", _) => return,
+        ("=", 3) => return check_call_types(&items[2], signature, mismatches),
+        _ => {}
+    }
+
+    if let Some(MettaValue::SExpr(sig)) = signature(op) {
+        let params = &sig[1..sig.len() - 1];
+        let args = &items[1..];
+        if args.len() != params.len() {
+            mismatches.push(TypeMismatch {
+                function: op.clone(),
+                message: format!(
+                    "'{}' expects {} argument{}, got {}",
+                    op,
+                    params.len(),
+                    if params.len() == 1 { "" } else { "s" },
+                    args.len()
+                ),
+                span: span.unwrap_or_else(Span::zero),
+            });
+        } else {
+            for (i, (arg, param)) in args.iter().zip(params).enumerate() {
+                let (Some(found), MettaValue::Atom(expected)) = (literal_type(arg), param) else {
+                    continue;
+                };
+                if matches!(expected.as_str(), "Number" | "String" | "Bool") && expected != found {
+                    mismatches.push(TypeMismatch {
+                        function: op.clone(),
+                        message: format!(
+                            "argument {} of '{}' should be {}, got {} {}",
+                            i + 1,
+                            op,
+                            expected,
+                            found,
+                            arg
+                        ),
+                        span: arg.span().unwrap_or_else(Span::zero),
+                    });
+                }
+            }
+        }
+    }
+
+    items[1..]
+        .iter()
+        .for_each(|item| check_call_types(item, signature, mismatches));
+}
+
+/// Type of a literal expression, or None if `expr` is not a literal
+fn literal_type(expr: &SExpr) -> Option<&'static str> {
+    match expr {
+        SExpr::Integer(_, _) | SExpr::Float(_, _) => Some("Number"),
+        SExpr::String(_, _) => Some("String"),
+        SExpr::Atom(name, _) if name == "True" || name == "False" => Some("Bool"),
+        _ => None,
+    }
+}
+
+/// Size summary of an environment, returned by `Environment::stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EnvStats {
+    /// Rules defined with `=`, counting each definition of a multiply-defined rule
+    pub rules: usize,
+    /// Rules without a head symbol, which are tried against every call
+    pub wildcard_rules: usize,
+    /// Atoms stored in the space that are not rules (facts and type assertions)
+    pub facts: usize,
+    /// Total atoms stored in the PathMap space, rules included
+    pub atoms: usize,
+    /// Symbols with documentation attached via `@doc`
+    pub documented_symbols: usize,
+    /// Modules loaded via `import!`
+    pub loaded_modules: usize,
+    /// State cells created with `new-state`
+    pub states: usize,
+    /// Tokens bound with `bind!`
+    pub tokens: usize,
+    /// Spaces created with `new-space`
+    pub named_spaces: usize,
+}
+
+impl EnvStats {
+    /// Named counts in a fixed order, as used by `(env-stats)` and the REPL
+    pub fn entries(&self) -> [(&'static str, usize); 9] {
+        [
+            ("rules", self.rules),
+            ("wildcard-rules", self.wildcard_rules),
+            ("facts", self.facts),
+            ("atoms", self.atoms),
+            ("documented-symbols", self.documented_symbols),
+            ("loaded-modules", self.loaded_modules),
+            ("states", self.states),
+            ("tokens", self.tokens),
+            ("named-spaces", self.named_spaces),
+        ]
+    }
+
+    /// Convert to an S-expression of `(name count)` pairs
+    pub fn to_metta_value(&self) -> MettaValue {
+        MettaValue::SExpr(
+            self.entries()
+                .iter()
+                .map(|(name, count)| {
+                    MettaValue::SExpr(vec![
+                        MettaValue::Atom(name.to_string()),
+                        MettaValue::Long(*count as i64),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Approximate per-symbol overhead of the intern table: the 8-byte id is stored
+/// in both the string -> id and id -> string maps
+const INTERN_ENTRY_OVERHEAD: usize = 16;
+
+/// Symbol interning summary, returned by `Environment::intern_stats`
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct InternStats {
+    /// Distinct interned symbols referenced by atoms in the space
+    pub symbols: usize,
+    /// Total length of those symbols' names in bytes
+    pub symbol_bytes: usize,
+    /// Approximate memory held by the intern table for those symbols
+    pub approx_bytes: usize,
+}
+
+impl InternStats {
+    /// Named counts in a fixed order, as used by `(intern-stats)` and the REPL
+    pub fn entries(&self) -> [(&'static str, usize); 3] {
+        [
+            ("symbols", self.symbols),
+            ("symbol-bytes", self.symbol_bytes),
+            ("approx-bytes", self.approx_bytes),
+        ]
+    }
+
+    /// Convert to an S-expression of `(name count)` pairs
+    pub fn to_metta_value(&self) -> MettaValue {
+        MettaValue::SExpr(
+            self.entries()
+                .iter()
+                .map(|(name, count)| {
+                    MettaValue::SExpr(vec![
+                        MettaValue::Atom(name.to_string()),
+                        MettaValue::Long(*count as i64),
+                    ])
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Load state of a module imported via `import!`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleLoadState {
+    /// The module is currently being evaluated (seeing it again means a circular import)
+    Loading,
+    /// The module has been fully evaluated into the environment
+    Loaded,
+}
 
 /// The environment contains the fact database and type assertions
 /// All facts (rules, atoms, s-expressions, type assertions) are stored in MORK PathMap
@@ -39,12 +452,13 @@ pub struct Environment {
     /// RwLock allows concurrent reads (multiple threads can read simultaneously)
     btm: Arc<RwLock<PathMap<()>>>,
 
-    /// Rule index: Maps (head_symbol, arity) -> Vec<Rule> for O(1) rule lookup
+    /// Rule index: Maps (head_symbol, arity) -> rules for O(1) rule lookup
     /// This enables O(k) rule matching where k = rules with matching head symbol
     /// Instead of O(n) iteration through all rules
+    /// Each bucket is further partitioned by the first argument (see RuleBucket)
     /// RwLock allows concurrent reads for parallel rule matching
     #[allow(clippy::type_complexity)]
-    rule_index: Arc<RwLock<HashMap<(String, usize), Vec<Rule>>>>,
+    rule_index: Arc<RwLock<HashMap<(String, usize), RuleBucket>>>,
 
     /// Wildcard rules: Rules without a clear head symbol (e.g., variable patterns, wildcards)
     /// These rules must be checked against all queries
@@ -79,6 +493,73 @@ pub struct Environment {
     /// Causes type_index to be rebuilt on next get_type() call
     /// RwLock allows concurrent checks of dirty flag
     type_index_dirty: Arc<RwLock<bool>>,
+
+    /// Module path: directory used to resolve relative `import!` paths
+    /// None means paths are resolved against the current working directory
+    module_path: Arc<RwLock<Option<PathBuf>>>,
+
+    /// Loaded modules: canonical file path -> load state for `import!`
+    /// Used to skip repeated imports and to detect circular imports
+    loaded_modules: Arc<RwLock<HashMap<PathBuf, ModuleLoadState>>>,
+
+    /// Documentation strings: symbol -> description attached via `(@doc symbol "description")`
+    docs: Arc<RwLock<HashMap<String, String>>>,
+
+    /// Tokens registered with `(bind! &name value)`: token -> value
+    /// An atom spelled like a bound token evaluates to its value
+    tokens: Arc<RwLock<HashMap<String, MettaValue>>>,
+
+    /// Random number generator for `random-int` / `random-float`
+    /// Deliberately shared by all clones (never deep-copied in make_owned), so that
+    /// parallel branches and sub-expression environments draw from one sequence
+    /// instead of replaying the same numbers
+    rng: Arc<RwLock<Rng>>,
+
+    /// State cells created by `new-state`
+    /// Shared by clones: a state is a mutable reference, so a change is seen through
+    /// every environment holding the reference, until an environment takes ownership
+    /// of its data (`make_owned`) and so stops sharing with its clones. Sibling
+    /// branches of a nondeterministic fork are isolated with `StateFork`.
+    states: Arc<RwLock<StateStore>>,
+
+    /// Spaces created by `new-space`
+    /// Shared by clones like `states`: a space handle is a reference, so atoms added
+    /// through one environment are seen by every environment sharing the store.
+    spaces: Arc<RwLock<SpaceStore>>,
+
+    /// Count of side effects (space, rule, state and random-generator changes)
+    /// Shared by all clones; an evaluation during which it does not change is pure
+    effects: Arc<AtomicU64>,
+
+    /// Results of pure `collapse` evaluations, keyed on the expression and the identity
+    /// of the environment data it ran against, tagged with the effect count at the time
+    collapse_cache: Arc<RwLock<LruCache<CollapseKey, (u64, Vec<MettaValue>)>>>,
+
+    /// Interrupt flag checked on every evaluation step; setting it aborts the
+    /// running evaluation with an "Interrupted" error
+    /// Shared by all clones so a flag installed before evaluation reaches every branch
+    interrupt: Arc<AtomicBool>,
+
+    /// Maximum number of nested `eval` calls before evaluation fails with a
+    /// recoverable error instead of exhausting the native stack
+    max_call_depth: usize,
+
+    /// Maximum number of nested `(eval ...)` forms, counted separately from
+    /// `max_call_depth` since each one re-enters evaluation on a computed expression
+    max_eval_depth: usize,
+
+    /// Maximum number of results `collapse` may gather; None means unlimited
+    max_collapse_results: Option<usize>,
+
+    /// Where rule applications are reported, if rule tracing is on
+    rule_trace: Option<RuleTraceSink>,
+
+    /// Where a rule defined again with the identical `(= lhs rhs)` is reported
+    duplicate_rule_warnings: Option<RuleTraceSink>,
+
+    /// Execution metrics collector (disabled by default)
+    /// Shared by all clones like `rng`, so nested and forked evaluations report together
+    metrics: Arc<MetricsCollector>,
 }
 
 impl Environment {
@@ -97,6 +578,24 @@ impl Environment {
             fuzzy_matcher: FuzzyMatcher::new(),
             type_index: Arc::new(RwLock::new(None)),
             type_index_dirty: Arc::new(RwLock::new(true)),
+            module_path: Arc::new(RwLock::new(None)),
+            loaded_modules: Arc::new(RwLock::new(HashMap::new())),
+            docs: Arc::new(RwLock::new(HashMap::new())),
+            tokens: Arc::new(RwLock::new(HashMap::new())),
+            rng: Arc::new(RwLock::new(Rng::from_entropy())),
+            states: Arc::new(RwLock::new(StateStore::default())),
+            spaces: Arc::new(RwLock::new(SpaceStore::default())),
+            effects: Arc::new(AtomicU64::new(0)),
+            collapse_cache: Arc::new(RwLock::new(LruCache::new(
+                NonZeroUsize::new(COLLAPSE_CACHE_SIZE).unwrap(),
+            ))),
+            interrupt: Arc::new(AtomicBool::new(false)),
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_eval_depth: DEFAULT_MAX_EVAL_DEPTH,
+            max_collapse_results: None,
+            rule_trace: None,
+            duplicate_rule_warnings: None,
+            metrics: Arc::new(MetricsCollector::new()),
         }
     }
 
@@ -110,7 +609,7 @@ impl Environment {
         }
         trace!(target: "mettatron::environment::make_owned", "Deep copying CoW data");
 
-        // Deep copy all 7 RwLock-wrapped fields
+        // Deep copy all 13 RwLock-wrapped fields
         // Clone the data first to avoid borrowing issues
         let btm_data = self.btm.read().unwrap().clone();
         let rule_index_data = self.rule_index.read().unwrap().clone();
@@ -119,6 +618,12 @@ impl Environment {
         let pattern_cache_data = self.pattern_cache.read().unwrap().clone();
         let type_index_data = self.type_index.read().unwrap().clone();
         let type_index_dirty_data = *self.type_index_dirty.read().unwrap();
+        let module_path_data = self.module_path.read().unwrap().clone();
+        let loaded_modules_data = self.loaded_modules.read().unwrap().clone();
+        let docs_data = self.docs.read().unwrap().clone();
+        let tokens_data = self.tokens.read().unwrap().clone();
+        let states_data = self.states.read().unwrap().clone();
+        let spaces_data = self.spaces.read().unwrap().clone();
 
         // Now assign the new Arc<RwLock<T>> instances
         self.btm = Arc::new(RwLock::new(btm_data));
@@ -128,12 +633,113 @@ impl Environment {
         self.pattern_cache = Arc::new(RwLock::new(pattern_cache_data));
         self.type_index = Arc::new(RwLock::new(type_index_data));
         self.type_index_dirty = Arc::new(RwLock::new(type_index_dirty_data));
+        self.module_path = Arc::new(RwLock::new(module_path_data));
+        self.loaded_modules = Arc::new(RwLock::new(loaded_modules_data));
+        self.docs = Arc::new(RwLock::new(docs_data));
+        self.tokens = Arc::new(RwLock::new(tokens_data));
+        self.states = Arc::new(RwLock::new(states_data));
+        self.spaces = Arc::new(RwLock::new(spaces_data));
 
         // Mark as owning data and modified
         self.owns_data = true;
         self.modified.store(true, Ordering::Release);
     }
 
+    /// Remove every rule, fact, type, doc, bound token, loaded module, state cell and
+    /// space created by `new-space`,
+    /// returning the environment to the contents of `Environment::new()`
+    ///
+    /// Configuration is kept: call and eval depth limits, trace and warning sinks,
+    /// the module path, the random generator, the interrupt flag, metrics and the
+    /// symbol table. Clones sharing data with this environment are unaffected, since
+    /// the emptied stores replace the shared ones here instead of being cleared in place.
+    pub fn clear(&mut self) {
+        trace!(target: "mettatron::environment::clear", "Clearing environment");
+        self.note_effect();
+
+        let strict_state_types = self.states.read().unwrap().strict_types;
+        let pattern_cache_size = self.pattern_cache.read().unwrap().cap();
+
+        self.btm = Arc::new(RwLock::new(PathMap::new()));
+        self.rule_index = Arc::new(RwLock::new(HashMap::new()));
+        self.wildcard_rules = Arc::new(RwLock::new(Vec::new()));
+        self.multiplicities = Arc::new(RwLock::new(HashMap::new()));
+        self.pattern_cache = Arc::new(RwLock::new(LruCache::new(pattern_cache_size)));
+        self.fuzzy_matcher = FuzzyMatcher::new();
+        self.type_index = Arc::new(RwLock::new(None));
+        self.type_index_dirty = Arc::new(RwLock::new(true));
+        self.loaded_modules = Arc::new(RwLock::new(HashMap::new()));
+        self.docs = Arc::new(RwLock::new(HashMap::new()));
+        self.tokens = Arc::new(RwLock::new(HashMap::new()));
+        self.states = Arc::new(RwLock::new(StateStore {
+            strict_types: strict_state_types,
+            ..StateStore::default()
+        }));
+        self.spaces = Arc::new(RwLock::new(SpaceStore::default()));
+        self.collapse_cache = Arc::new(RwLock::new(LruCache::new(
+            NonZeroUsize::new(COLLAPSE_CACHE_SIZE).unwrap(),
+        )));
+
+        self.owns_data = true;
+        self.modified.store(true, Ordering::Release);
+    }
+
+    /// Capture rules, facts, types, docs, bound tokens, loaded modules, state cells and
+    /// `new-space` spaces so a later `restore` can roll back everything done in between
+    ///
+    /// Takes `&mut self` because the environment gives up ownership of its stores:
+    /// the next write copies the store instead of changing the one the snapshot holds.
+    /// Configuration (depth limits, sinks, random generator, metrics) is not captured.
+    pub fn snapshot(&mut self) -> EnvSnapshot {
+        trace!(target: "mettatron::environment::snapshot", "Taking snapshot");
+        self.owns_data = false;
+        EnvSnapshot {
+            btm: Arc::clone(&self.btm),
+            rule_index: Arc::clone(&self.rule_index),
+            wildcard_rules: Arc::clone(&self.wildcard_rules),
+            multiplicities: Arc::clone(&self.multiplicities),
+            pattern_cache: Arc::clone(&self.pattern_cache),
+            fuzzy_matcher: self.fuzzy_matcher.clone(),
+            type_index: Arc::clone(&self.type_index),
+            type_index_dirty: Arc::clone(&self.type_index_dirty),
+            module_path: Arc::clone(&self.module_path),
+            loaded_modules: Arc::clone(&self.loaded_modules),
+            docs: Arc::clone(&self.docs),
+            tokens: Arc::clone(&self.tokens),
+            states: self.states.read().unwrap().clone(),
+            spaces: self.spaces.read().unwrap().clone(),
+        }
+    }
+
+    /// Roll the environment back to `snapshot`, discarding every rule, fact and
+    /// state change made since it was taken
+    ///
+    /// State cells and spaces are restored in place, so clones sharing them see the
+    /// rollback.
+    pub fn restore(&mut self, snapshot: EnvSnapshot) {
+        trace!(target: "mettatron::environment::restore", "Restoring snapshot");
+        self.note_effect();
+
+        self.btm = snapshot.btm;
+        self.rule_index = snapshot.rule_index;
+        self.wildcard_rules = snapshot.wildcard_rules;
+        self.multiplicities = snapshot.multiplicities;
+        self.pattern_cache = snapshot.pattern_cache;
+        self.fuzzy_matcher = snapshot.fuzzy_matcher;
+        self.type_index = snapshot.type_index;
+        self.type_index_dirty = snapshot.type_index_dirty;
+        self.module_path = snapshot.module_path;
+        self.loaded_modules = snapshot.loaded_modules;
+        self.docs = snapshot.docs;
+        self.tokens = snapshot.tokens;
+        *self.states.write().unwrap() = snapshot.states;
+        *self.spaces.write().unwrap() = snapshot.spaces;
+
+        // The restored stores may still be shared with other snapshots
+        self.owns_data = false;
+        self.modified.store(true, Ordering::Release);
+    }
+
     /// Create a thread-local Space for operations
     /// Following the Rholang LSP pattern: cheap clone via structural sharing
     ///
@@ -151,6 +757,7 @@ impl Environment {
     /// Update PathMap and shared mapping after Space modifications (write operations)
     /// This updates both the PathMap (btm) and the SharedMappingHandle (sm)
     pub(crate) fn update_pathmap(&mut self, space: Space) {
+        self.note_effect();
         self.make_owned(); // CoW: ensure we own data before modifying
         *self.btm.write().unwrap() = space.btm; // CoW: write lock for exclusive access
         self.shared_mapping = space.sm;
@@ -386,6 +993,7 @@ impl Environment {
     /// Invalidates the type index cache
     pub fn add_type(&mut self, name: String, typ: MettaValue) {
         trace!(target: "mettatron::environment::add_type", name, ?typ);
+        self.note_effect();
         self.make_owned(); // CoW: ensure we own data before modifying
 
         // Create type assertion: (: name typ)
@@ -514,6 +1122,59 @@ impl Environment {
         self.get_type_linear(name)
     }
 
+    /// Get every type asserted for an atom
+    ///
+    /// Assertions accumulate: after `(: x Number)` and `(: x Symbol)` both types are
+    /// returned, in space order. Empty if the name has no assertion.
+    pub fn get_types(&self, name: &str) -> Vec<MettaValue> {
+        trace!(target: "mettatron::environment::get_types", name);
+        let type_var = MettaValue::Atom("$type".to_string());
+        let pattern = MettaValue::SExpr(vec![
+            MettaValue::Atom(":".to_string()),
+            MettaValue::Atom(name.to_string()),
+            type_var.clone(),
+        ]);
+        self.match_space(&pattern, &type_var)
+    }
+
+    /// Check every call in `forms` against its function's declared signature
+    ///
+    /// Signatures come from top-level `(: name (-> ...))` forms in `forms` and from
+    /// type assertions already in the environment. A call with the wrong number of
+    /// arguments is reported, as is a literal argument whose type (`Number`, `String`
+    /// or `Bool`) differs from the declared parameter type. Parameters typed with a
+    /// variable or any other type are not checked. Quoted code, type assertions and
+    /// rule left-hand sides are skipped. Mismatches are returned in source order.
+    pub fn type_check(&self, forms: &[SExpr]) -> Vec<TypeMismatch> {
+        trace!(target: "mettatron::environment::type_check", forms = forms.len());
+        let mut declared = HashMap::new();
+        for form in forms {
+            if let SExpr::List(items, _) = form {
+                if let [SExpr::Atom(colon, _), SExpr::Atom(name, _), sig] = &items[..] {
+                    if let (":", Ok(sig)) = (colon.as_str(), MettaValue::try_from(sig)) {
+                        declared.insert(name.clone(), sig);
+                    }
+                }
+            }
+        }
+
+        let signature = |name: &str| {
+            declared
+                .get(name)
+                .cloned()
+                .or_else(|| self.get_type(name))
+                .filter(|sig| {
+                    matches!(sig, MettaValue::SExpr(items)
+                        if items.len() >= 2 && items[0] == MettaValue::Atom("->".to_string()))
+                })
+        };
+        let mut mismatches = Vec::new();
+        for form in forms {
+            check_call_types(form, &signature, &mut mismatches);
+        }
+        mismatches
+    }
+
     /// Linear search fallback for get_type() - O(n) iteration
     /// Used when exact match via descend_to_check() fails
     fn get_type_linear(&self, name: &str) -> Option<MettaValue> {
@@ -551,6 +1212,91 @@ impl Environment {
         None
     }
 
+    /// Summarize the size of the environment
+    /// Rule counts come from the rule index; atoms are counted by walking the PathMap
+    pub fn stats(&self) -> EnvStats {
+        let rules_in_index: usize = self
+            .rule_index
+            .read()
+            .unwrap()
+            .values()
+            .map(|b| b.len())
+            .sum();
+        let wildcard_rules = self.wildcard_rules.read().unwrap().len();
+
+        let atoms = {
+            let btm = self.btm.read().unwrap();
+            let mut rz = btm.read_zipper();
+            let mut count = 0;
+            while rz.to_next_val() {
+                count += 1;
+            }
+            count
+        };
+
+        // The space stores each distinct rule once, keyed like the multiplicities
+        let distinct_rules = self.multiplicities.read().unwrap().len();
+
+        EnvStats {
+            rules: rules_in_index + wildcard_rules,
+            wildcard_rules,
+            facts: atoms.saturating_sub(distinct_rules),
+            atoms,
+            documented_symbols: self.docs.read().unwrap().len(),
+            loaded_modules: self.loaded_modules.read().unwrap().len(),
+            states: self.states.read().unwrap().cells.len(),
+            tokens: self.tokens.read().unwrap().len(),
+            named_spaces: self.spaces.read().unwrap().atoms.len(),
+        }
+    }
+
+    /// Summarize symbol interning for the atoms currently in the space
+    /// Walks the PathMap collecting distinct symbol ids and resolves each one through
+    /// the SharedMappingHandle to measure its name. Symbols stay interned after the
+    /// atoms using them are removed, so this is a lower bound on the table's size.
+    pub fn intern_stats(&self) -> InternStats {
+        use mork_expr::{maybe_byte_item, Tag};
+
+        let btm = self.btm.read().unwrap();
+        let mut rz = btm.read_zipper();
+        let mut seen: HashSet<Vec<u8>> = HashSet::new();
+        let mut symbol_bytes = 0;
+        while rz.to_next_val() {
+            let path = rz.path();
+            let mut offset = 0;
+            while offset < path.len() {
+                let tag = maybe_byte_item(path[offset]);
+                offset += 1;
+                if let Ok(Tag::SymbolSize(size)) = tag {
+                    let end = (offset + size as usize).min(path.len());
+                    let symbol = &path[offset..end];
+                    offset = end;
+                    if seen.insert(symbol.to_vec()) {
+                        symbol_bytes += self.interned_symbol_len(symbol);
+                    }
+                }
+            }
+        }
+
+        InternStats {
+            symbols: seen.len(),
+            symbol_bytes,
+            approx_bytes: symbol_bytes + seen.len() * INTERN_ENTRY_OVERHEAD,
+        }
+    }
+
+    /// Length of the name behind a symbol as stored in the space
+    /// With interning the stored bytes are an 8-byte id resolved through the symbol table
+    fn interned_symbol_len(&self, symbol: &[u8]) -> usize {
+        #[cfg(feature = "interning")]
+        if let Ok(id) = <[u8; 8]>::try_from(symbol) {
+            if let Some(bytes) = self.shared_mapping.get_bytes(id) {
+                return bytes.len();
+            }
+        }
+        symbol.len()
+    }
+
     /// Get the number of rules in the environment
     /// Counts rules directly from PathMap Space
     pub fn rule_count(&self) -> usize {
@@ -558,17 +1304,23 @@ impl Environment {
     }
 
     /// Iterator over all rules in the Space
-    /// Rules are stored as MORK s-expressions: (= lhs rhs)
+    /// Rules are stored as MORK s-expressions: (= lhs rhs) or (= lhs rhs where guard)
+    pub fn iter_rules(&self) -> impl Iterator<Item = Rule> {
+        self.space_atoms()
+            .into_iter()
+            .filter_map(|value| Rule::from_sexpr(&value))
+    }
+
+    /// Every atom in the Space, in trie order
     ///
     /// Uses direct zipper traversal to avoid dump/parse overhead.
     /// This provides O(n) iteration without string serialization.
-    #[allow(clippy::collapsible_match)]
-    pub fn iter_rules(&self) -> impl Iterator<Item = Rule> {
+    fn space_atoms(&self) -> Vec<MettaValue> {
         use mork_expr::Expr;
 
         let space = self.create_space();
         let mut rz = space.btm.read_zipper();
-        let mut rules = Vec::new();
+        let mut atoms = Vec::new();
 
         // Directly iterate through all values in the trie
         while rz.to_next_val() {
@@ -580,30 +1332,65 @@ impl Environment {
             // FIXED: Use mork_expr_to_metta_value() instead of serialize2-based conversion
             // This avoids the "reserved byte" panic during evaluation
             if let Ok(value) = Self::mork_expr_to_metta_value(&expr, &space) {
-                if let MettaValue::SExpr(items) = &value {
-                    if items.len() == 3 {
-                        if let MettaValue::Atom(op) = &items[0] {
-                            if op == "=" {
-                                rules.push(Rule {
-                                    lhs: items[1].clone(),
-                                    rhs: items[2].clone(),
-                                });
-                            }
-                        }
-                    }
-                }
+                atoms.push(value);
             }
         }
 
         drop(space);
-        rules.into_iter()
+        atoms
+    }
+
+    /// Write the environment's knowledge back out as MeTTa source
+    ///
+    /// Type assertions come first, then the remaining facts, then rules ordered by head
+    /// symbol and arity (rules without a head symbol last). Facts precede rules so that
+    /// reloading adds them as data instead of calling the rules on them. Each atom is on
+    /// its own line, so loading the text into a fresh environment restores the same
+    /// rules, facts and types. Runtime-only data such as states and `bind!` tokens is
+    /// not included.
+    pub fn to_metta_source(&self) -> String {
+        trace!(target: "mettatron::environment::to_metta_source");
+        let is_form = |value: &MettaValue, op: &str| {
+            matches!(value, MettaValue::SExpr(items)
+                if items.len() == 3 && matches!(&items[0], MettaValue::Atom(head) if head == op))
+        };
+        let (types, facts): (Vec<MettaValue>, Vec<MettaValue>) = self
+            .space_atoms()
+            .into_iter()
+            .filter(|atom| Rule::from_sexpr(atom).is_none())
+            .partition(|atom| is_form(atom, ":"));
+
+        let rules: Vec<Rule> = {
+            let index = self.rule_index.read().unwrap();
+            let wildcards = self.wildcard_rules.read().unwrap();
+            let mut keys: Vec<&(String, usize)> = index.keys().collect();
+            keys.sort();
+            keys.into_iter()
+                .flat_map(|key| index[key].iter().cloned())
+                .chain(wildcards.iter().cloned())
+                .collect()
+        };
+
+        let mut source = String::new();
+        for atom in &types {
+            source.push_str(&format!("{}\n", atom));
+        }
+        for atom in &facts {
+            source.push_str(&format!("{}\n", atom));
+        }
+        for rule in &rules {
+            source.push_str(&format!("{}\n", rule.to_sexpr()));
+        }
+        source
     }
 
     /// Rebuild the rule index from the MORK Space
     /// This is needed after deserializing an Environment from PathMap Par,
     /// since the serialization only preserves the MORK Space, not the index.
+    /// The rebuilt index orders rules as the Space stores them, not by definition.
     pub fn rebuild_rule_index(&mut self) {
         trace!(target: "mettatron::environment::rebuild_rule_index", "Rebuilding rule index");
+        self.note_effect();
         self.make_owned(); // CoW: ensure we own data before modifying
 
         // Clear existing indices
@@ -649,6 +1436,20 @@ impl Environment {
     /// Vector of instantiated templates (MettaValue) for all matches
     pub fn match_space(&self, pattern: &MettaValue, template: &MettaValue) -> Vec<MettaValue> {
         trace!(target: "mettatron::environment::match_space", ?pattern, ?template);
+        self.match_space_with_bindings(pattern, template)
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect()
+    }
+
+    /// Like [`match_space`](Self::match_space), but also returns the bindings that
+    /// produced each instantiated template (used by `collapse-bind`)
+    pub fn match_space_with_bindings(
+        &self,
+        pattern: &MettaValue,
+        template: &MettaValue,
+    ) -> Vec<(MettaValue, Bindings)> {
+        trace!(target: "mettatron::environment::match_space_with_bindings", ?pattern, ?template);
         use crate::backend::eval::{apply_bindings, pattern_match};
         use mork_expr::Expr;
 
@@ -670,7 +1471,7 @@ impl Environment {
                 if let Some(bindings) = pattern_match(pattern, &atom) {
                     // Apply bindings to the template
                     let instantiated = apply_bindings(template, &bindings);
-                    results.push(instantiated);
+                    results.push((instantiated, bindings));
                 }
             }
         }
@@ -685,25 +1486,26 @@ impl Environment {
     /// Rules are also indexed by (head_symbol, arity) for fast lookup
     pub fn add_rule(&mut self, rule: Rule) {
         trace!(target: "mettatron::environment::add_rule", ?rule);
+        self.note_effect();
         self.make_owned(); // CoW: ensure we own data before modifying
 
-        // Create a rule s-expression: (= lhs rhs)
-        let rule_sexpr = MettaValue::SExpr(vec![
-            MettaValue::Atom("=".to_string()),
-            rule.lhs.clone(),
-            rule.rhs.clone(),
-        ]);
+        // Create a rule s-expression: (= lhs rhs) or (= lhs rhs where guard)
+        let rule_sexpr = rule.to_sexpr();
 
         // Generate a canonical key for the rule
         // Use MORK string format for readable serialization
         let rule_key = rule_sexpr.to_mork_string();
 
         // Increment the count for this rule
-        {
+        let new_count = {
             let mut counts = self.multiplicities.write().unwrap();
             let new_count = *counts.entry(rule_key.clone()).or_insert(0) + 1;
             counts.insert(rule_key.clone(), new_count);
-        } // Drop the RefMut borrow before add_to_space
+            new_count
+        }; // Drop the RefMut borrow before add_to_space
+        if new_count > 1 {
+            self.warn_duplicate_rule(&rule_sexpr, new_count);
+        }
 
         // Add to rule index for O(k) lookup
         // Note: We store the rule only ONCE (in either index or wildcard list)
@@ -737,6 +1539,7 @@ impl Environment {
     /// Complexity: O(k) where k = batch size (vs O(n × lock) for individual adds)
     pub fn add_rules_bulk(&mut self, rules: Vec<Rule>) -> Result<(), String> {
         trace!(target: "mettatron::environment::add_rules_bulk", rule_count = rules.len());
+        self.note_effect();
         if rules.is_empty() {
             return Ok(());
         }
@@ -750,17 +1553,20 @@ impl Environment {
         let mut rule_index_updates: HashMap<(String, usize), Vec<Rule>> = HashMap::new();
         let mut wildcard_updates: Vec<Rule> = Vec::new();
         let mut multiplicity_updates: HashMap<String, usize> = HashMap::new();
+        // Rule expressions by key, kept only to name duplicates in warnings
+        let mut rule_sexprs: HashMap<String, MettaValue> = HashMap::new();
 
         for rule in rules {
-            // Create rule s-expression: (= lhs rhs)
-            let rule_sexpr = MettaValue::SExpr(vec![
-                MettaValue::Atom("=".to_string()),
-                rule.lhs.clone(),
-                rule.rhs.clone(),
-            ]);
+            // Create rule s-expression: (= lhs rhs) or (= lhs rhs where guard)
+            let rule_sexpr = rule.to_sexpr();
 
             // Track multiplicity
             let rule_key = rule_sexpr.to_mork_string();
+            if self.duplicate_rule_warnings.is_some() {
+                rule_sexprs
+                    .entry(rule_key.clone())
+                    .or_insert_with(|| rule_sexpr.clone());
+            }
             *multiplicity_updates.entry(rule_key).or_insert(0) += 1;
 
             // Prepare rule index updates
@@ -799,12 +1605,22 @@ impl Environment {
         // Apply all updates in batch (minimize critical sections)
 
         // Update multiplicities
+        let mut duplicates = Vec::new();
         {
             let mut counts = self.multiplicities.write().unwrap();
             for (key, delta) in multiplicity_updates {
-                *counts.entry(key).or_insert(0) += delta;
+                let count = counts.entry(key.clone()).or_insert(0);
+                *count += delta;
+                if *count > 1 {
+                    if let Some(rule_sexpr) = rule_sexprs.remove(&key) {
+                        duplicates.push((rule_sexpr, *count));
+                    }
+                }
             }
         }
+        for (rule_sexpr, count) in duplicates {
+            self.warn_duplicate_rule(&rule_sexpr, count);
+        }
 
         // Update rule index
         {
@@ -832,12 +1648,7 @@ impl Environment {
     /// Get the number of times a rule has been defined (multiplicity)
     /// Returns 1 if the rule exists but count wasn't tracked (for backward compatibility)
     pub fn get_rule_count(&self, rule: &Rule) -> usize {
-        let rule_sexpr = MettaValue::SExpr(vec![
-            MettaValue::Atom("=".to_string()),
-            rule.lhs.clone(),
-            rule.rhs.clone(),
-        ]);
-        let rule_key = rule_sexpr.to_mork_string();
+        let rule_key = rule.to_sexpr().to_mork_string();
 
         let counts = self.multiplicities.read().unwrap();
         *counts.get(&rule_key).unwrap_or(&1)
@@ -850,11 +1661,408 @@ impl Environment {
 
     /// Set the multiplicities (used for deserialization)
     pub fn set_multiplicities(&mut self, counts: HashMap<String, usize>) {
+        self.note_effect();
         self.make_owned(); // CoW: ensure we own data before modifying
         *self.multiplicities.write().unwrap() = counts;
         self.modified.store(true, Ordering::Release); // CoW: mark as modified
     }
 
+    /// Get the directory used to resolve relative `import!` paths
+    pub fn module_path(&self) -> Option<PathBuf> {
+        self.module_path.read().unwrap().clone()
+    }
+
+    /// Set the directory used to resolve relative `import!` paths
+    pub fn set_module_path(&mut self, dir: Option<PathBuf>) {
+        self.make_owned(); // CoW: ensure we own data before modifying
+        *self.module_path.write().unwrap() = dir;
+        self.modified.store(true, Ordering::Release); // CoW: mark as modified
+    }
+
+    /// Get the load state of an imported module (keyed by canonical path)
+    pub fn module_load_state(&self, path: &Path) -> Option<ModuleLoadState> {
+        self.loaded_modules.read().unwrap().get(path).copied()
+    }
+
+    /// Record the load state of an imported module (keyed by canonical path)
+    pub fn set_module_load_state(&mut self, path: PathBuf, state: ModuleLoadState) {
+        trace!(target: "mettatron::environment::set_module_load_state", ?path, ?state);
+        self.make_owned(); // CoW: ensure we own data before modifying
+        self.loaded_modules.write().unwrap().insert(path, state);
+        self.modified.store(true, Ordering::Release); // CoW: mark as modified
+    }
+
+    /// Attach a documentation string to a symbol (replaces any previous doc)
+    pub fn set_doc(&mut self, symbol: &str, doc: &str) {
+        trace!(target: "mettatron::environment::set_doc", symbol, doc);
+        self.note_effect();
+        self.make_owned(); // CoW: ensure we own data before modifying
+        self.docs
+            .write()
+            .unwrap()
+            .insert(symbol.to_string(), doc.to_string());
+        self.modified.store(true, Ordering::Release); // CoW: mark as modified
+    }
+
+    /// Get the documentation string attached to a symbol, if any
+    pub fn get_doc(&self, symbol: &str) -> Option<String> {
+        self.docs.read().unwrap().get(symbol).cloned()
+    }
+
+    /// Bind `token` to `value`, replacing any previous binding; see `bind!`
+    pub fn bind_token(&mut self, token: &str, value: MettaValue) {
+        trace!(target: "mettatron::environment::bind_token", token, ?value);
+        self.note_effect();
+        self.make_owned(); // CoW: ensure we own data before modifying
+        self.tokens
+            .write()
+            .unwrap()
+            .insert(token.to_string(), value);
+        self.modified.store(true, Ordering::Release); // CoW: mark as modified
+    }
+
+    /// Get the value bound to `token` with `bind!`, if any
+    pub fn get_token(&self, token: &str) -> Option<MettaValue> {
+        self.tokens.read().unwrap().get(token).cloned()
+    }
+
+    /// Reseed the random number generator for a reproducible sequence
+    /// Affects every environment sharing this generator
+    pub fn seed_rng(&self, seed: u64) {
+        self.note_effect();
+        *self.rng.write().unwrap() = Rng::from_seed(seed);
+    }
+
+    /// Uniform random integer in `[lo, hi)`; requires `lo < hi`
+    pub fn random_int(&self, lo: i64, hi: i64) -> i64 {
+        self.note_effect();
+        self.rng.write().unwrap().range_i64(lo, hi)
+    }
+
+    /// Uniform random float in `[0, 1)`
+    pub fn random_float(&self) -> f64 {
+        self.note_effect();
+        self.rng.write().unwrap().next_f64()
+    }
+
+    /// Create a new state cell holding `value` and return its id
+    ///
+    /// Ids are unique across every environment in the process, so a state reference
+    /// from one environment is never mistaken for a cell of another.
+    pub fn new_state(&self, value: MettaValue) -> u64 {
+        self.note_effect();
+        let id = NEXT_STATE_ID.fetch_add(1, Ordering::Relaxed);
+        let mut states = self.states.write().unwrap();
+        let value_type = state_value_type(&value);
+        states.cells.insert(id, (value, value_type));
+        id
+    }
+
+    /// Current value of a state cell, or None if no such cell exists
+    pub fn get_state(&self, id: u64) -> Option<MettaValue> {
+        self.states
+            .read()
+            .unwrap()
+            .cells
+            .get(&id)
+            .map(|(value, _)| value.clone())
+    }
+
+    /// Replace the value of a state cell
+    ///
+    /// With strict state typing the new value must have the same type as the cell's
+    /// initial value; on a mismatch the cell is left unchanged and the error names
+    /// both types.
+    pub fn change_state(&self, id: u64, value: MettaValue) -> Result<(), String> {
+        self.note_effect();
+        let mut states = self.states.write().unwrap();
+        let strict = states.strict_types;
+        let (current, cell_type) = states
+            .cells
+            .get_mut(&id)
+            .ok_or_else(|| format!("no state cell with id {}", id))?;
+
+        let new_type = state_value_type(&value);
+        if strict && new_type != *cell_type {
+            return Err(format!(
+                "state holds {} but new value is {}",
+                cell_type, new_type
+            ));
+        }
+        *current = value;
+        Ok(())
+    }
+
+    /// Create a new empty space and return its id
+    pub fn new_space(&self) -> u64 {
+        self.note_effect();
+        let mut spaces = self.spaces.write().unwrap();
+        let id = spaces.next_id;
+        spaces.next_id += 1;
+        spaces.atoms.insert(id, Vec::new());
+        id
+    }
+
+    /// Add `atom` to the space created by `new_space` with this id
+    /// Returns false if no such space exists.
+    pub fn add_to_named_space(&self, id: u64, atom: MettaValue) -> bool {
+        self.note_effect();
+        match self.spaces.write().unwrap().atoms.get_mut(&id) {
+            Some(atoms) => {
+                atoms.push(atom);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Atoms of the space created by `new_space` with this id, in insertion order,
+    /// or None if no such space exists
+    pub fn named_space_atoms(&self, id: u64) -> Option<Vec<MettaValue>> {
+        self.spaces.read().unwrap().atoms.get(&id).cloned()
+    }
+
+    /// Copy every atom of the space created by `new_space` with this id into this
+    /// environment's own space, where `match & self` sees it
+    ///
+    /// The atoms are added as data, as by `add-atom`. The space is a set, so an atom
+    /// that is already there is not added again. Returns false if no such space exists.
+    pub fn merge_named_space_into_self(&mut self, id: u64) -> bool {
+        trace!(target: "mettatron::environment::merge_named_space_into_self", id);
+        let Some(atoms) = self.named_space_atoms(id) else {
+            return false;
+        };
+        self.make_owned(); // CoW: ensure we own data before modifying
+        for atom in &atoms {
+            self.add_to_space(atom);
+        }
+        true
+    }
+
+    /// Start a nondeterministic fork: remember the state cells every branch starts from
+    pub(crate) fn fork_states(&self) -> StateFork {
+        StateFork {
+            origin: self.states.read().unwrap().cells.clone(),
+            changes: HashMap::new(),
+        }
+    }
+
+    /// End one branch of `fork`: record the cells it created or changed and restore
+    /// the cells to the state at the fork for the next sibling
+    pub(crate) fn end_state_branch(&self, fork: &mut StateFork) {
+        let mut states = self.states.write().unwrap();
+        let mut changed = false;
+        for (id, cell) in &states.cells {
+            if fork.origin.get(id) != Some(cell) {
+                fork.changes.insert(*id, cell.clone());
+                changed = true;
+            }
+        }
+        if changed {
+            self.note_effect();
+            states.cells = fork.origin.clone();
+        }
+    }
+
+    /// Join `fork` once its last branch has ended, keeping every branch's changes
+    pub(crate) fn join_state_fork(&self, fork: StateFork) {
+        if fork.changes.is_empty() {
+            return;
+        }
+        self.note_effect();
+        self.states.write().unwrap().cells.extend(fork.changes);
+    }
+
+    /// Enable or disable type checking in `change-state!` (enabled by default)
+    pub fn set_strict_state_types(&self, strict: bool) {
+        self.note_effect();
+        self.states.write().unwrap().strict_types = strict;
+    }
+
+    /// Record a side effect, invalidating cached `collapse` results
+    #[inline]
+    fn note_effect(&self) {
+        self.effects.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Number of side effects recorded so far; unchanged across an evaluation means
+    /// the evaluation was pure
+    pub(crate) fn effect_count(&self) -> u64 {
+        self.effects.load(Ordering::Relaxed)
+    }
+
+    /// `depth` is the number of nested `eval` calls and `(eval ...)` forms active where
+    /// the collapse runs
+    fn collapse_key(&self, expr: &MettaValue, depth: (usize, usize)) -> CollapseKey {
+        (
+            HashedValue::new(expr.clone()),
+            Arc::as_ptr(&self.btm) as usize,
+            Arc::as_ptr(&self.rule_index) as usize,
+            Arc::as_ptr(&self.wildcard_rules) as usize,
+            self.max_call_depth.saturating_sub(depth.0),
+            self.max_eval_depth.saturating_sub(depth.1),
+        )
+    }
+
+    /// Whether `collapse` may use its cache: rule tracing needs every rule application
+    /// to happen, and an interrupted evaluation must stop rather than answer
+    fn collapse_cache_enabled(&self) -> bool {
+        self.rule_trace.is_none() && !self.is_interrupted()
+    }
+
+    /// Cached results of collapsing `expr`, if it was collapsed purely against this
+    /// environment, at the same depth headroom, and no side effect has happened since
+    pub(crate) fn cached_collapse(
+        &self,
+        expr: &MettaValue,
+        depth: (usize, usize),
+    ) -> Option<Vec<MettaValue>> {
+        if !self.collapse_cache_enabled() {
+            return None;
+        }
+        let key = self.collapse_key(expr, depth);
+        let mut cache = self.collapse_cache.write().unwrap();
+        match cache.get(&key) {
+            Some((effects, results)) if *effects == self.effect_count() => {
+                self.metrics.record_collapse_cache_hit();
+                Some(results.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Remember the results of collapsing `expr`
+    ///
+    /// `effects_before` is the effect count read before evaluating `expr`; if anything
+    /// happened since then the evaluation was not pure and nothing is stored.
+    pub(crate) fn cache_collapse(
+        &self,
+        expr: &MettaValue,
+        depth: (usize, usize),
+        effects_before: u64,
+        results: &[MettaValue],
+    ) {
+        if !self.collapse_cache_enabled() || self.effect_count() != effects_before {
+            return;
+        }
+        let key = self.collapse_key(expr, depth);
+        self.collapse_cache
+            .write()
+            .unwrap()
+            .put(key, (effects_before, results.to_vec()));
+    }
+
+    /// Use `flag` to interrupt evaluation: once it is set (from any thread), every
+    /// evaluation running in this environment or its clones stops at its next step
+    /// and returns an "Interrupted" error. The caller clears the flag to resume.
+    pub fn set_interrupt_flag(&mut self, flag: Arc<AtomicBool>) {
+        self.interrupt = flag;
+    }
+
+    /// The interrupt flag checked by evaluation (see `set_interrupt_flag`)
+    pub fn interrupt_flag(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.interrupt)
+    }
+
+    #[inline]
+    pub(crate) fn is_interrupted(&self) -> bool {
+        self.interrupt.load(Ordering::Relaxed)
+    }
+
+    /// Maximum number of nested `eval` calls (see `set_max_call_depth`)
+    pub fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Limit how deeply evaluation may re-enter itself
+    ///
+    /// Non-tail recursion through special forms such as `if` nests native `eval`
+    /// calls; past this limit evaluation returns an error rather than overflowing
+    /// the stack.
+    pub fn set_max_call_depth(&mut self, depth: usize) {
+        self.max_call_depth = depth;
+    }
+
+    /// Maximum number of nested `(eval ...)` forms (see `set_max_eval_depth`)
+    pub fn max_eval_depth(&self) -> usize {
+        self.max_eval_depth
+    }
+
+    /// Limit how deeply `(eval ...)` forms may nest
+    ///
+    /// Metaprograms that keep building and evaluating new expressions can nest
+    /// `eval` without bound; past this limit `eval` returns an error.
+    pub fn set_max_eval_depth(&mut self, depth: usize) {
+        self.max_eval_depth = depth;
+    }
+
+    /// Maximum number of results `collapse` may gather (see `set_max_collapse_results`)
+    pub fn max_collapse_results(&self) -> Option<usize> {
+        self.max_collapse_results
+    }
+
+    /// Limit how many results `collapse` and `collapse-bind` may gather, or lift the
+    /// limit with `None`
+    ///
+    /// A collapse over a search with more results than this returns an error
+    /// instead of building an expression of all of them. The limit also applies to
+    /// every call with several matching rules while the collapsed expression is
+    /// evaluated, so a search that never stops producing results is cut short.
+    pub fn set_max_collapse_results(&mut self, limit: Option<usize>) {
+        self.note_effect();
+        self.max_collapse_results = limit;
+    }
+
+    /// Report every rule application to `sink`, or stop reporting with `None`
+    ///
+    /// Each line names the call, the rule that fired and the bindings it produced,
+    /// independently of the `tracing` logs. Bindings made by `let` are reported too.
+    pub fn set_rule_trace(&mut self, sink: Option<RuleTraceSink>) {
+        self.rule_trace = sink;
+    }
+
+    pub(crate) fn rule_trace(&self) -> Option<&RuleTraceSink> {
+        self.rule_trace.as_ref()
+    }
+
+    /// Warn through `sink` whenever a rule is added that is structurally identical to
+    /// one already defined, or stop warning with `None`
+    ///
+    /// Identical definitions are not errors: each copy is kept and makes the rule
+    /// produce its result once more, which is rarely intended.
+    pub fn set_duplicate_rule_warnings(&mut self, sink: Option<RuleTraceSink>) {
+        self.duplicate_rule_warnings = sink;
+    }
+
+    fn warn_duplicate_rule(&self, rule: &MettaValue, count: usize) {
+        if let Some(sink) = &self.duplicate_rule_warnings {
+            sink(&format!(
+                "warning: duplicate rule {} (now defined {} times)",
+                rule, count
+            ));
+        }
+    }
+
+    /// Turn execution metrics collection on or off
+    pub fn set_collect_metrics(&self, enabled: bool) {
+        self.metrics.set_enabled(enabled);
+    }
+
+    /// Snapshot of the execution metrics collected so far
+    pub fn metrics(&self) -> EvalMetrics {
+        self.metrics.snapshot()
+    }
+
+    /// Zero the execution metrics counters
+    pub fn reset_metrics(&self) {
+        self.metrics.reset();
+    }
+
+    /// Collector the evaluator reports execution metrics to
+    pub(crate) fn metrics_collector(&self) -> &MetricsCollector {
+        &self.metrics
+    }
+
     /// Check if an atom fact exists (queries MORK Space)
     /// OPTIMIZED: Uses O(p) exact match via descend_to_check() where p = pattern depth
     ///
@@ -924,7 +2132,7 @@ impl Environment {
 
         let space = self.create_space();
 
-        // Parse to MORK Expr (following try_match_all_rules_query_multi pattern)
+        // Parse to MORK Expr
         let mut parse_buffer = vec![0u8; 4096];
         let mut pdp = mork::space::ParDataParser::new(&space.sm);
         let mut ez = mork_expr::ExprZipper::new(Expr {
@@ -1021,13 +2229,10 @@ impl Environment {
         Ok(bytes)
     }
 
-    /// Check if a MettaValue contains variables ($x, &y, 'z, or _)
+    /// Check if a MettaValue contains variables ($x, 'z, or _)
     fn contains_variables(value: &MettaValue) -> bool {
         match value {
-            MettaValue::Atom(s) => {
-                s == "_"
-                    || (s.starts_with('$') || s.starts_with('&') || s.starts_with('\'')) && s != "&"
-            }
+            MettaValue::Atom(s) => s == "_" || s.starts_with('$') || s.starts_with('\''),
             MettaValue::SExpr(items) => items.iter().any(Self::contains_variables),
             MettaValue::Error(_, details) => Self::contains_variables(details),
             MettaValue::Type(t) => Self::contains_variables(t),
@@ -1115,6 +2320,16 @@ impl Environment {
         None
     }
 
+    /// Add a ground fact such as `(parent Tom Bob)` to this environment's space,
+    /// where `match & self` and `?` queries see it
+    ///
+    /// Unlike `add_rule`, the value is stored only as data: it is never indexed as a
+    /// rule, even if it has the shape `(= lhs rhs)`.
+    pub fn add_fact(&mut self, fact: &MettaValue) {
+        trace!(target: "mettatron::environment::add_fact", ?fact);
+        self.add_to_space(fact);
+    }
+
     /// Add a fact to the MORK Space for pattern matching
     /// Converts the MettaValue to MORK format and stores it
     /// OPTIMIZATION (Variant C): Uses direct MORK byte conversion for ground values
@@ -1124,6 +2339,7 @@ impl Environment {
     /// To query nested parts, use pattern matching with variables, e.g., (Outer $x)
     pub fn add_to_space(&mut self, value: &MettaValue) {
         trace!(target: "mettatron::environment::add_to_space", ?value);
+        self.note_effect();
         use crate::backend::mork_convert::{metta_to_mork_bytes, ConversionContext};
 
         // Try direct byte conversion first (Variant C)
@@ -1181,6 +2397,7 @@ impl Environment {
     /// - Marks environment as modified (CoW)
     pub fn remove_from_space(&mut self, value: &MettaValue) {
         trace!(target: "mettatron::environment::remove_from_space", ?value);
+        self.note_effect();
         use crate::backend::mork_convert::{metta_to_mork_bytes, ConversionContext};
 
         // Try direct byte conversion first (same optimization as add_to_space)
@@ -1271,6 +2488,7 @@ impl Environment {
     /// Complexity: O(m) where m = size of fact batch (vs O(n × lock) for individual inserts)
     pub fn add_facts_bulk(&mut self, facts: &[MettaValue]) -> Result<(), String> {
         trace!(target: "mettatron::environment::add_facts_bulk", ?facts);
+        self.note_effect();
 
         if facts.is_empty() {
             return Ok(());
@@ -1363,6 +2581,75 @@ impl Environment {
         matching_rules
     }
 
+    /// Get the rules that may match a call expression, using the first-argument index
+    ///
+    /// Like [`get_matching_rules`](Self::get_matching_rules) for the call's head and arity,
+    /// but when the call has a first argument only rules keyed by that argument's literal
+    /// value or shape are returned, plus rules whose first argument is a variable.
+    /// Wildcard rules are always included.
+    pub fn get_candidate_rules(&self, expr: &MettaValue) -> Vec<Rule> {
+        trace!(target: "mettatron::environment::get_candidate_rules", ?expr);
+        let Some(head) = expr.get_head_symbol() else {
+            return self.get_matching_rules("", 0);
+        };
+        let key = (head.to_owned(), expr.get_arity());
+        let first_arg = match expr {
+            MettaValue::SExpr(items) => items.get(1),
+            _ => None,
+        };
+
+        let index = self.rule_index.read().unwrap();
+        let wildcards = self.wildcard_rules.read().unwrap();
+
+        let mut matching_rules: Vec<Rule> = match index.get(&key) {
+            Some(bucket) => bucket.candidates(first_arg).into_iter().cloned().collect(),
+            None => Vec::new(),
+        };
+        matching_rules.extend(wildcards.iter().cloned());
+
+        trace!(
+            target: "mettatron::environment::get_candidate_rules",
+            match_ctr = matching_rules.len(), "Candidate rules"
+        );
+        matching_rules
+    }
+
+    /// Get every rule defined for a head symbol, across all arities
+    ///
+    /// Meant for tooling such as `:rules` in the REPL. Indexed rules come first, ordered
+    /// by arity and then definition order, followed by the wildcard rules that could
+    /// apply to a call with this head: those whose left-hand side is a variable or
+    /// starts with one.
+    pub fn find_rules_by_head(&self, head: &str) -> Vec<Rule> {
+        trace!(target: "mettatron::environment::find_rules_by_head", head);
+
+        let index = self.rule_index.read().unwrap();
+        let wildcards = self.wildcard_rules.read().unwrap();
+
+        let mut buckets: Vec<(usize, &RuleBucket)> = index
+            .iter()
+            .filter(|((name, _), _)| name == head)
+            .map(|((_, arity), bucket)| (*arity, bucket))
+            .collect();
+        buckets.sort_unstable_by_key(|(arity, _)| *arity);
+
+        let is_variable = |value: &MettaValue| matches!(value, MettaValue::Atom(name) if name.starts_with('$') || name == "_");
+        let mut rules: Vec<Rule> = buckets
+            .into_iter()
+            .flat_map(|(_, bucket)| bucket.iter().cloned())
+            .collect();
+        rules.extend(
+            wildcards
+                .iter()
+                .filter(|rule| match &rule.lhs {
+                    MettaValue::SExpr(items) => items.first().is_some_and(is_variable),
+                    lhs => is_variable(lhs),
+                })
+                .cloned(),
+        );
+        rules
+    }
+
     /// Get fuzzy suggestions for a potentially misspelled symbol
     ///
     /// Returns a list of (symbol, distance) pairs sorted by Levenshtein distance.
@@ -1424,6 +2711,14 @@ impl Environment {
         let fuzzy_matcher = self.fuzzy_matcher.clone();
         let type_index = self.type_index.clone();
         let type_index_dirty = self.type_index_dirty.clone();
+        let module_path = self.module_path.clone();
+        let loaded_modules = self.loaded_modules.clone();
+        let docs = self.docs.clone();
+        let tokens = self.tokens.clone();
+        let rng = self.rng.clone();
+        let states = self.states.clone();
+        let spaces = self.spaces.clone();
+        let metrics = self.metrics.clone();
 
         Environment {
             shared_mapping,
@@ -1437,6 +2732,22 @@ impl Environment {
             fuzzy_matcher,
             type_index,
             type_index_dirty,
+            module_path,
+            loaded_modules,
+            docs,
+            tokens,
+            rng,
+            states,
+            spaces,
+            effects: self.effects.clone(),
+            collapse_cache: self.collapse_cache.clone(),
+            interrupt: self.interrupt.clone(),
+            max_call_depth: self.max_call_depth,
+            max_eval_depth: self.max_eval_depth,
+            max_collapse_results: self.max_collapse_results,
+            rule_trace: self.rule_trace.clone(),
+            duplicate_rule_warnings: self.duplicate_rule_warnings.clone(),
+            metrics,
         }
     }
 }
@@ -1457,6 +2768,22 @@ impl Clone for Environment {
             fuzzy_matcher: self.fuzzy_matcher.clone(),
             type_index: Arc::clone(&self.type_index),
             type_index_dirty: Arc::clone(&self.type_index_dirty),
+            module_path: Arc::clone(&self.module_path),
+            loaded_modules: Arc::clone(&self.loaded_modules),
+            docs: Arc::clone(&self.docs),
+            tokens: Arc::clone(&self.tokens),
+            rng: Arc::clone(&self.rng),
+            states: Arc::clone(&self.states),
+            spaces: Arc::clone(&self.spaces),
+            effects: Arc::clone(&self.effects),
+            collapse_cache: Arc::clone(&self.collapse_cache),
+            interrupt: Arc::clone(&self.interrupt),
+            max_call_depth: self.max_call_depth,
+            max_eval_depth: self.max_eval_depth,
+            max_collapse_results: self.max_collapse_results,
+            rule_trace: self.rule_trace.clone(),
+            duplicate_rule_warnings: self.duplicate_rule_warnings.clone(),
+            metrics: Arc::clone(&self.metrics),
         }
     }
 }
@@ -1485,10 +2812,10 @@ mod cow_tests {
 
     /// Helper: Create a simple rule for testing
     fn make_test_rule(lhs: &str, rhs: &str) -> Rule {
-        Rule {
-            lhs: MettaValue::Atom(lhs.to_string()),
-            rhs: MettaValue::Atom(rhs.to_string()),
-        }
+        Rule::new(
+            MettaValue::Atom(lhs.to_string()),
+            MettaValue::Atom(rhs.to_string()),
+        )
     }
 
     /// Helper: Extract head symbol and arity from a MettaValue (for get_matching_rules)
@@ -1632,6 +2959,30 @@ mod cow_tests {
         assert_eq!(clone_rules2.len(), 1, "Clone should have new rule");
     }
 
+    #[test]
+    fn test_isolation_of_states_and_spaces_after_clone_mutation() {
+        let env = Environment::new();
+        let counter = env.new_state(MettaValue::Long(0));
+        let space = env.new_space();
+
+        // Until the clone owns its data, state and space changes are shared
+        let mut clone = env.clone();
+        clone.change_state(counter, MettaValue::Long(1)).unwrap();
+        assert_eq!(env.get_state(counter), Some(MettaValue::Long(1)));
+
+        // Once it does, they are its own
+        clone.add_rule(make_test_rule("(cloned $y)", "(cloned-result $y)"));
+        clone.change_state(counter, MettaValue::Long(2)).unwrap();
+        clone.add_to_named_space(space, MettaValue::Atom("a".to_string()));
+        assert_eq!(env.get_state(counter), Some(MettaValue::Long(1)));
+        assert_eq!(env.named_space_atoms(space), Some(vec![]));
+        assert_eq!(clone.get_state(counter), Some(MettaValue::Long(2)));
+        assert_eq!(
+            clone.named_space_atoms(space),
+            Some(vec![MettaValue::Atom("a".to_string())])
+        );
+    }
+
     #[test]
     fn test_modification_tracking() {
         // Test: Modification flag is correctly tracked
@@ -2116,7 +3467,7 @@ mod thread_safety_tests {
             MettaValue::Atom(body.to_string())
         };
 
-        Rule { lhs, rhs }
+        Rule::new(lhs, rhs)
     }
 
     // Helper: Extract head and arity from a pattern
@@ -2124,9 +3475,9 @@ mod thread_safety_tests {
         match pattern {
             MettaValue::SExpr(items) if !items.is_empty() => {
                 if let MettaValue::Atom(head) = &items[0] {
-                    // Count variables (starts with $, &, or ')
+                    // Count variables (starts with $ or ')
                     let arity = items[1..].iter().filter(|item| {
-                        matches!(item, MettaValue::Atom(s) if s.starts_with('$') || s.starts_with('&') || s.starts_with('\''))
+                        matches!(item, MettaValue::Atom(s) if s.starts_with('$') || s.starts_with('\''))
                     }).count();
                     (head.as_str(), arity)
                 } else {
@@ -2293,10 +3644,10 @@ mod thread_safety_tests {
                 // Verify other thread's rules DON'T exist
                 for i in 0..RULES_PER_THREAD {
                     let pattern = format!("(t{}_r{} $x)", other_id, i);
-                    let rule = Rule {
-                        lhs: MettaValue::Atom(pattern),
-                        rhs: MettaValue::Atom(format!("(res{} $x)", i)),
-                    };
+                    let rule = Rule::new(
+                        MettaValue::Atom(pattern),
+                        MettaValue::Atom(format!("(res{} $x)", i)),
+                    );
                     let (head, arity) = extract_head_arity(&rule.lhs);
                     let matches = clone.get_matching_rules(head, arity);
                     assert!(
@@ -2599,3 +3950,419 @@ mod thread_safety_tests {
         assert_eq!(shared.rule_count(), 30);
     }
 }
+
+#[cfg(test)]
+mod rule_index_tests {
+    use super::*;
+
+    #[test]
+    fn test_stats_counts_rules_and_facts() {
+        let mut env = Environment::new();
+        assert_eq!(env.stats(), EnvStats::default());
+
+        env.add_rule(Rule::new(
+            lookup(MettaValue::Atom("$x".to_string())),
+            MettaValue::Atom("$x".to_string()),
+        ));
+        env.add_rule(Rule::new(lookup(key(1)), MettaValue::Long(1)));
+        env.add_rule(Rule::new(
+            MettaValue::Atom("$anything".to_string()),
+            MettaValue::Atom("wild".to_string()),
+        ));
+        for i in 0..3 {
+            env.add_to_space(&MettaValue::SExpr(vec![
+                MettaValue::Atom("fact".to_string()),
+                MettaValue::Long(i),
+            ]));
+        }
+        env.set_doc("lookup", "Looks things up");
+
+        let stats = env.stats();
+        assert_eq!(stats.rules, 3);
+        assert_eq!(stats.wildcard_rules, 1);
+        assert_eq!(stats.facts, 3);
+        assert_eq!(stats.atoms, 6);
+        assert_eq!(stats.documented_symbols, 1);
+        assert_eq!(stats.loaded_modules, 0);
+    }
+
+    fn lookup(arg: MettaValue) -> MettaValue {
+        MettaValue::SExpr(vec![MettaValue::Atom("lookup".to_string()), arg])
+    }
+
+    fn key(i: usize) -> MettaValue {
+        MettaValue::Atom(format!("key{}", i))
+    }
+
+    #[test]
+    fn test_first_arg_index_limits_candidates() {
+        let mut env = Environment::new();
+        let mut rules: Vec<Rule> = (0..500)
+            .map(|i| Rule::new(lookup(key(i)), MettaValue::Long(i as i64)))
+            .collect();
+        rules.push(Rule::new(
+            lookup(MettaValue::Atom("$k".to_string())),
+            MettaValue::Atom("default".to_string()),
+        ));
+        env.add_rules_bulk(rules).unwrap();
+
+        // Without the first-argument index every rule for (lookup _) would be tried
+        assert_eq!(env.get_matching_rules("lookup", 1).len(), 501);
+
+        // With it, only the keyed rule and the variable-headed rule are tried
+        let candidates = env.get_candidate_rules(&lookup(key(42)));
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].rhs, MettaValue::Long(42));
+        assert_eq!(candidates[1].rhs, MettaValue::Atom("default".to_string()));
+
+        // Unknown keys still reach the variable-headed rule
+        let candidates = env.get_candidate_rules(&lookup(MettaValue::Atom("other".to_string())));
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].rhs, MettaValue::Atom("default".to_string()));
+    }
+
+    #[test]
+    fn test_first_arg_index_literals_and_shapes() {
+        let mut env = Environment::new();
+        env.add_rule(Rule::new(
+            lookup(MettaValue::Long(1)),
+            MettaValue::Atom("one".to_string()),
+        ));
+        env.add_rule(Rule::new(
+            lookup(MettaValue::SExpr(vec![
+                MettaValue::Atom("pair".to_string()),
+                MettaValue::Atom("$a".to_string()),
+                MettaValue::Atom("$b".to_string()),
+            ])),
+            MettaValue::Atom("pair".to_string()),
+        ));
+        env.add_rule(Rule::new(
+            lookup(MettaValue::SExpr(vec![
+                MettaValue::Atom("$f".to_string()),
+                MettaValue::Atom("$a".to_string()),
+            ])),
+            MettaValue::Atom("unary".to_string()),
+        ));
+
+        let rhs_of = |arg: MettaValue| -> Vec<MettaValue> {
+            env.get_candidate_rules(&lookup(arg))
+                .into_iter()
+                .map(|r| r.rhs)
+                .collect()
+        };
+
+        assert_eq!(
+            rhs_of(MettaValue::Long(1)),
+            vec![MettaValue::Atom("one".to_string())]
+        );
+        assert_eq!(
+            rhs_of(MettaValue::SExpr(vec![
+                MettaValue::Atom("pair".to_string()),
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+            ])),
+            vec![MettaValue::Atom("pair".to_string())]
+        );
+        // A variable-headed expression pattern is always a candidate
+        assert_eq!(
+            rhs_of(MettaValue::SExpr(vec![
+                MettaValue::Atom("g".to_string()),
+                MettaValue::Long(1),
+            ])),
+            vec![MettaValue::Atom("unary".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_first_arg_index_survives_rebuild() {
+        let mut env = Environment::new();
+        for i in 0..10 {
+            env.add_rule(Rule::new(lookup(key(i)), MettaValue::Long(i as i64)));
+        }
+        env.rebuild_rule_index();
+
+        let candidates = env.get_candidate_rules(&lookup(key(7)));
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].rhs, MettaValue::Long(7));
+    }
+
+    #[test]
+    fn test_intern_stats_grows_only_for_new_symbols() {
+        let mut env = Environment::new();
+        assert_eq!(env.intern_stats(), InternStats::default());
+
+        let fact = |head: &str, arg: &str| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom(head.to_string()),
+                MettaValue::Atom(arg.to_string()),
+            ])
+        };
+
+        env.add_to_space(&fact("color", "red"));
+        let first = env.intern_stats();
+        assert_eq!(first.symbols, 2);
+        assert_eq!(first.symbol_bytes, "color".len() + "red".len());
+
+        // A new symbol grows the count
+        env.add_to_space(&fact("color", "green"));
+        let second = env.intern_stats();
+        assert_eq!(second.symbols, 3);
+        assert!(second.approx_bytes > first.approx_bytes);
+
+        // Reusing known symbols keeps it flat
+        env.add_to_space(&fact("red", "green"));
+        env.add_to_space(&fact("green", "color"));
+        assert_eq!(env.intern_stats(), second);
+    }
+
+    #[test]
+    fn test_duplicate_rule_warnings() {
+        use std::sync::Mutex;
+
+        let mut env = Environment::new();
+        let warnings = Arc::new(Mutex::new(Vec::new()));
+        let sink_warnings = warnings.clone();
+        env.set_duplicate_rule_warnings(Some(Arc::new(move |line: &str| {
+            sink_warnings.lock().unwrap().push(line.to_string())
+        })));
+
+        let double = || {
+            Rule::new(
+                lookup(MettaValue::Atom("$x".to_string())),
+                MettaValue::Atom("$x".to_string()),
+            )
+        };
+        env.add_rule(double());
+        // Same head, different body: not a duplicate
+        env.add_rule(Rule::new(
+            lookup(MettaValue::Atom("$x".to_string())),
+            MettaValue::Long(0),
+        ));
+        assert!(warnings.lock().unwrap().is_empty());
+
+        env.add_rule(double());
+        assert_eq!(
+            *warnings.lock().unwrap(),
+            vec!["warning: duplicate rule (= (lookup $x) $x) (now defined 2 times)".to_string()]
+        );
+
+        // Bulk loading reports duplicates too, once per rule
+        env.add_rules_bulk(vec![double(), key_rule(1), key_rule(1)])
+            .unwrap();
+        let warnings = warnings.lock().unwrap();
+        assert_eq!(warnings.len(), 3);
+        assert!(warnings[1..]
+            .iter()
+            .any(|w| w.contains("(now defined 3 times)")));
+        assert!(warnings[1..]
+            .iter()
+            .any(|w| w.contains("(= (lookup 1) 1) (now defined 2 times)")));
+    }
+
+    fn key_rule(i: i64) -> Rule {
+        Rule::new(lookup(MettaValue::Long(i)), MettaValue::Long(i))
+    }
+
+    #[test]
+    fn test_clear_resets_rules_facts_and_states() {
+        let mut env = Environment::new();
+        env.add_rule(Rule::new(
+            lookup(MettaValue::Atom("$x".to_string())),
+            MettaValue::Atom("$x".to_string()),
+        ));
+        env.add_rule(key_rule(1));
+        env.add_to_space(&MettaValue::SExpr(vec![
+            MettaValue::Atom("fact".to_string()),
+            MettaValue::Long(1),
+        ]));
+        env.set_doc("lookup", "Looks things up");
+        env.new_state(MettaValue::Long(0));
+        env.bind_token("&answer", MettaValue::Long(42));
+        env.new_space();
+        env.set_max_call_depth(7);
+        let stats = env.stats();
+        assert_eq!((stats.states, stats.tokens, stats.named_spaces), (1, 1, 1));
+        let snapshot = env.clone();
+
+        env.clear();
+        assert_eq!(env.stats(), EnvStats::default());
+        assert_eq!(env.rule_count(), 0);
+        assert!(env.get_matching_rules("lookup", 1).is_empty());
+        assert!(env.get_doc("lookup").is_none());
+        assert_eq!(env.max_call_depth(), 7);
+
+        // Dispatch no longer finds the rule, so the call is left as data
+        let call = lookup(MettaValue::Long(1));
+        let (results, env) = crate::backend::eval::eval(call.clone(), env);
+        assert_eq!(results, vec![call]);
+
+        // Clones taken before clearing keep their contents
+        assert_eq!(snapshot.stats().rules, 2);
+        assert_eq!(snapshot.get_matching_rules("lookup", 1).len(), 2);
+
+        // The cleared environment is usable again
+        let mut env = env;
+        env.add_rule(key_rule(2));
+        assert_eq!(env.rule_count(), 1);
+    }
+
+    #[test]
+    fn test_find_rules_by_head_returns_all_arities() {
+        let var = |name: &str| MettaValue::Atom(name.to_string());
+        let mut env = Environment::new();
+        env.add_rule(key_rule(1));
+        env.add_rule(Rule::new(
+            MettaValue::SExpr(vec![var("lookup"), var("$k"), var("$default")]),
+            var("$default"),
+        ));
+        env.add_rule(Rule::new(
+            MettaValue::SExpr(vec![var("lookup")]),
+            MettaValue::Long(0),
+        ));
+        env.add_rule(key_rule(2));
+        env.add_rule(Rule::new(
+            MettaValue::SExpr(vec![var("other"), var("$x")]),
+            var("$x"),
+        ));
+        let wildcard = Rule::new(MettaValue::SExpr(vec![var("$f"), var("$x")]), var("$x"));
+        env.add_rule(wildcard.clone());
+        env.add_rule(Rule::new(
+            MettaValue::SExpr(vec![MettaValue::SExpr(vec![var("curry")]), var("$x")]),
+            var("$x"),
+        ));
+
+        let rules = env.find_rules_by_head("lookup");
+        let lhs: Vec<MettaValue> = rules.iter().map(|rule| rule.lhs.clone()).collect();
+        assert_eq!(
+            lhs,
+            vec![
+                MettaValue::SExpr(vec![var("lookup")]),
+                key_rule(1).lhs,
+                key_rule(2).lhs,
+                MettaValue::SExpr(vec![var("lookup"), var("$k"), var("$default")]),
+                wildcard.lhs,
+            ]
+        );
+
+        assert_eq!(env.find_rules_by_head("missing").len(), 1);
+    }
+
+    fn type_check_source(env: &Environment, src: &str) -> Vec<TypeMismatch> {
+        let forms = crate::tree_sitter_parser::TreeSitterMettaParser::new()
+            .unwrap()
+            .parse(src)
+            .unwrap();
+        env.type_check(&forms)
+    }
+
+    #[test]
+    fn test_type_check_accepts_well_typed_program() {
+        let src = r#"
+            (: greet (-> String Number String))
+            (: even (-> Number Bool))
+            (= (greet $name $n) (if (even $n) $name "odd"))
+            !(greet "bob" 4)
+            !(quote (greet 1 2 3))
+            !(even $x)
+        "#;
+        assert_eq!(type_check_source(&Environment::new(), src), vec![]);
+    }
+
+    #[test]
+    fn test_type_check_reports_wrong_literal_and_arity() {
+        let src = "(: inc (-> Number Number))\n!(inc \"one\")\n!(+ 1 (inc 2 3))\n";
+        let mismatches = type_check_source(&Environment::new(), src);
+        assert_eq!(mismatches.len(), 2);
+        assert_eq!(
+            mismatches[0].message,
+            "argument 1 of 'inc' should be Number, got String \"one\""
+        );
+        assert_eq!(
+            mismatches[0].to_string(),
+            "2:7: argument 1 of 'inc' should be Number, got String \"one\""
+        );
+        assert_eq!(mismatches[1].message, "'inc' expects 1 argument, got 2");
+        assert_eq!(mismatches[1].span.start, crate::ir::Position::new(2, 6));
+
+        // Signatures already asserted in the environment are used too
+        let mut env = Environment::new();
+        env.add_type(
+            "shout".to_string(),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("->".to_string()),
+                MettaValue::Atom("String".to_string()),
+                MettaValue::Atom("String".to_string()),
+            ]),
+        );
+        let mismatches = type_check_source(&env, "!(shout True)");
+        assert_eq!(mismatches.len(), 1);
+        assert_eq!(mismatches[0].function, "shout");
+    }
+
+    #[test]
+    fn test_state_ids_are_unique_across_environments() {
+        let first = Environment::new();
+        let second = Environment::new();
+        let a = first.new_state(MettaValue::Long(1));
+        let b = second.new_state(MettaValue::Long(2));
+        assert_ne!(a, b);
+        assert_eq!(second.get_state(a), None);
+        assert!(second.change_state(a, MettaValue::Long(3)).is_err());
+        assert_eq!(first.get_state(a), Some(MettaValue::Long(1)));
+    }
+
+    #[test]
+    fn test_snapshot_restore_rolls_back_rules_facts_states_and_spaces() {
+        let mut env = Environment::new();
+        env.add_rule(key_rule(1));
+        let fact = MettaValue::SExpr(vec![
+            MettaValue::Atom("fact".to_string()),
+            MettaValue::Long(1),
+        ]);
+        env.add_to_space(&fact);
+        let counter = env.new_state(MettaValue::Long(0));
+        let space = env.new_space();
+        env.add_to_named_space(space, MettaValue::Long(1));
+        let before = env.stats();
+
+        let snapshot = env.snapshot();
+
+        env.add_rule(key_rule(2));
+        env.add_rule(key_rule(1));
+        env.add_to_space(&MettaValue::SExpr(vec![
+            MettaValue::Atom("fact".to_string()),
+            MettaValue::Long(2),
+        ]));
+        env.set_doc("key", "Looks up a key");
+        env.change_state(counter, MettaValue::Long(5)).unwrap();
+        let scratch = env.new_state(MettaValue::Bool(true));
+        env.add_to_named_space(space, MettaValue::Long(2));
+        let scratch_space = env.new_space();
+        assert_ne!(env.stats(), before);
+
+        env.restore(snapshot.clone());
+        assert_eq!(env.stats(), before);
+        assert_eq!(env.rule_count(), 1);
+        assert!(env.get_doc("key").is_none());
+        assert_eq!(env.get_state(counter), Some(MettaValue::Long(0)));
+        assert_eq!(env.get_state(scratch), None);
+        assert_eq!(
+            env.named_space_atoms(space),
+            Some(vec![MettaValue::Long(1)])
+        );
+        assert_eq!(env.named_space_atoms(scratch_space), None);
+        let pattern = MettaValue::SExpr(vec![
+            MettaValue::Atom("fact".to_string()),
+            MettaValue::Atom("$n".to_string()),
+        ]);
+        assert_eq!(
+            env.match_space(&pattern, &MettaValue::Atom("$n".to_string())),
+            vec![MettaValue::Long(1)]
+        );
+
+        // Writing after a restore leaves the snapshot intact for another rollback
+        env.add_rule(key_rule(3));
+        env.restore(snapshot);
+        assert_eq!(env.stats(), before);
+    }
+}
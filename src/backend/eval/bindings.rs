@@ -41,13 +41,9 @@ fn pattern_mismatch_suggestion(pattern: &MettaValue, value: &MettaValue) -> Stri
     // Check for literal mismatch inside structures
     if let (MettaValue::SExpr(p_items), MettaValue::SExpr(v_items)) = (pattern, value) {
         for (i, (p, v)) in p_items.iter().zip(v_items.iter()).enumerate() {
-            // Skip if pattern is a variable (starts with $, &, or ')
+            // Skip if pattern is a variable (starts with $ or ')
             if let MettaValue::Atom(name) = p {
-                if name.starts_with('$')
-                    || name.starts_with('&')
-                    || name.starts_with('\'')
-                    || name == "_"
-                {
+                if name.starts_with('$') || name.starts_with('\'') || name == "_" {
                     continue;
                 }
             }
@@ -65,23 +61,53 @@ fn pattern_mismatch_suggestion(pattern: &MettaValue, value: &MettaValue) -> Stri
     "Hint: pattern structure doesn't match value. Check that variable names align with value positions.".to_string()
 }
 
+/// Find the first place where a destructuring pattern and a value are both
+/// expressions of different lengths, searching positions in order
+fn destructuring_arity_mismatch<'a>(
+    pattern: &'a MettaValue,
+    value: &'a MettaValue,
+) -> Option<(&'a MettaValue, &'a MettaValue)> {
+    let (MettaValue::SExpr(p_items), MettaValue::SExpr(v_items)) = (pattern, value) else {
+        return None;
+    };
+    if p_items.len() != v_items.len() {
+        return Some((pattern, value));
+    }
+    p_items
+        .iter()
+        .zip(v_items)
+        .find_map(|(p, v)| destructuring_arity_mismatch(p, v))
+}
+
 /// Evaluate let binding: (let pattern value body)
 /// Evaluates value, binds it to pattern, and evaluates body with those bindings
 /// Supports both simple variable binding and pattern matching:
 ///   - (let $x 42 body) - simple binding
 ///   - (let ($a $b) (tuple 1 2) body) - destructuring pattern
+///
+/// A destructuring pattern never binds part of a value: if it (or any expression
+/// nested in it) has a different number of elements than the value at that position,
+/// the result is `(Error "let: pattern/value arity mismatch: ..." ArityError)`.
+///
+/// With rule tracing on, each successful binding is reported to the trace sink,
+/// naming the pattern's variables and their values.
+///
+/// If value is nondeterministic the body is evaluated once per result, so
+/// (let $x (superpose (1 2 3)) (* $x $x)) yields 1, 4 and 9. Nested lets multiply
+/// out: every combination of their values is visited.
 pub(super) fn eval_let(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     let args = &items[1..];
     trace!(target: "mettatron::eval::eval_let", ?args, ?items);
 
-    if args.len() < 3 {
+    let expected = super::special_form_arity("let").expect("let has an arity");
+    if args.len() < expected {
         let got = args.len();
         let err = MettaValue::Error(
             format!(
-                "let requires exactly 3 arguments, got {}. Usage: (let pattern value body)",
-                got
+                "let requires exactly {} arguments, got {}. Usage: (let pattern value body)",
+                expected, got
             ),
-            Arc::new(MettaValue::SExpr(args.to_vec())),
+            Arc::new(MettaValue::Atom("ArityError".to_string())),
         );
         return (vec![err], env);
     }
@@ -94,15 +120,39 @@ pub(super) fn eval_let(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     let (value_results, value_env) = eval(value_expr.clone(), env);
 
     // Handle nondeterminism: if value evaluates to multiple results, try each one
+    // The environment is threaded through the branches so space updates persist
     let mut all_results = Vec::new();
+    let mut current_env = value_env;
 
     for value in value_results {
         // Try to match the pattern against the value
         if let Some(bindings) = pattern_match(pattern, &value) {
+            if let Some(sink) = current_env.rule_trace() {
+                sink(&format!(
+                    "let {} = {} with {}",
+                    pattern,
+                    value,
+                    bindings.to_metta_value()
+                ));
+            }
             // Apply bindings to the body and evaluate it
             let instantiated_body = apply_bindings(body, &bindings);
-            let (body_results, _) = eval(instantiated_body, value_env.clone());
+            let (body_results, body_env) = eval(instantiated_body, current_env);
+            current_env = body_env;
             all_results.extend(body_results);
+        } else if let Some((p, v)) = destructuring_arity_mismatch(pattern, &value) {
+            let err = MettaValue::Error(
+                format!(
+                    "let: pattern/value arity mismatch: pattern {} has {} element(s) but value {} has {}",
+                    super::friendly_value_repr(p),
+                    sexpr_len(p),
+                    super::friendly_value_repr(v),
+                    sexpr_len(v)
+                ),
+                Arc::new(MettaValue::Atom("ArityError".to_string())),
+            );
+            debug!(target: "mettatron::eval::eval_let", ?err, "Destructuring arity mismatch");
+            all_results.push(err);
         } else {
             // Pattern match failed - provide helpful suggestion
             let suggestion = pattern_mismatch_suggestion(pattern, &value);
@@ -120,12 +170,139 @@ pub(super) fn eval_let(items: Vec<MettaValue>, env: Environment) -> EvalResult {
         }
     }
 
-    (all_results, value_env)
+    (all_results, current_env)
+}
+
+/// Sequential let: (let* ((pattern value) ...) body)
+/// Each binding sees the ones before it; the form is rewritten into nested lets.
+/// The body may be left out, in which case the value of the last binding is
+/// returned: (let* (($x 1) ($y (+ $x 1)))) -> 2. With no bindings and no body,
+/// (let* ()) returns unit `()`.
+pub(super) fn eval_let_star(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_let_star", ?items);
+    const USAGE: &str = "Usage: (let* ((pattern value) ...) body)";
+
+    let (bindings, body) = match &items[1..] {
+        [MettaValue::SExpr(bindings)] => (bindings, None),
+        [MettaValue::SExpr(bindings), body] => (bindings, Some(body)),
+        args => {
+            let err = MettaValue::Error(
+                format!(
+                    "let* expects a binding list and an optional body, got {} argument(s). {}",
+                    args.len(),
+                    USAGE
+                ),
+                Arc::new(MettaValue::SExpr(args.to_vec())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let mut pairs = Vec::with_capacity(bindings.len());
+    for binding in bindings {
+        match binding {
+            MettaValue::SExpr(pair) if pair.len() == 2 => pairs.push((&pair[0], &pair[1])),
+            _ => {
+                let err = MettaValue::Error(
+                    format!(
+                        "let*: binding {} is not a (pattern value) pair. {}",
+                        super::friendly_value_repr(binding),
+                        USAGE
+                    ),
+                    Arc::new(binding.clone()),
+                );
+                return (vec![err], env);
+            }
+        }
+    }
+
+    // Without a body the last binding's value is the result
+    let body = match (body, pairs.pop()) {
+        (Some(body), last) => {
+            pairs.extend(last);
+            body.clone()
+        }
+        (None, Some((_, value))) => value.clone(),
+        (None, None) => return (vec![MettaValue::SExpr(vec![])], env),
+    };
+
+    let nested = pairs
+        .into_iter()
+        .rev()
+        .fold(body, |body, (pattern, value)| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("let".to_string()),
+                pattern.clone(),
+                value.clone(),
+                body,
+            ])
+        });
+    eval(nested, env)
+}
+
+fn sexpr_len(value: &MettaValue) -> usize {
+    match value {
+        MettaValue::SExpr(items) => items.len(),
+        _ => 1,
+    }
+}
+
+/// Name usable after `&` in a `bind!` token: not empty, not a variable
+fn is_token_name(name: &str) -> bool {
+    !name.is_empty() && !name.starts_with('$') && !name.starts_with('&')
+}
+
+/// Bind: (bind! &token value)
+/// Evaluates value and registers it under the token, after which the atom `&token`
+/// evaluates to it: (bind! &limit (* 2 50)) then (< 3 &limit) -> True. The leading
+/// `&` may be left out: (bind! limit ...) binds `&limit` too. Binding a token again
+/// replaces its value. Returns unit `()`.
+pub(super) fn eval_bind(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_bind", ?items);
+    require_args_with_usage!("bind!", items, env, "(bind! &token value)");
+
+    let (token, value_expr) = match &items[1..] {
+        [MettaValue::Atom(name), value] if name.starts_with('&') && is_token_name(&name[1..]) => {
+            (name.clone(), value)
+        }
+        [MettaValue::Atom(name), value] if is_token_name(name) => (format!("&{}", name), value),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "bind!: expected a token such as &name and a value, got {}. Usage: (bind! &token value)",
+                    super::friendly_value_repr(&MettaValue::SExpr(items[1..].to_vec()))
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let (results, mut new_env) = eval(value_expr.clone(), env);
+    let value = match results.as_slice() {
+        [err @ MettaValue::Error(_, _)] => return (vec![err.clone()], new_env),
+        [value] => value.clone(),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "bind!: value for {} must evaluate to exactly one result, got {}",
+                    token,
+                    results.len()
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], new_env);
+        }
+    };
+
+    new_env.bind_token(&token, value);
+    (vec![MettaValue::SExpr(vec![])], new_env)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::run::eval_last;
 
     #[test]
     fn test_let_simple_binding() {
@@ -405,13 +582,13 @@ mod tests {
     fn test_let_with_different_variable_types() {
         let env = Environment::new();
 
-        // Test different variable prefixes: $, &, '
+        // Test different variable prefixes: $ and '
         let mixed_vars = MettaValue::SExpr(vec![
             MettaValue::Atom("let".to_string()),
             MettaValue::SExpr(vec![
                 MettaValue::Atom("triple".to_string()),
                 MettaValue::Atom("$x".to_string()),
-                MettaValue::Atom("&y".to_string()),
+                MettaValue::Atom("$y".to_string()),
                 MettaValue::Atom("'z".to_string()),
             ]),
             MettaValue::SExpr(vec![
@@ -425,7 +602,7 @@ mod tests {
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::SExpr(vec![
                     MettaValue::Atom("+".to_string()),
-                    MettaValue::Atom("&y".to_string()),
+                    MettaValue::Atom("$y".to_string()),
                     MettaValue::Atom("'z".to_string()),
                 ]),
             ]),
@@ -779,4 +956,257 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], MettaValue::Long(30)); // (5 * 5) + 5 = 30
     }
+
+    #[test]
+    fn test_let_fans_out_over_superpose() {
+        let env = Environment::new();
+
+        // (let $x (superpose (1 2 3)) (* $x $x)) -> 1 4 9
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("let".to_string()),
+            MettaValue::Atom("$x".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("superpose".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Long(1),
+                    MettaValue::Long(2),
+                    MettaValue::Long(3),
+                ]),
+            ]),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("*".to_string()),
+                MettaValue::Atom("$x".to_string()),
+                MettaValue::Atom("$x".to_string()),
+            ]),
+        ]);
+
+        let (results, _) = eval(value, env);
+        assert_eq!(
+            results,
+            vec![
+                MettaValue::Long(1),
+                MettaValue::Long(4),
+                MettaValue::Long(9)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_let_multiplies_search_space() {
+        let env = Environment::new();
+
+        let superpose = |values: &[i64]| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("superpose".to_string()),
+                MettaValue::SExpr(values.iter().map(|v| MettaValue::Long(*v)).collect()),
+            ])
+        };
+
+        // (let $x (superpose (1 2)) (let $y (superpose (10 20 30)) (+ $x $y)))
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("let".to_string()),
+            MettaValue::Atom("$x".to_string()),
+            superpose(&[1, 2]),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("let".to_string()),
+                MettaValue::Atom("$y".to_string()),
+                superpose(&[10, 20, 30]),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("+".to_string()),
+                    MettaValue::Atom("$x".to_string()),
+                    MettaValue::Atom("$y".to_string()),
+                ]),
+            ]),
+        ]);
+
+        let (results, _) = eval(value, env);
+        let expected: Vec<MettaValue> = [11, 21, 31, 12, 22, 32]
+            .into_iter()
+            .map(MettaValue::Long)
+            .collect();
+        assert_eq!(results, expected);
+    }
+
+    #[test]
+    fn test_let_star_sequential_bindings() {
+        assert_eq!(
+            eval_last("!(let* (($x 1) ($y (+ $x 1))) (* $x $y))"),
+            vec![MettaValue::Long(2)]
+        );
+    }
+
+    #[test]
+    fn test_let_star_without_body_returns_last_binding() {
+        assert_eq!(
+            eval_last("!(let* (($x 1) ($y (+ $x 1)) ($z (* $y 10))))"),
+            vec![MettaValue::Long(20)]
+        );
+        assert_eq!(eval_last("!(let* (($x 5)))"), vec![MettaValue::Long(5)]);
+    }
+
+    #[test]
+    fn test_let_star_empty_bindings() {
+        assert_eq!(eval_last("!(let* ())"), vec![MettaValue::SExpr(vec![])]);
+        assert_eq!(eval_last("!(let* () (+ 1 2))"), vec![MettaValue::Long(3)]);
+    }
+
+    #[test]
+    fn test_let_star_malformed_binding() {
+        let results = eval_last("!(let* ($x 1) $x)");
+        assert!(
+            matches!(&results[..], [MettaValue::Error(msg, _)] if msg.contains("not a (pattern value) pair"))
+        );
+    }
+
+    #[test]
+    fn test_let_bindings_appear_in_rule_trace() {
+        use std::sync::Mutex;
+
+        let state =
+            crate::backend::compile::compile("!(let ($a $b) (1 2) (let* (($sum (+ $a $b))) $sum))")
+                .unwrap();
+        let mut env = state.environment;
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink_lines = lines.clone();
+        env.set_rule_trace(Some(Arc::new(move |line: &str| {
+            sink_lines.lock().unwrap().push(line.to_string())
+        })));
+
+        let (results, _) = eval(state.source[0].clone(), env);
+        assert_eq!(results, vec![MettaValue::Long(3)]);
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "let ($a $b) = (1 2) with (($a 1) ($b 2))".to_string(),
+                "let $sum = 3 with (($sum 3))".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sibling_lets_do_not_interfere() {
+        let results = eval_last("!((let $x 1 (+ $x 10)) (let $x 2 (+ $x 20)))");
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Long(11),
+                MettaValue::Long(22)
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_let_bound_variable_not_visible_outside() {
+        // $x is only bound inside the inner let; the outer body sees it unbound
+        let results = eval_last("!(let $t (let $x 1 $x) ($t $x))");
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Long(1),
+                MettaValue::Atom("$x".to_string())
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_nested_let_shadows_outer_binding() {
+        assert_eq!(
+            eval_last("!(let $x 1 (let $x 2 $x))"),
+            vec![MettaValue::Long(2)]
+        );
+        // The inner value expression still sees the outer binding
+        assert_eq!(
+            eval_last("!(let $x 1 (let $x (+ $x 5) (* $x 2)))"),
+            vec![MettaValue::Long(12)]
+        );
+        // Variables not rebound by the inner pattern are still substituted
+        assert_eq!(
+            eval_last("!(let $x 1 (let $y 2 (+ $x $y)))"),
+            vec![MettaValue::Long(3)]
+        );
+    }
+
+    fn assert_arity_mismatch(src: &str) {
+        let state = crate::backend::compile::compile(src).unwrap();
+        let (results, _) = eval(state.source[0].clone(), state.environment);
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            err @ MettaValue::Error(msg, _) => {
+                assert!(
+                    msg.starts_with("let: pattern/value arity mismatch"),
+                    "{}",
+                    msg
+                );
+                assert_eq!(
+                    err.error_kind(),
+                    Some(crate::backend::models::ErrorKind::ArityMismatch)
+                );
+            }
+            other => panic!("Expected arity mismatch error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_let_destructuring_value_too_long() {
+        assert_arity_mismatch("!(let ($a $b) (quote (pair 1 2 3)) $a)");
+        // Nested pattern against a longer nested value
+        assert_arity_mismatch("!(let (($a $b) $c) (quote ((1 2 3) 4)) $a)");
+    }
+
+    #[test]
+    fn test_let_destructuring_value_too_short() {
+        assert_arity_mismatch("!(let (pair $a $b $c) (quote (pair 1 2)) $a)");
+        assert_arity_mismatch("!(let ($a ($b $c)) (quote (1 (2))) $b)");
+    }
+
+    #[test]
+    fn test_bind_token_used_in_arithmetic() {
+        let (outputs, env) = crate::backend::run::eval_forms(
+            r#"
+            !(bind! foo (+ 1 2))
+            !(* &foo 10)
+            !(bind! &foo 5)
+            !(+ &foo 1)
+            !(match &self (missing $x) $x)
+        "#,
+            Environment::new(),
+        );
+
+        assert_eq!(outputs[0], vec![MettaValue::SExpr(vec![])]);
+        assert_eq!(outputs[1], vec![MettaValue::Long(30)]);
+        // Rebinding replaces the value
+        assert_eq!(outputs[3], vec![MettaValue::Long(6)]);
+        // Unbound references such as the self space are left alone
+        assert!(outputs[4].is_empty());
+        assert_eq!(env.get_token("&foo"), Some(MettaValue::Long(5)));
+
+        // A `&name` atom built by an embedder is resolved the same way
+        let call = MettaValue::SExpr(vec![
+            MettaValue::Atom("-".to_string()),
+            MettaValue::Atom("&foo".to_string()),
+            MettaValue::Long(1),
+        ]);
+        assert_eq!(eval(call, env.clone()).0, vec![MettaValue::Long(4)]);
+
+        // Tokens that were never bound stay as they are
+        let unbound = MettaValue::Atom("&unbound".to_string());
+        assert_eq!(eval(unbound.clone(), env).0, vec![unbound]);
+    }
+
+    #[test]
+    fn test_bind_rejects_variables_and_nondeterministic_values() {
+        let run = |src: &str| {
+            let state = crate::backend::compile::compile(src).unwrap();
+            eval(state.source[0].clone(), state.environment).0
+        };
+
+        let results = eval_last("!(bind! $x 1)");
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("token")));
+        // `& foo` is a bare `&` followed by `foo`, not a token
+        let results = eval_last("!(bind! & foo 1)");
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("token")));
+
+        let results = eval_last("!(bind! &x (superpose (1 2)))");
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("exactly one")));
+    }
 }
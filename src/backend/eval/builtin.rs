@@ -43,6 +43,42 @@ pub(crate) fn try_eval_builtin(op: &str, args: &[MettaValue]) -> Option<MettaVal
         "atan-math" => Some(eval_atan(args)),
         "isnan-math" => Some(eval_isnan(args)),
         "isinf-math" => Some(eval_isinf(args)),
+
+        // Symbol/string conversions
+        "atom-to-string" => Some(eval_atom_to_string(args)),
+        "string-to-atom" => Some(eval_string_to_atom(args)),
+        "string-split" => Some(eval_string_split(args)),
+        "string-join" => Some(eval_string_join(args)),
+
+        // Type predicates
+        "number?" => Some(eval_type_predicate(args, "number?", |v| {
+            matches!(v, MettaValue::Long(_) | MettaValue::Float(_))
+        })),
+        "string?" => Some(eval_type_predicate(args, "string?", |v| {
+            matches!(v, MettaValue::String(_))
+        })),
+        "symbol?" => Some(eval_type_predicate(
+            args,
+            "symbol?",
+            |v| matches!(v, MettaValue::Atom(name) if !name.starts_with('$')),
+        )),
+        "variable?" => Some(eval_type_predicate(
+            args,
+            "variable?",
+            |v| matches!(v, MettaValue::Atom(name) if name.starts_with('$')),
+        )),
+        "expression?" => Some(eval_type_predicate(args, "expression?", |v| {
+            matches!(v, MettaValue::SExpr(_) | MettaValue::Nil)
+        })),
+
+        // String formatting
+        "format" => Some(eval_format(args)),
+
+        // URIs
+        "uri-scheme" => Some(eval_uri_scheme(args)),
+
+        // List construction
+        "list" => Some(eval_list(args)),
         _ => None,
     }
 }
@@ -77,45 +113,62 @@ where
 }
 
 /// Evaluate power (exponentiation) with overflow checking
-/// Takes base (first argument) and power (second argument) and returns result of base ^ power
-/// Negative exponents are not supported for integer exponentiation
+/// Takes base (first argument) and an integer power (second argument) and returns base ^ power
+///
+/// An integer base with a non-negative power gives an integer, and overflow is an
+/// error rather than a wrapped value: (pow-math 2 10) -> 1024. A negative power or a
+/// float base gives a float: (pow-math 2 -1) -> 0.5, (pow-math 1.5 2) -> 2.25. A float
+/// result that is infinite or NaN is an error too: (pow-math 0 -1), (pow-math 10.0 400).
 fn eval_power(args: &[MettaValue]) -> MettaValue {
     require_builtin_args!("Power", args, 2);
 
-    let base = match extract_long(&args[0], "Cannot perform power") {
+    let exp = match extract_long(&args[1], "Cannot perform power") {
         Ok(n) => n,
         Err(e) => return e,
     };
 
-    let exp = match extract_long(&args[1], "Cannot perform power") {
-        Ok(n) => n,
-        Err(e) => return e,
+    let base = match &args[0] {
+        MettaValue::Long(n) => *n,
+        other => {
+            return match extract_float(other, "Cannot perform power") {
+                Ok(f) => float_power(f, exp),
+                Err(e) => e,
+            }
+        }
     };
 
-    // Negative exponents result in fractions, which are not integers
     if exp < 0 {
-        return MettaValue::Error(
-            format!(
-                "Negative exponent not supported for integer exponentiation: {} ^ {}",
-                base, exp
-            ),
-            Arc::new(MettaValue::Atom("ArithmeticError".to_string())),
-        );
+        return float_power(base as f64, exp);
     }
 
-    // Use checked_pow for overflow protection
-    match base.checked_pow(exp as u32) {
+    // Use checked_pow for overflow protection; 0, 1 and -1 stay small for any power
+    let result = match base {
+        0 | 1 => Some(if exp == 0 { 1 } else { base }),
+        -1 => Some(if exp % 2 == 0 { 1 } else { -1 }),
+        _ => u32::try_from(exp).ok().and_then(|e| base.checked_pow(e)),
+    };
+    match result {
         Some(result) => MettaValue::Long(result),
         None => MettaValue::Error(
-            format!(
-                "Arithmetic overflow: {} ^ {} exceeds integer bounds",
-                base, exp
-            ),
+            format!("pow overflow: {} ^ {} exceeds integer bounds", base, exp),
             Arc::new(MettaValue::Atom("ArithmeticError".to_string())),
         ),
     }
 }
 
+/// Float power for `eval_power`, rejecting results that are not finite
+fn float_power(base: f64, exp: i64) -> MettaValue {
+    let result = base.powf(exp as f64);
+    if result.is_finite() {
+        MettaValue::Float(result)
+    } else {
+        MettaValue::Error(
+            format!("pow: {} ^ {} is not a finite number", base, exp),
+            Arc::new(MettaValue::Atom("ArithmeticError".to_string())),
+        )
+    }
+}
+
 /// Evaluate a comparison operation with strict type checking
 fn eval_comparison<F>(args: &[MettaValue], op: F) -> MettaValue
 where
@@ -171,6 +224,272 @@ fn eval_logical_not(args: &[MettaValue]) -> MettaValue {
     }
 }
 
+/// Convert a symbol to a string: (atom-to-string foo) -> "foo"
+fn eval_atom_to_string(args: &[MettaValue]) -> MettaValue {
+    require_builtin_args!("'atom-to-string'", args, 1, "(atom-to-string symbol)");
+
+    match &args[0] {
+        MettaValue::Atom(name) => MettaValue::String(name.clone()),
+        other => MettaValue::Error(
+            format!(
+                "'atom-to-string': expected Symbol, got {}",
+                other.friendly_type_name()
+            ),
+            Arc::new(MettaValue::Atom("TypeError".to_string())),
+        ),
+    }
+}
+
+/// String interpolation: (format "x={} y={}" 1 2) -> "x=1 y=2"
+/// Each `{}` is replaced, left to right, by the next argument printed as it would be
+/// read back (strings keep their quotes); `{{` and `}}` stand for literal braces.
+/// The number of placeholders must match the number of arguments.
+fn eval_format(args: &[MettaValue]) -> MettaValue {
+    const USAGE: &str = "(format \"template\" arg...)";
+
+    let template = match args.first() {
+        Some(MettaValue::String(template)) => template,
+        Some(other) => {
+            return MettaValue::Error(
+                format!(
+                    "'format': expected String template, got {}",
+                    other.friendly_type_name()
+                ),
+                Arc::new(MettaValue::Atom("TypeError".to_string())),
+            )
+        }
+        None => {
+            return MettaValue::Error(
+                format!("'format' requires a template string. Usage: {}", USAGE),
+                Arc::new(MettaValue::Atom("ArityError".to_string())),
+            )
+        }
+    };
+    let values = &args[1..];
+
+    let mut out = String::with_capacity(template.len());
+    let mut placeholders = 0;
+    let mut chars = template.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' if chars.peek() == Some(&'}') => {
+                chars.next();
+                if let Some(value) = values.get(placeholders) {
+                    out.push_str(&value.to_string());
+                }
+                placeholders += 1;
+            }
+            '{' | '}' => {
+                return MettaValue::Error(
+                    format!(
+                        "'format': unmatched '{}' in template \"{}\"; write '{}{}' for a literal",
+                        ch, template, ch, ch
+                    ),
+                    Arc::new(MettaValue::Atom("ValueError".to_string())),
+                )
+            }
+            _ => out.push(ch),
+        }
+    }
+
+    if placeholders != values.len() {
+        return MettaValue::Error(
+            format!(
+                "'format': template has {} placeholder{} but {} argument{} given. Usage: {}",
+                placeholders,
+                if placeholders == 1 { "" } else { "s" },
+                values.len(),
+                if values.len() == 1 { " was" } else { "s were" },
+                USAGE
+            ),
+            Arc::new(MettaValue::Atom("ArityError".to_string())),
+        );
+    }
+    MettaValue::String(out)
+}
+
+/// Type predicates: (number? 1) -> True, (symbol? "s") -> False
+/// Symbols are atoms that are not `$variables`; `()` counts as an expression.
+fn eval_type_predicate(args: &[MettaValue], op: &str, pred: fn(&MettaValue) -> bool) -> MettaValue {
+    require_builtin_args!(format!("'{}'", op), args, 1, format!("({} value)", op));
+    MettaValue::Bool(pred(&args[0]))
+}
+
+/// Scheme of a URI: (uri-scheme `http://x/y`) -> "http"
+/// URI literals are read as strings, so plain strings are accepted too. A relative
+/// reference has no scheme and gives `Empty`; a malformed URI is a ValueError.
+fn eval_uri_scheme(args: &[MettaValue]) -> MettaValue {
+    require_builtin_args!("'uri-scheme'", args, 1, "(uri-scheme uri)");
+
+    let uri = match &args[0] {
+        MettaValue::String(uri) => uri,
+        other => {
+            return MettaValue::Error(
+                format!(
+                    "'uri-scheme': expected URI, got {}",
+                    other.friendly_type_name()
+                ),
+                Arc::new(MettaValue::Atom("TypeError".to_string())),
+            )
+        }
+    };
+
+    match crate::tree_sitter_parser::validate_uri(uri) {
+        Ok(Some(scheme)) => MettaValue::String(scheme.to_ascii_lowercase()),
+        Ok(None) => MettaValue::Atom("Empty".to_string()),
+        Err(reason) => MettaValue::Error(
+            format!("'uri-scheme': invalid URI \"{}\": {}", uri, reason),
+            Arc::new(MettaValue::Atom("ValueError".to_string())),
+        ),
+    }
+}
+
+/// Build a list from the evaluated arguments: (list 1 (+ 1 1) c) -> (1 2 c)
+/// Lists share the S-expression representation, so `(list)` is the empty expression `()`
+/// and a list prints exactly like the expression holding the same elements.
+fn eval_list(args: &[MettaValue]) -> MettaValue {
+    MettaValue::SExpr(args.to_vec())
+}
+
+/// Split a string on a separator: (string-split "a,b,c" ",") -> ("a" "b" "c")
+/// Adjacent separators give empty strings, and the empty string splits into `()`.
+/// An empty separator is an error rather than a split into characters.
+fn eval_string_split(args: &[MettaValue]) -> MettaValue {
+    require_builtin_args!(
+        "'string-split'",
+        args,
+        2,
+        "(string-split \"text\" \"separator\")"
+    );
+
+    let (text, separator) = match (&args[0], &args[1]) {
+        (MettaValue::String(text), MettaValue::String(separator)) => (text, separator),
+        (MettaValue::String(_), other) | (other, _) => {
+            return MettaValue::Error(
+                format!(
+                    "'string-split': expected String, got {}",
+                    other.friendly_type_name()
+                ),
+                Arc::new(MettaValue::Atom("TypeError".to_string())),
+            )
+        }
+    };
+    if separator.is_empty() {
+        return MettaValue::Error(
+            "'string-split': separator must not be empty".to_string(),
+            Arc::new(MettaValue::Atom("ValueError".to_string())),
+        );
+    }
+    if text.is_empty() {
+        return MettaValue::SExpr(vec![]);
+    }
+
+    MettaValue::SExpr(
+        text.split(separator.as_str())
+            .map(|part| MettaValue::String(part.to_string()))
+            .collect(),
+    )
+}
+
+/// Join an expression of strings: (string-join ("a" "b") "-") -> "a-b"
+/// Joining the empty expression gives the empty string.
+fn eval_string_join(args: &[MettaValue]) -> MettaValue {
+    require_builtin_args!(
+        "'string-join'",
+        args,
+        2,
+        "(string-join (\"a\" \"b\") \"separator\")"
+    );
+
+    let parts: &[MettaValue] = match &args[0] {
+        MettaValue::SExpr(parts) => parts,
+        MettaValue::Nil => &[],
+        other => {
+            return MettaValue::Error(
+                format!(
+                    "'string-join': expected Expression of strings, got {}",
+                    other.friendly_type_name()
+                ),
+                Arc::new(MettaValue::Atom("TypeError".to_string())),
+            )
+        }
+    };
+    let separator = match &args[1] {
+        MettaValue::String(separator) => separator,
+        other => {
+            return MettaValue::Error(
+                format!(
+                    "'string-join': expected String separator, got {}",
+                    other.friendly_type_name()
+                ),
+                Arc::new(MettaValue::Atom("TypeError".to_string())),
+            )
+        }
+    };
+
+    let mut strings = Vec::with_capacity(parts.len());
+    for part in parts {
+        match part {
+            MettaValue::String(s) => strings.push(s.as_str()),
+            other => {
+                return MettaValue::Error(
+                    format!(
+                        "'string-join': expected String element, got {}",
+                        other.friendly_type_name()
+                    ),
+                    Arc::new(MettaValue::Atom("TypeError".to_string())),
+                )
+            }
+        }
+    }
+    MettaValue::String(strings.join(separator))
+}
+
+/// Convert a string to a symbol: (string-to-atom "foo") -> foo
+/// A name starting with `$` produces a variable atom, e.g. (string-to-atom "$x") -> $x.
+/// The name must be a single non-empty token: strings that are empty or contain
+/// whitespace or parentheses could not be read back as one atom and are rejected.
+fn eval_string_to_atom(args: &[MettaValue]) -> MettaValue {
+    require_builtin_args!("'string-to-atom'", args, 1, "(string-to-atom \"name\")");
+
+    let name = match &args[0] {
+        MettaValue::String(s) => s,
+        other => {
+            return MettaValue::Error(
+                format!(
+                    "'string-to-atom': expected String, got {}",
+                    other.friendly_type_name()
+                ),
+                Arc::new(MettaValue::Atom("TypeError".to_string())),
+            )
+        }
+    };
+
+    let is_token = !name.is_empty()
+        && !name
+            .chars()
+            .any(|c| c.is_whitespace() || c == '(' || c == ')' || c == '"');
+    if !is_token {
+        return MettaValue::Error(
+            format!(
+                "'string-to-atom': {:?} is not a valid symbol name (it must be non-empty and contain no whitespace, parentheses or quotes)",
+                name
+            ),
+            Arc::new(MettaValue::Atom("ValueError".to_string())),
+        );
+    }
+
+    MettaValue::Atom(name.clone())
+}
+
 /// Evaluate division with division-by-zero and overflow checking
 fn eval_division(args: &[MettaValue]) -> MettaValue {
     require_builtin_args!("Division", args, 2);
@@ -188,7 +507,7 @@ fn eval_division(args: &[MettaValue]) -> MettaValue {
     if b == 0 {
         return MettaValue::Error(
             "Division by zero".to_string(),
-            Arc::new(MettaValue::Atom("ArithmeticError".to_string())),
+            Arc::new(MettaValue::Atom("DivisionByZero".to_string())),
         );
     }
 
@@ -220,7 +539,7 @@ fn eval_modulo(args: &[MettaValue]) -> MettaValue {
     if b == 0 {
         return MettaValue::Error(
             "Division by zero".to_string(),
-            Arc::new(MettaValue::Atom("ArithmeticError".to_string())),
+            Arc::new(MettaValue::Atom("DivisionByZero".to_string())),
         );
     }
 
@@ -956,7 +1275,7 @@ mod tests {
                 MettaValue::Long(10),
                 MettaValue::Long(0),
             ]),
-            "ArithmeticError"
+            "DivisionByZero"
         );
         assert_error!(
             MettaValue::SExpr(vec![
@@ -1058,49 +1377,43 @@ mod tests {
 
     #[test]
     fn test_power_negative_exponent() {
-        let env = Environment::new();
-
-        // Test: 2^-3 should produce error (negative exponents not supported for integers)
-        let value = MettaValue::SExpr(vec![
-            MettaValue::Atom("pow-math".to_string()),
-            MettaValue::Long(2),
-            MettaValue::Long(-3),
-        ]);
-        let (results, _) = eval(value, env.clone());
-        assert_eq!(results.len(), 1);
-
-        match &results[0] {
-            MettaValue::Error(msg, details) => {
-                assert!(
-                    msg.contains("Negative exponent"),
-                    "Expected negative exponent error: {}",
-                    msg
-                );
-                assert_eq!(**details, MettaValue::Atom("ArithmeticError".to_string()));
-            }
-            other => panic!("Expected Error, got {:?}", other),
-        }
-
-        // Test: 10^-1 should also produce error
-        let value = MettaValue::SExpr(vec![
-            MettaValue::Atom("pow-math".to_string()),
-            MettaValue::Long(10),
-            MettaValue::Long(-1),
-        ]);
-        let (results, _) = eval(value, env);
-        assert_eq!(results.len(), 1);
+        // A negative exponent gives a float
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Long(2),
+                MettaValue::Long(-1),
+            ]),
+            MettaValue::Float(0.5)
+        );
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Long(2),
+                MettaValue::Long(-3),
+            ]),
+            MettaValue::Float(0.125)
+        );
+    }
 
-        match &results[0] {
-            MettaValue::Error(msg, details) => {
-                assert!(
-                    msg.contains("Negative exponent"),
-                    "Expected negative exponent error: {}",
-                    msg
-                );
-                assert_eq!(**details, MettaValue::Atom("ArithmeticError".to_string()));
-            }
-            other => panic!("Expected Error, got {:?}", other),
-        }
+    #[test]
+    fn test_power_float_base() {
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Float(1.5),
+                MettaValue::Long(2),
+            ]),
+            MettaValue::Float(2.25)
+        );
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Float(4.0),
+                MettaValue::Long(-1),
+            ]),
+            MettaValue::Float(0.25)
+        );
     }
 
     #[test]
@@ -1164,7 +1477,7 @@ mod tests {
         match &results[0] {
             MettaValue::Error(msg, details) => {
                 assert!(
-                    msg.contains("Arithmetic overflow"),
+                    msg.contains("pow overflow"),
                     "Expected overflow error: {}",
                     msg
                 );
@@ -1179,13 +1492,13 @@ mod tests {
             MettaValue::Long(10),
             MettaValue::Long(19),
         ]);
-        let (results, _) = eval(value, env);
+        let (results, _) = eval(value, env.clone());
         assert_eq!(results.len(), 1);
 
         match &results[0] {
             MettaValue::Error(msg, details) => {
                 assert!(
-                    msg.contains("Arithmetic overflow"),
+                    msg.contains("pow overflow"),
                     "Expected overflow error: {}",
                     msg
                 );
@@ -1193,6 +1506,64 @@ mod tests {
             }
             other => panic!("Expected Error, got {:?}", other),
         }
+
+        // Powers too large for u32 still overflow, except for 0, 1 and -1
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("pow-math".to_string()),
+            MettaValue::Long(2),
+            MettaValue::Long(i64::MAX),
+        ]);
+        let (results, _) = eval(value, env);
+        assert!(
+            matches!(&results[..], [MettaValue::Error(msg, _)] if msg.contains("pow overflow"))
+        );
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Long(-1),
+                MettaValue::Long(i64::MAX),
+            ]),
+            MettaValue::Long(-1)
+        );
+    }
+
+    #[test]
+    fn test_power_float_overflow() {
+        // Zero to a negative power and float results past f64::MAX are errors, not inf
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Long(0),
+                MettaValue::Long(-1),
+            ]),
+            "ArithmeticError"
+        );
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Float(10.0),
+                MettaValue::Long(400),
+            ]),
+            "ArithmeticError"
+        );
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Float(f64::NAN),
+                MettaValue::Long(2),
+            ]),
+            "ArithmeticError"
+        );
+
+        // Large but finite float powers are still computed
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("pow-math".to_string()),
+                MettaValue::Float(2.0),
+                MettaValue::Long(1000),
+            ]),
+            MettaValue::Float(2f64.powi(1000))
+        );
     }
 
     #[test]
@@ -2428,4 +2799,329 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], MettaValue::Bool(true));
     }
+
+    #[test]
+    fn test_atom_to_string() {
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("atom-to-string".to_string()),
+                MettaValue::Atom("foo".to_string()),
+            ]),
+            MettaValue::String("foo".to_string())
+        );
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("atom-to-string".to_string()),
+                MettaValue::Long(1),
+            ]),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn test_string_to_atom() {
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("string-to-atom".to_string()),
+                MettaValue::String("foo".to_string()),
+            ]),
+            MettaValue::Atom("foo".to_string())
+        );
+        // A leading $ yields a variable
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("string-to-atom".to_string()),
+                MettaValue::String("$x".to_string()),
+            ]),
+            MettaValue::Atom("$x".to_string())
+        );
+        // Round trip through both conversions
+        assert_eval!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("string-to-atom".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("atom-to-string".to_string()),
+                    MettaValue::Atom("bar-baz".to_string()),
+                ]),
+            ]),
+            MettaValue::Atom("bar-baz".to_string())
+        );
+    }
+
+    fn strings(parts: &[&str]) -> MettaValue {
+        MettaValue::SExpr(
+            parts
+                .iter()
+                .map(|s| MettaValue::String(s.to_string()))
+                .collect(),
+        )
+    }
+
+    fn string_split(text: &str, separator: &str) -> MettaValue {
+        MettaValue::SExpr(vec![
+            MettaValue::Atom("string-split".to_string()),
+            MettaValue::String(text.to_string()),
+            MettaValue::String(separator.to_string()),
+        ])
+    }
+
+    fn string_join(parts: MettaValue, separator: &str) -> MettaValue {
+        MettaValue::SExpr(vec![
+            MettaValue::Atom("string-join".to_string()),
+            parts,
+            MettaValue::String(separator.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_string_split() {
+        assert_eval!(string_split("a,b,c", ","), strings(&["a", "b", "c"]));
+        assert_eval!(string_split("a::b", "::"), strings(&["a", "b"]));
+        assert_eval!(string_split("a,,b,", ","), strings(&["a", "", "b", ""]));
+        assert_eval!(string_split("abc", ","), strings(&["abc"]));
+        assert_eval!(string_split("", ","), MettaValue::SExpr(vec![]));
+        assert_error!(string_split("abc", ""), "ValueError");
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("string-split".to_string()),
+                MettaValue::Atom("abc".to_string()),
+                MettaValue::String(",".to_string()),
+            ]),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn test_string_join() {
+        assert_eval!(
+            string_join(strings(&["a", "b"]), "-"),
+            MettaValue::String("a-b".to_string())
+        );
+        assert_eval!(
+            string_join(strings(&["solo"]), ", "),
+            MettaValue::String("solo".to_string())
+        );
+        assert_eval!(
+            string_join(MettaValue::SExpr(vec![]), "-"),
+            MettaValue::String(String::new())
+        );
+        assert_error!(
+            string_join(
+                MettaValue::SExpr(vec![
+                    MettaValue::String("a".to_string()),
+                    MettaValue::Long(1)
+                ]),
+                "-"
+            ),
+            "TypeError"
+        );
+
+        // Splitting and joining on the same separator round-trips
+        assert_eval!(
+            string_join(string_split("x/y/z", "/"), "/"),
+            MettaValue::String("x/y/z".to_string())
+        );
+    }
+
+    #[test]
+    fn test_string_to_atom_invalid_names() {
+        for name in ["two words", "", "(x)"] {
+            assert_error!(
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("string-to-atom".to_string()),
+                    MettaValue::String(name.to_string()),
+                ]),
+                "ValueError"
+            );
+        }
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("string-to-atom".to_string()),
+                MettaValue::Atom("foo".to_string()),
+            ]),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn test_list_construction() {
+        let list = |items: Vec<MettaValue>| {
+            let mut expr = vec![MettaValue::Atom("list".to_string())];
+            expr.extend(items);
+            MettaValue::SExpr(expr)
+        };
+
+        // (list) -> ()
+        assert_eval!(list(vec![]), MettaValue::SExpr(vec![]));
+
+        // (list 7) -> (7)
+        assert_eval!(
+            list(vec![MettaValue::Long(7)]),
+            MettaValue::SExpr(vec![MettaValue::Long(7)])
+        );
+
+        // (list a (+ 1 2) "s") -> (a 3 "s")
+        let result = MettaValue::SExpr(vec![
+            MettaValue::Atom("a".to_string()),
+            MettaValue::Long(3),
+            MettaValue::String("s".to_string()),
+        ]);
+        assert_eval!(
+            list(vec![
+                MettaValue::Atom("a".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("+".to_string()),
+                    MettaValue::Long(1),
+                    MettaValue::Long(2),
+                ]),
+                MettaValue::String("s".to_string()),
+            ]),
+            result.clone()
+        );
+        assert_eq!(result.to_string(), "(a 3 \"s\")");
+    }
+
+    #[test]
+    fn test_format() {
+        let format = |template: &str, values: Vec<MettaValue>| {
+            let mut expr = vec![
+                MettaValue::Atom("format".to_string()),
+                MettaValue::String(template.to_string()),
+            ];
+            expr.extend(values);
+            MettaValue::SExpr(expr)
+        };
+
+        assert_eval!(
+            format("x={} y={}", vec![MettaValue::Long(1), MettaValue::Long(2)]),
+            MettaValue::String("x=1 y=2".to_string())
+        );
+        // Arguments are evaluated and rendered as they would be read back
+        assert_eval!(
+            format(
+                "{} {} {}",
+                vec![
+                    MettaValue::String("s".to_string()),
+                    MettaValue::SExpr(vec![
+                        MettaValue::Atom("+".to_string()),
+                        MettaValue::Long(1),
+                        MettaValue::Long(2),
+                    ]),
+                    MettaValue::SExpr(vec![
+                        MettaValue::Atom("quote".to_string()),
+                        MettaValue::SExpr(vec![
+                            MettaValue::Atom("a".to_string()),
+                            MettaValue::Bool(true),
+                        ]),
+                    ]),
+                ]
+            ),
+            MettaValue::String("\"s\" 3 (a True)".to_string())
+        );
+        assert_eval!(
+            format("no placeholders", vec![]),
+            MettaValue::String("no placeholders".to_string())
+        );
+
+        // Escaped braces
+        assert_eval!(
+            format("{{{}}} {{}}", vec![MettaValue::Long(5)]),
+            MettaValue::String("{5} {}".to_string())
+        );
+        assert_error!(format("oops {", vec![]), "ValueError");
+        assert_error!(format("oops }", vec![]), "ValueError");
+
+        // Placeholder/argument count mismatch
+        assert_error!(format("{} {}", vec![MettaValue::Long(1)]), "ArityError");
+        assert_error!(
+            format("{}", vec![MettaValue::Long(1), MettaValue::Long(2)]),
+            "ArityError"
+        );
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("format".to_string()),
+                MettaValue::Long(1),
+            ]),
+            "TypeError"
+        );
+    }
+
+    #[test]
+    fn test_uri_scheme() {
+        let uri_scheme = |uri: &str| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("uri-scheme".to_string()),
+                MettaValue::String(uri.to_string()),
+            ])
+        };
+
+        assert_eval!(
+            uri_scheme("http://x/y"),
+            MettaValue::String("http".to_string())
+        );
+        assert_eval!(
+            uri_scheme("URN:isbn:0451450523"),
+            MettaValue::String("urn".to_string())
+        );
+        // Relative references have no scheme
+        assert_eval!(
+            uri_scheme("docs/readme.md"),
+            MettaValue::Atom("Empty".to_string())
+        );
+        assert_error!(uri_scheme("1http://x"), "ValueError");
+        assert_error!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("uri-scheme".to_string()),
+                MettaValue::Long(1),
+            ]),
+            "TypeError"
+        );
+
+        // Written as a literal in source
+        let state =
+            crate::backend::compile::compile("!(uri-scheme `https://example.org/a`)").unwrap();
+        let (results, _) = eval(state.source[0].clone(), state.environment);
+        assert_eq!(results, vec![MettaValue::String("https".to_string())]);
+    }
+
+    #[test]
+    fn test_type_predicates() {
+        let check = |op: &str, value: MettaValue, expected: bool| {
+            let (results, _) = eval(
+                MettaValue::SExpr(vec![MettaValue::Atom(op.to_string()), value.clone()]),
+                Environment::new(),
+            );
+            assert_eq!(
+                results,
+                vec![MettaValue::Bool(expected)],
+                "({} {:?})",
+                op,
+                value
+            );
+        };
+        let atom = |name: &str| MettaValue::Atom(name.to_string());
+        let quoted = |value: MettaValue| MettaValue::SExpr(vec![atom("quote"), value]);
+
+        check("number?", MettaValue::Long(3), true);
+        check("number?", MettaValue::Float(2.5), true);
+        check("number?", MettaValue::String("3".to_string()), false);
+
+        check("string?", MettaValue::String("s".to_string()), true);
+        check("string?", atom("s"), false);
+
+        check("symbol?", atom("foo"), true);
+        check("symbol?", MettaValue::Long(1), false);
+        check("symbol?", quoted(atom("$x")), false);
+
+        check("variable?", quoted(atom("$x")), true);
+        check("variable?", atom("foo"), false);
+
+        check(
+            "expression?",
+            quoted(MettaValue::SExpr(vec![atom("a"), atom("b")])),
+            true,
+        );
+        check("expression?", quoted(MettaValue::SExpr(vec![])), true);
+        check("expression?", atom("a"), false);
+    }
 }
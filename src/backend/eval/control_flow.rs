@@ -3,53 +3,171 @@ use crate::backend::models::{EvalResult, MettaValue};
 use std::sync::Arc;
 use tracing::{debug, trace};
 
-use super::{apply_bindings, eval, pattern_match};
+use super::{apply_bindings, eval, pattern_match, EvalStep};
 
 /// Evaluate if control flow: (if condition then-branch else-branch)
 /// Only evaluates the chosen branch (lazy evaluation)
-pub(super) fn eval_if(items: Vec<MettaValue>, env: Environment) -> EvalResult {
-    let args = &items[1..];
-    trace!(target: "mettatron::eval::eval_if", ?items, ?args);
+///
+/// The condition must evaluate to a Bool: `True` is the only truthy value and `False`
+/// the only falsy one. Any other value yields `(Error "if: condition not boolean" <value>)`.
+/// An error in the condition is propagated, and a condition with no results takes the
+/// else-branch.
+///
+/// The chosen branch is in tail position: it is handed back to the trampoline instead of
+/// being evaluated here, so recursion through `if` does not nest native calls.
+pub(super) fn eval_if(items: Vec<MettaValue>, env: Environment) -> EvalStep {
+    match choose_if_branch(items, env) {
+        Ok((branch, env)) => EvalStep::EvalTail { value: branch, env },
+        Err(result) => EvalStep::Done(result),
+    }
+}
 
-    require_args_with_usage!(
-        "if",
-        items,
-        3,
-        env,
-        "(if condition then-branch else-branch)"
-    );
+/// Evaluate the condition of an `if` and pick the branch to continue with
+/// Usage errors and errors or non-Bool values from the condition are returned as `Err`.
+fn choose_if_branch(
+    items: Vec<MettaValue>,
+    env: Environment,
+) -> Result<(MettaValue, Environment), EvalResult> {
+    trace!(target: "mettatron::eval::eval_if", ?items);
+    if items.len() < 4 {
+        return Err(if_usage_error(items, env));
+    }
 
-    let condition = &args[0];
-    let then_branch = &args[1];
-    let else_branch = &args[2];
+    let condition = items[1].clone();
+    let then_branch = items[2].clone();
+    let else_branch = items[3].clone();
 
     // Evaluate the condition
-    let (cond_results, env_after_cond) = eval(condition.clone(), env);
+    let (cond_results, env_after_cond) = eval(condition, env);
 
     // Check for error in condition
     if let Some(first) = cond_results.first() {
         if matches!(first, MettaValue::Error(_, _)) {
-            return (vec![first.clone()], env_after_cond);
+            return Err((vec![first.clone()], env_after_cond));
         }
 
-        // Check if condition is true
+        // Only Bool conditions are accepted: True selects the then-branch,
+        // False the else-branch, anything else is an error
         let is_true = match first {
-            MettaValue::Bool(true) => true,
-            MettaValue::Bool(false) => false,
-            // Non-boolean values: treat as true if not Nil
-            MettaValue::Nil => false,
-            _ => true,
+            MettaValue::Bool(b) => *b,
+            other => {
+                let err = MettaValue::Error(
+                    "if: condition not boolean".to_string(),
+                    Arc::new(other.clone()),
+                );
+                return Err((vec![err], env_after_cond));
+            }
         };
 
         // Evaluate only the chosen branch
         if is_true {
-            eval(then_branch.clone(), env_after_cond)
+            Ok((then_branch, env_after_cond))
         } else {
-            eval(else_branch.clone(), env_after_cond)
+            Ok((else_branch, env_after_cond))
         }
     } else {
         // No result from condition - treat as false
-        eval(else_branch.clone(), env_after_cond)
+        Ok((else_branch, env_after_cond))
+    }
+}
+
+/// Usage error for an `if` that is missing arguments
+fn if_usage_error(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    require_args_with_usage!("if", items, env, "(if condition then-branch else-branch)");
+    (vec![], env)
+}
+
+/// Evaluate a loop: (while condition body)
+/// Re-evaluates `body` for as long as `condition` evaluates to `True`, returning `()`
+///
+/// The loop runs iteratively rather than by recursion, so it is not limited by the
+/// call depth and is meant for state-driven code such as counters kept in `new-state`
+/// cells. The condition follows the rules of `if`: `False` or no result ends the loop,
+/// and a non-Bool value is an error. An error from the condition or the body ends the
+/// loop and is returned.
+pub(super) fn eval_while(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    let args = &items[1..];
+    trace!(target: "mettatron::eval::eval_while", ?items, ?args);
+
+    require_args_with_usage!("while", items, env, "(while condition body)");
+
+    let condition = &args[0];
+    let body = &args[1];
+    let mut env = env;
+
+    loop {
+        let (cond_results, env_after_cond) = eval(condition.clone(), env);
+        env = env_after_cond;
+
+        match cond_results.first() {
+            Some(MettaValue::Bool(true)) => {}
+            Some(MettaValue::Bool(false)) | None => break,
+            Some(err @ MettaValue::Error(_, _)) => return (vec![err.clone()], env),
+            Some(other) => {
+                let err = MettaValue::Error(
+                    "while: condition not boolean".to_string(),
+                    Arc::new(other.clone()),
+                );
+                return (vec![err], env);
+            }
+        }
+
+        let (body_results, env_after_body) = eval(body.clone(), env);
+        env = env_after_body;
+        if let Some(err) = body_results
+            .into_iter()
+            .find(|r| matches!(r, MettaValue::Error(_, _)))
+        {
+            return (vec![err], env);
+        }
+    }
+
+    (vec![MettaValue::SExpr(vec![])], env)
+}
+
+/// If-empty: branch on whether an expression produces any results
+/// (if-empty expr then-branch else-branch)
+///
+/// All results of `expr` are collected first. If there are none, `then-branch` is
+/// evaluated; otherwise the results of `expr` are passed through unchanged and
+/// `else-branch` is not evaluated.
+pub(super) fn eval_if_empty(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_if_empty", ?items);
+    require_args_with_usage!(
+        "if-empty",
+        items,
+        env,
+        "(if-empty expr then-branch else-branch)"
+    );
+
+    let (results, env) = eval(items[1].clone(), env);
+    if results.is_empty() {
+        eval(items[2].clone(), env)
+    } else {
+        (results, env)
+    }
+}
+
+/// If-non-empty: the counterpart of `if-empty`
+/// (if-non-empty expr then-branch else-branch)
+///
+/// All results of `expr` are collected first. If there is at least one, `then-branch`
+/// is evaluated; otherwise `else-branch` is. The results of `expr` themselves are
+/// discarded.
+pub(super) fn eval_if_non_empty(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_if_non_empty", ?items);
+    require_args_with_usage!(
+        "if-non-empty",
+        items,
+        env,
+        "(if-non-empty expr then-branch else-branch)"
+    );
+
+    let (results, env) = eval(items[1].clone(), env);
+    if results.is_empty() {
+        eval(items[3].clone(), env)
+    } else {
+        eval(items[2].clone(), env)
     }
 }
 
@@ -63,9 +181,8 @@ pub(super) fn eval_if_equal(items: Vec<MettaValue>, env: Environment) -> EvalRes
     trace!(target: "mettatron::eval::eval_if_equal", ?items, ?args);
 
     require_args_with_usage!(
-        "eval_if_equal",
+        "if-equal",
         items,
-        4,
         env,
         "(if-equal predicate-1 predicate-2 then_branch else_branch)"
     );
@@ -84,15 +201,14 @@ pub(super) fn eval_if_equal(items: Vec<MettaValue>, env: Environment) -> EvalRes
 
 /// Subsequently tests multiple pattern-matching conditions (second argument) for the
 /// given value (first argument)
+///
+/// A case may carry a guard between its pattern and result: `(pattern guard result)`.
+/// The guard is evaluated with the pattern's bindings, and the case is only taken when
+/// it evaluates to True; otherwise matching continues with the next case.
+/// Example: (case $x (($p (> $p 0) positive) ($p nonpositive)))
 pub(super) fn eval_case(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_case", ?items);
-    require_args_with_usage!(
-        "case",
-        items,
-        2,
-        env,
-        "(case expr ((pattern1 result1) ...))"
-    );
+    require_args_with_usage!("case", items, env, "(case expr ((pattern1 result1) ...))");
 
     let atom = items[1].clone();
     let cases = items[2].clone();
@@ -130,7 +246,6 @@ pub(super) fn eval_switch(items: Vec<MettaValue>, env: Environment) -> EvalResul
     require_args_with_usage!(
         "switch",
         items,
-        2,
         env,
         "(switch expr ((pattern1 result1) ...))"
     );
@@ -141,13 +256,7 @@ pub(super) fn eval_switch(items: Vec<MettaValue>, env: Environment) -> EvalResul
 
 pub(super) fn eval_switch_minimal_handler(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_switch_minimal_handler", ?items);
-    require_args_with_usage!(
-        "switch-minimal",
-        items,
-        2,
-        env,
-        "(switch-minimal expr cases)"
-    );
+    require_args_with_usage!("switch-minimal", items, env, "(switch-minimal expr cases)");
     let atom = items[1].clone();
     let cases = items[2].clone();
     eval_switch_minimal(atom, cases, env)
@@ -160,7 +269,6 @@ pub(super) fn eval_switch_internal_handler(items: Vec<MettaValue>, env: Environm
     require_args_with_usage!(
         "switch-internal",
         items,
-        2,
         env,
         "(switch-internal expr cases-data)"
     );
@@ -225,11 +333,11 @@ fn eval_switch_internal(atom: MettaValue, cases_data: MettaValue, env: Environme
         let remaining_cases = cases_items[1].clone();
 
         if let MettaValue::SExpr(case_items) = first_case {
-            if case_items.len() != 2 {
+            if case_items.len() != 2 && case_items.len() != 3 {
                 let err = MettaValue::Error(
                     format!(
-                        "switch case should be a pattern-template pair with exactly 2 elements, got {}. \
-Usage: (switch expr (pattern1 result1) (pattern2 result2) ...)",
+                        "switch case should be a pattern-template pair (optionally with a guard), got {} elements. \
+Usage: (switch expr ((pattern1 result1) (pattern2 guard2 result2) ...))",
                         case_items.len()
                     ),
                     Arc::new(MettaValue::SExpr(case_items)),
@@ -237,15 +345,31 @@ Usage: (switch expr (pattern1 result1) (pattern2 result2) ...)",
                 return (vec![err], env);
             }
 
-            let pattern = case_items[0].clone();
-            let template = case_items[1].clone();
+            let pattern = &case_items[0];
+            let (guard, template) = match case_items.as_slice() {
+                [_, guard, template] => (Some(guard), template),
+                [_, template] => (None, template),
+                _ => unreachable!("case length checked above"),
+            };
 
-            if let Some(bindings) = pattern_match(&pattern, &atom) {
-                let instantiated_template = apply_bindings(&template, &bindings);
-                return eval(instantiated_template, env);
-            } else {
+            let Some(bindings) = pattern_match(pattern, &atom) else {
                 return eval_switch_minimal(atom, remaining_cases, env);
-            }
+            };
+
+            let env = match guard {
+                Some(guard) => {
+                    let (guard_results, env) = eval(apply_bindings(guard, &bindings), env);
+                    match guard_results.as_slice() {
+                        [MettaValue::Bool(true)] => env,
+                        [err @ MettaValue::Error(_, _), ..] => return (vec![err.clone()], env),
+                        _ => return eval_switch_minimal(atom, remaining_cases, env),
+                    }
+                }
+                None => env,
+            };
+
+            let instantiated_template = apply_bindings(template, &bindings);
+            return eval(instantiated_template, env);
         } else {
             let err = MettaValue::Error(
                 format!(
@@ -272,6 +396,7 @@ Usage: (switch expr (pattern1 result1) (pattern2 result2) ...)",
 mod tests {
     use super::*;
     use crate::backend::models::Rule;
+    use crate::backend::run::{eval_last, eval_last_in};
 
     #[test]
     fn test_if_true_branch() {
@@ -298,6 +423,63 @@ mod tests {
         assert_eq!(results[0], MettaValue::Long(3)); // 1 + 2
     }
 
+    #[test]
+    fn test_if_non_bool_condition_is_error() {
+        for condition in [
+            MettaValue::Long(1),
+            MettaValue::Nil,
+            MettaValue::Atom("true".to_string()),
+            MettaValue::String("yes".to_string()),
+        ] {
+            let env = Environment::new();
+
+            // (if <non-bool> a b)
+            let value = MettaValue::SExpr(vec![
+                MettaValue::Atom("if".to_string()),
+                condition.clone(),
+                MettaValue::Atom("a".to_string()),
+                MettaValue::Atom("b".to_string()),
+            ]);
+
+            let (results, _) = eval(value, env);
+            assert_eq!(results.len(), 1);
+            match &results[0] {
+                MettaValue::Error(msg, details) => {
+                    assert_eq!(msg, "if: condition not boolean");
+                    assert_eq!(**details, condition);
+                }
+                other => panic!("Expected Error for {:?}, got {:?}", condition, other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_if_non_bool_condition_from_evaluation() {
+        let env = Environment::new();
+
+        // (if (+ 1 2) a b) - the evaluated condition 3 is not a Bool
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("if".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("+".to_string()),
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+            ]),
+            MettaValue::Atom("a".to_string()),
+            MettaValue::Atom("b".to_string()),
+        ]);
+
+        let (results, _) = eval(value, env);
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            MettaValue::Error(msg, details) => {
+                assert_eq!(msg, "if: condition not boolean");
+                assert_eq!(**details, MettaValue::Long(3));
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_if_false_branch() {
         let env = Environment::new();
@@ -644,10 +826,10 @@ mod tests {
         let mut env = Environment::new();
 
         // First define a rule that returns empty: (= (empty-result) ())
-        let empty_rule = Rule {
-            lhs: MettaValue::SExpr(vec![MettaValue::Atom("empty-result".to_string())]),
-            rhs: MettaValue::SExpr(vec![]),
-        };
+        let empty_rule = Rule::new(
+            MettaValue::SExpr(vec![MettaValue::Atom("empty-result".to_string())]),
+            MettaValue::SExpr(vec![]),
+        );
         env.add_rule(empty_rule);
 
         // (case (empty-result) ((Empty "was empty") (42 "was forty-two")))
@@ -863,12 +1045,12 @@ mod tests {
         let mut env = Environment::new();
 
         // Define a rule that can return Empty: (= (maybe-empty $x) (if (== $x 0) () $x))
-        let maybe_empty_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let maybe_empty_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("maybe-empty".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 MettaValue::SExpr(vec![
                     MettaValue::Atom("==".to_string()),
@@ -878,7 +1060,7 @@ mod tests {
                 MettaValue::SExpr(vec![]), // Empty s-expression
                 MettaValue::Atom("$x".to_string()),
             ]),
-        };
+        );
         env.add_rule(maybe_empty_rule);
 
         // Test switch: does NOT evaluate first argument
@@ -1066,4 +1248,92 @@ mod tests {
             MettaValue::String("fallback matched".to_string())
         );
     }
+
+    #[test]
+    fn test_mutual_tail_recursion_runs_in_constant_depth() {
+        // Evaluate (is-even n) and report the result with the deepest evaluation depth
+        let is_even = |n: i64| {
+            let mut env = Environment::new();
+            env.set_collect_metrics(true);
+            let (results, env) = eval_last_in(
+                &format!(
+                    "(= (is-even $n) (if (== $n 0) True (is-odd (- $n 1))))\n\
+                     (= (is-odd $n) (if (== $n 0) False (is-even (- $n 1))))\n\
+                     !(is-even {})",
+                    n
+                ),
+                env,
+            );
+            (results, env.metrics().max_depth)
+        };
+
+        let (small, small_depth) = is_even(10);
+        assert_eq!(small, vec![MettaValue::Bool(true)]);
+
+        // Far deeper than the evaluation depth and call depth limits
+        let (large, large_depth) = is_even(20_001);
+        assert_eq!(large, vec![MettaValue::Bool(false)]);
+        assert_eq!(large_depth, small_depth);
+    }
+
+    #[test]
+    fn test_if_empty_fires_then_branch_on_no_results() {
+        assert_eq!(
+            eval_last("!(if-empty (empty) none some)"),
+            vec![MettaValue::Atom("none".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_if_empty_passes_results_through() {
+        let results = eval_last("!(if-empty (superpose (1 2)) none some)");
+        assert_eq!(results, vec![MettaValue::Long(1), MettaValue::Long(2)]);
+    }
+
+    #[test]
+    fn test_if_non_empty_branches() {
+        assert_eq!(
+            eval_last("!(if-non-empty (superpose (1 2)) some none)"),
+            vec![MettaValue::Atom("some".to_string())]
+        );
+        assert_eq!(
+            eval_last("!(if-non-empty (empty) some none)"),
+            vec![MettaValue::Atom("none".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_case_guard_passes() {
+        assert_eq!(
+            eval_last("!(case 5 (($p (> $p 0) positive) ($p nonpositive)))"),
+            vec![MettaValue::Atom("positive".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_case_guard_fails_falls_through() {
+        assert_eq!(
+            eval_last("!(case -3 (($p (> $p 0) positive) ($p nonpositive)))"),
+            vec![MettaValue::Atom("nonpositive".to_string())]
+        );
+        // With every guard failing the case does not reduce
+        assert_eq!(
+            eval_last("!(case 0 (($p (> $p 0) positive) ($p (< $p 0) negative)))"),
+            vec![MettaValue::Atom("NotReducible".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_case_guard_uses_pattern_bindings() {
+        // The guard sees $b bound by the pattern; unguarded cases keep working alongside
+        assert_eq!(
+            eval_last(
+                "!(case (pair 2 7) (((pair $a $b) (> $b 5) (big $a)) ((pair $a $b) (small $a))))"
+            ),
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("big".to_string()),
+                MettaValue::Long(2)
+            ])]
+        );
+    }
 }
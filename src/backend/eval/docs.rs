@@ -0,0 +1,171 @@
+use std::sync::Arc;
+use tracing::trace;
+
+use crate::backend::environment::Environment;
+use crate::backend::models::{EvalResult, MettaValue};
+
+/// Doc: attach a documentation string to a symbol
+/// (@doc symbol "description")
+///
+/// The parser splits `@doc` into the `@` operator followed by `doc`, so both the
+/// split form and a single `@doc` atom are accepted. Returns no results, like `=`.
+pub(super) fn eval_doc(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_doc", ?items);
+
+    // Normalize (@ doc symbol "description") to (@doc symbol "description")
+    let args: &[MettaValue] = match items.as_slice() {
+        [MettaValue::Atom(at), MettaValue::Atom(doc), rest @ ..] if at == "@" && doc == "doc" => {
+            rest
+        }
+        [MettaValue::Atom(head), rest @ ..] if head == "@doc" => rest,
+        _ => {
+            let err = MettaValue::Error(
+                "Unknown annotation. Usage: (@doc symbol \"description\")".to_string(),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    if args.len() != 2 {
+        let err = MettaValue::Error(
+            format!(
+                "@doc requires exactly 2 arguments, got {}. Usage: (@doc symbol \"description\")",
+                args.len()
+            ),
+            Arc::new(MettaValue::Atom("ArityError".to_string())),
+        );
+        return (vec![err], env);
+    }
+
+    let symbol = match &args[0] {
+        MettaValue::Atom(name) => name.clone(),
+        other => {
+            let err = MettaValue::Error(
+                format!(
+                    "@doc expects a symbol to document, got {}",
+                    super::friendly_value_repr(other)
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let description = match &args[1] {
+        MettaValue::String(s) => s.clone(),
+        other => {
+            let err = MettaValue::Error(
+                format!(
+                    "@doc expects a string description, got {}",
+                    super::friendly_value_repr(other)
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let mut env = env;
+    env.set_doc(&symbol, &description);
+    (vec![], env)
+}
+
+/// Get-doc: retrieve the documentation string attached to a symbol
+/// (get-doc symbol) - returns Empty (no results) if the symbol is undocumented
+pub(super) fn eval_get_doc(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_get_doc", ?items);
+    require_args_with_usage!("get-doc", items, env, "(get-doc symbol)");
+
+    match &items[1] {
+        MettaValue::Atom(name) => match env.get_doc(name) {
+            Some(doc) => (vec![MettaValue::String(doc)], env),
+            None => (vec![], env),
+        },
+        other => {
+            let err = MettaValue::Error(
+                format!(
+                    "get-doc expects a symbol, got {}",
+                    super::friendly_value_repr(other)
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            (vec![err], env)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::eval::eval;
+
+    fn doc_expr(symbol: &str, description: &str) -> MettaValue {
+        MettaValue::SExpr(vec![
+            MettaValue::Atom("@".to_string()),
+            MettaValue::Atom("doc".to_string()),
+            MettaValue::Atom(symbol.to_string()),
+            MettaValue::String(description.to_string()),
+        ])
+    }
+
+    fn get_doc_expr(symbol: &str) -> MettaValue {
+        MettaValue::SExpr(vec![
+            MettaValue::Atom("get-doc".to_string()),
+            MettaValue::Atom(symbol.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_set_and_get_doc() {
+        let env = Environment::new();
+
+        let (results, env) = eval(doc_expr("double", "Doubles a number"), env);
+        assert!(results.is_empty());
+
+        let (results, _) = eval(get_doc_expr("double"), env);
+        assert_eq!(
+            results,
+            vec![MettaValue::String("Doubles a number".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_doc_single_atom_form_and_overwrite() {
+        let env = Environment::new();
+
+        let single = MettaValue::SExpr(vec![
+            MettaValue::Atom("@doc".to_string()),
+            MettaValue::Atom("inc".to_string()),
+            MettaValue::String("old".to_string()),
+        ]);
+        let (_, env) = eval(single, env);
+        let (_, env) = eval(doc_expr("inc", "Adds one"), env);
+
+        let (results, _) = eval(get_doc_expr("inc"), env);
+        assert_eq!(results, vec![MettaValue::String("Adds one".to_string())]);
+    }
+
+    #[test]
+    fn test_get_doc_missing_is_empty() {
+        let env = Environment::new();
+        let (results, _) = eval(get_doc_expr("unknown"), env);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_doc_requires_string_description() {
+        let env = Environment::new();
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("@".to_string()),
+            MettaValue::Atom("doc".to_string()),
+            MettaValue::Atom("foo".to_string()),
+            MettaValue::Long(1),
+        ]);
+        let (results, _) = eval(value, env);
+        assert_eq!(results.len(), 1);
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("string description"))
+        );
+    }
+}
@@ -29,7 +29,7 @@ pub(super) fn eval_error(items: Vec<MettaValue>, env: Environment) -> EvalResult
 /// Is-error: check if value is an error (for error recovery)
 pub(super) fn eval_if_error(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_if_error", ?items);
-    require_args_with_usage!("is-error", items, 1, env, "(is-error expr)");
+    require_args_with_usage!("is-error", items, env, "(is-error expr)");
 
     let (results, new_env) = eval(items[1].clone(), env);
     if let Some(first) = results.first() {
@@ -80,6 +80,8 @@ pub(super) fn eval_catch(items: Vec<MettaValue>, env: Environment) -> EvalResult
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::models::ErrorKind;
+    use crate::backend::run::eval_last;
 
     #[test]
     fn test_is_error_missing_argument() {
@@ -562,10 +564,10 @@ mod tests {
 
         // Create a rule that returns empty: (= (returns-empty) ())
         use crate::backend::models::Rule;
-        let empty_rule = Rule {
-            lhs: MettaValue::SExpr(vec![MettaValue::Atom("returns-empty".to_string())]),
-            rhs: MettaValue::SExpr(vec![]),
-        };
+        let empty_rule = Rule::new(
+            MettaValue::SExpr(vec![MettaValue::Atom("returns-empty".to_string())]),
+            MettaValue::SExpr(vec![]),
+        );
         env.add_rule(empty_rule);
 
         // Test is-error with expression that returns empty
@@ -670,4 +672,37 @@ mod tests {
         assert_eq!(results.len(), 1);
         assert_eq!(results[0], MettaValue::String("has-error".to_string()));
     }
+
+    fn kind_of(src: &str) -> Option<ErrorKind> {
+        let results = eval_last(src);
+        assert_eq!(results.len(), 1, "{}: {:?}", src, results);
+        results[0].error_kind()
+    }
+
+    #[test]
+    fn test_error_kinds_of_failing_programs() {
+        assert_eq!(kind_of("!(/ 10 0)"), Some(ErrorKind::DivByZero));
+        assert_eq!(kind_of("!(% 10 0)"), Some(ErrorKind::DivByZero));
+        assert_eq!(
+            kind_of("!(* 9223372036854775807 2)"),
+            Some(ErrorKind::Arithmetic)
+        );
+        assert_eq!(kind_of("!(+ 1 \"a\")"), Some(ErrorKind::TypeError));
+        assert_eq!(kind_of("!(not 1 2)"), Some(ErrorKind::ArityMismatch));
+        assert_eq!(kind_of("!(size-atom)"), Some(ErrorKind::ArityMismatch));
+        assert_eq!(
+            kind_of("!(string-to-atom \"two words\")"),
+            Some(ErrorKind::InvalidValue)
+        );
+        assert_eq!(
+            kind_of("(= (double $x) (* $x 2))\n!(doubel 3)"),
+            Some(ErrorKind::UnknownSymbol)
+        );
+        assert_eq!(kind_of("!(error \"boom\" 1)"), Some(ErrorKind::Runtime));
+    }
+
+    #[test]
+    fn test_error_kind_of_non_error_is_none() {
+        assert_eq!(kind_of("!(+ 1 2)"), None);
+    }
 }
@@ -1,15 +1,65 @@
 use crate::backend::environment::Environment;
 use crate::backend::models::{EvalResult, MettaValue};
+use std::cell::Cell;
 use std::sync::Arc;
 use tracing::{trace, warn};
 
 use super::{apply_bindings, eval, pattern_match};
 
+thread_local! {
+    /// Number of `(eval ...)` forms being evaluated on this thread, compared against
+    /// `Environment::max_eval_depth`
+    static EVAL_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Decrements `EVAL_DEPTH` when an `(eval ...)` form finishes (or unwinds)
+struct EvalDepthGuard;
+
+impl EvalDepthGuard {
+    fn enter() -> Self {
+        EVAL_DEPTH.with(|d| d.set(d.get() + 1));
+        EvalDepthGuard
+    }
+}
+
+impl Drop for EvalDepthGuard {
+    fn drop(&mut self) {
+        EVAL_DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// Number of `(eval ...)` forms being evaluated on this thread
+pub(super) fn eval_depth() -> usize {
+    EVAL_DEPTH.with(|d| d.get())
+}
+
 /// Eval: force evaluation of quoted expressions
 /// (eval expr) - complementary to quote
+///
+/// Nested `eval` forms are limited to `Environment::max_eval_depth`; the form that
+/// would exceed it returns an `EvalDepthExceeded` error instead.
 pub(super) fn eval_eval(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_eval", ?items);
-    require_args_with_usage!("eval", items, 1, env, "(eval expr)");
+    require_args_with_usage!("eval", items, env, "(eval expr)");
+
+    let eval_depth = EVAL_DEPTH.with(|d| d.get());
+    if eval_depth >= env.max_eval_depth() {
+        warn!(
+            target: "mettatron::eval::eval_eval",
+            eval_depth,
+            max_eval_depth = env.max_eval_depth(),
+            "Maximum eval depth exceeded"
+        );
+        let err = MettaValue::Error(
+            format!(
+                "Maximum eval depth ({}) exceeded: too many nested eval forms",
+                env.max_eval_depth()
+            ),
+            Arc::new(MettaValue::Atom("EvalDepthExceeded".to_string())),
+        );
+        return (vec![err], env);
+    }
+    let _guard = EvalDepthGuard::enter();
 
     // First evaluate the argument to get the expression
     let (arg_results, arg_env) = eval(items[1].clone(), env);
@@ -22,9 +72,11 @@ pub(super) fn eval_eval(items: Vec<MettaValue>, env: Environment) -> EvalResult
 }
 
 /// Evaluation: ! expr - force evaluation
+/// `!` only triggers evaluation: the result is the value of expr with no `!` marker,
+/// so `!(+ 1 2)` nested as an argument compares equal to `(+ 1 2)`.
 pub(super) fn force_eval(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::force_eval", ?items);
-    require_args_with_usage!("!", items, 1, env, "(! expr)");
+    require_args_with_usage!("!", items, env, "(! expr)");
     // Evaluate the expression after !
     eval(items[1].clone(), env)
 }
@@ -33,7 +85,7 @@ pub(super) fn force_eval(items: Vec<MettaValue>, env: Environment) -> EvalResult
 /// until it encounters a return value
 pub(super) fn eval_function(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_function", ?items);
-    require_args_with_usage!("function", items, 1, env, "(function expr)");
+    require_args_with_usage!("function", items, env, "(function expr)");
 
     let mut current_expr = items[1].clone();
     let mut current_env = env;
@@ -100,7 +152,7 @@ pub(super) fn eval_function(items: Vec<MettaValue>, env: Environment) -> EvalRes
 /// Return: signals termination from a function evaluation loop
 pub(super) fn eval_return(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_return", ?items);
-    require_args_with_usage!("return", items, 1, env, "(return value)");
+    require_args_with_usage!("return", items, env, "(return value)");
 
     let (arg_results, arg_env) = eval(items[1].clone(), env);
     for result in &arg_results {
@@ -117,38 +169,49 @@ pub(super) fn eval_return(items: Vec<MettaValue>, env: Environment) -> EvalResul
     (return_results, arg_env)
 }
 
-/// Subsequently tests multiple pattern-matching conditions (second argument) for the
-/// given value (first argument)
+/// Chain: evaluate an expression, bind its result and continue
+/// (chain expr $var template)
+///
+/// `expr` is evaluated first. Each of its results is bound to `$var` and the
+/// template is evaluated once per result, so a nondeterministic `expr` fans out
+/// into one template evaluation per result. If any result of `expr` is an error,
+/// that error is returned unchanged and the template is never evaluated.
 pub(super) fn eval_chain(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_chain", ?items);
-    require_args_with_usage!("chain", items, 3, env, "(chain expr $var body)");
+    require_args_with_usage!("chain", items, env, "(chain expr $var body)");
 
     let expr = &items[1];
     let var = &items[2];
     let body = &items[3];
 
     let (expr_results, expr_env) = eval(expr.clone(), env);
-    for result in &expr_results {
-        if matches!(result, MettaValue::Error(_, _)) {
-            return (vec![result.clone()], expr_env);
-        }
+    if let Some(error) = expr_results
+        .iter()
+        .find(|result| matches!(result, MettaValue::Error(_, _)))
+    {
+        return (vec![error.clone()], expr_env);
     }
 
+    // Thread the environment through each branch so space updates made by one
+    // template evaluation are visible to the next
     let mut all_results = Vec::new();
+    let mut current_env = expr_env;
     for value in expr_results {
         if let Some(bindings) = pattern_match(var, &value) {
             let instantiated_body = apply_bindings(body, &bindings);
-            let (body_results, _) = eval(instantiated_body, expr_env.clone());
+            let (body_results, body_env) = eval(instantiated_body, current_env);
+            current_env = body_env;
             all_results.extend(body_results);
         }
     }
 
-    (all_results, expr_env)
+    (all_results, current_env)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::run::eval_last;
     use crate::Rule;
 
     #[test]
@@ -216,12 +279,12 @@ mod tests {
         let mut env = Environment::new();
 
         // Define factorial rule that only uses return for base case
-        let factorial_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let factorial_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("factorial".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 // Condition: (== $n 0)
                 MettaValue::SExpr(vec![
@@ -241,19 +304,19 @@ mod tests {
                     MettaValue::Long(1),
                 ]),
             ]),
-        };
+        );
 
         // (= (factorial-helper $n $acc)
         //      (if (== $n 0)
         //          (return $acc)
         //          (factorial-helper (- $n 1) (* $n $acc))))
-        let helper_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let helper_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("factorial-helper".to_string()),
                 MettaValue::Atom("$n".to_string()),
                 MettaValue::Atom("$acc".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 // Condition: (== $n 0)
                 MettaValue::SExpr(vec![
@@ -281,7 +344,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-        };
+        );
 
         env.add_rule(factorial_rule);
         env.add_rule(helper_rule);
@@ -317,31 +380,31 @@ mod tests {
 
         // Use tail-recursive fibonacci with accumulator
         // (= (fib $n) (fib-helper $n 0 1))
-        let fib_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let fib_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("fib".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("fib-helper".to_string()),
                 MettaValue::Atom("$n".to_string()),
                 MettaValue::Long(0),
                 MettaValue::Long(1),
             ]),
-        };
+        );
 
         // (= (fib-helper $n $a $b)
         //    (if (== $n 0)
         //        (return $a)
         //        (fib-helper (- $n 1) $b (+ $a $b))))
-        let fib_helper_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let fib_helper_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("fib-helper".to_string()),
                 MettaValue::Atom("$n".to_string()),
                 MettaValue::Atom("$a".to_string()),
                 MettaValue::Atom("$b".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 // Condition: (== $n 0)
                 MettaValue::SExpr(vec![
@@ -370,7 +433,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-        };
+        );
 
         env.add_rule(fib_rule);
         env.add_rule(fib_helper_rule);
@@ -406,32 +469,32 @@ mod tests {
 
         // Use tail-recursive power with accumulator
         // (= (power $base $exp) (power-helper $base $exp 1))
-        let power_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let power_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("power".to_string()),
                 MettaValue::Atom("$base".to_string()),
                 MettaValue::Atom("$exp".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("power-helper".to_string()),
                 MettaValue::Atom("$base".to_string()),
                 MettaValue::Atom("$exp".to_string()),
                 MettaValue::Long(1),
             ]),
-        };
+        );
 
         // (= (power-helper $base $exp $acc)
         //    (if (== $exp 0)
         //        (return $acc)
         //        (power-helper $base (- $exp 1) (* $acc $base))))
-        let power_helper_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let power_helper_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("power-helper".to_string()),
                 MettaValue::Atom("$base".to_string()),
                 MettaValue::Atom("$exp".to_string()),
                 MettaValue::Atom("$acc".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 // Condition: (== $exp 0)
                 MettaValue::SExpr(vec![
@@ -460,7 +523,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-        };
+        );
 
         env.add_rule(power_rule);
         env.add_rule(power_helper_rule);
@@ -555,26 +618,26 @@ mod tests {
         let mut env = Environment::new();
 
         // Define a simple increment rule: (= (inc $x) (+ $x 1))
-        let inc_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let inc_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("inc".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("+".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Long(1),
             ]),
-        };
+        );
         env.add_rule(inc_rule);
 
         // Define computation that uses chain: (= (compute $n) (chain (inc $n) $result (return $result)))
-        let compute_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let compute_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("compute".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("chain".to_string()),
                 MettaValue::SExpr(vec![
                     MettaValue::Atom("inc".to_string()),
@@ -586,7 +649,7 @@ mod tests {
                     MettaValue::Atom("$result".to_string()),
                 ]),
             ]),
-        };
+        );
         env.add_rule(compute_rule);
 
         // Test: (function (compute 5)) should increment 5 to 6, bind to $result, then return 6
@@ -635,42 +698,42 @@ mod tests {
 
         // Define helper functions for computation pipeline
         // (= (double $x) (* $x 2))
-        let double_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let double_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("double".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("*".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Long(2),
             ]),
-        };
+        );
 
         // (= (square $x) (* $x $x))
-        let square_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let square_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("square".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("*".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-        };
+        );
 
         // Complex chained computation: (= (complex-calc $n)
         //   (chain (+ $n 3) $step1
         //     (chain (double $step1) $step2
         //       (chain (square $step2) $result
         //         (return $result)))))
-        let complex_calc_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let complex_calc_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("complex-calc".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("chain".to_string()),
                 // Step 1: (+ $n 3)
                 MettaValue::SExpr(vec![
@@ -702,7 +765,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-        };
+        );
 
         env.add_rule(double_rule);
         env.add_rule(square_rule);
@@ -735,12 +798,12 @@ mod tests {
         //         (chain (* $n $n) $squared
         //           (chain (+ $squared 1) $incremented
         //             (return $incremented))))))
-        let process_rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let process_rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("process-number".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("chain".to_string()),
                 // Check if $n > 10
                 MettaValue::SExpr(vec![
@@ -783,7 +846,7 @@ mod tests {
                     ]),
                 ]),
             ]),
-        };
+        );
 
         env.add_rule(process_rule);
 
@@ -813,4 +876,146 @@ mod tests {
         assert_eq!(results2.len(), 1);
         assert_eq!(results2[0], MettaValue::Long(15));
     }
+
+    #[test]
+    fn test_chain_nondeterministic_fan_out() {
+        let mut env = Environment::new();
+
+        // (= (pick) 1) and (= (pick) 2) make (pick) nondeterministic
+        for n in [1, 2] {
+            env.add_rule(Rule::new(
+                MettaValue::SExpr(vec![MettaValue::Atom("pick".to_string())]),
+                MettaValue::Long(n),
+            ));
+        }
+
+        // (chain (pick) $x (* $x 10)) runs the template once per result
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("chain".to_string()),
+            MettaValue::SExpr(vec![MettaValue::Atom("pick".to_string())]),
+            MettaValue::Atom("$x".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("*".to_string()),
+                MettaValue::Atom("$x".to_string()),
+                MettaValue::Long(10),
+            ]),
+        ]);
+
+        let (mut results, _) = eval(value, env);
+        results.sort_by_key(|v| match v {
+            MettaValue::Long(n) => *n,
+            _ => i64::MAX,
+        });
+        assert_eq!(results, vec![MettaValue::Long(10), MettaValue::Long(20)]);
+    }
+
+    #[test]
+    fn test_chain_propagates_expr_error() {
+        let env = Environment::new();
+
+        // (chain (error boom 0) $x (+ $x 1)) - the template must not run
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("chain".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("error".to_string()),
+                MettaValue::Atom("boom".to_string()),
+                MettaValue::Long(0),
+            ]),
+            MettaValue::Atom("$x".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("+".to_string()),
+                MettaValue::Atom("$x".to_string()),
+                MettaValue::Long(1),
+            ]),
+        ]);
+
+        let (results, _) = eval(value, env);
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            MettaValue::Error(msg, details) => {
+                assert_eq!(msg, "boom");
+                assert_eq!(**details, MettaValue::Long(0));
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+    }
+
+    /// `(eval (eval ... (eval (quote (+ 1 2)))))` with `depth` eval forms
+    fn nested_eval(depth: usize) -> MettaValue {
+        let mut expr = MettaValue::SExpr(vec![
+            MettaValue::Atom("quote".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("+".to_string()),
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+            ]),
+        ]);
+        for _ in 0..depth {
+            expr = MettaValue::SExpr(vec![MettaValue::Atom("eval".to_string()), expr]);
+        }
+        expr
+    }
+
+    #[test]
+    fn test_eval_depth_limit() {
+        let mut env = Environment::new();
+        env.set_max_eval_depth(5);
+
+        let (results, env) = eval(nested_eval(5), env);
+        assert_eq!(results, vec![MettaValue::Long(3)]);
+
+        let (results, env) = eval(nested_eval(6), env);
+        match results.as_slice() {
+            [MettaValue::Error(msg, details)] => {
+                assert!(msg.contains("Maximum eval depth (5)"), "{}", msg);
+                assert_eq!(**details, MettaValue::Atom("EvalDepthExceeded".to_string()));
+            }
+            other => panic!("Expected an eval depth error, got {:?}", other),
+        }
+
+        // The counter unwinds, so later evaluation is unaffected
+        let (results, _) = eval(nested_eval(5), env);
+        assert_eq!(results, vec![MettaValue::Long(3)]);
+    }
+
+    #[test]
+    fn test_eval_quoted_call_applies_rules() {
+        let (outputs, _) = crate::backend::run::eval_forms(
+            "(= (double $x) (* $x 2))\n!(quote (double 5))\n!(eval (quote (double 5)))",
+            Environment::new(),
+        );
+
+        // Quoting keeps the call as data; eval then dispatches it through the rules
+        assert_eq!(
+            outputs[1],
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("double".to_string()),
+                MettaValue::Long(5),
+            ])]
+        );
+        assert_eq!(outputs[2], vec![MettaValue::Long(10)]);
+    }
+
+    #[test]
+    fn test_force_prefix_does_not_appear_in_values() {
+        assert_eq!(eval_last("!(+ 1 2)"), eval_last("(+ 1 2)"));
+        assert_eq!(eval_last("!(+ 1 2)"), vec![MettaValue::Long(3)]);
+        assert_eq!(
+            eval_last("!(== !(+ 1 2) (+ 1 2))"),
+            vec![MettaValue::Bool(true)]
+        );
+        assert_eq!(
+            eval_last("(= (f $x) (* $x 2))\n!(== !(f 4) (f 4))"),
+            vec![MettaValue::Bool(true)]
+        );
+        // Nested inside data, the forced argument leaves only its value behind
+        assert_eq!(
+            eval_last("!(pair !(+ 1 2) x)"),
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("pair".to_string()),
+                MettaValue::Long(3),
+                MettaValue::Atom("x".to_string()),
+            ])]
+        );
+    }
 }
@@ -4,12 +4,14 @@ use crate::backend::models::{EvalResult, MettaValue};
 use std::sync::Arc;
 use tracing::trace;
 
+use super::eval;
+
 /// Cons atom: (cons-atom head tail)
 /// Constructs an expression using two arguments
 /// Example: (cons-atom a (b c)) -> (a b c)
 pub(super) fn eval_cons_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_cons_atom", ?items);
-    require_args_with_usage!("cons-atom", items, 2, env, "(cons-atom head tail)");
+    require_args_with_usage!("cons-atom", items, env, "(cons-atom head tail)");
 
     let new_head = &items[1];
     let tail_expr = &items[2];
@@ -45,7 +47,7 @@ pub(super) fn eval_cons_atom(items: Vec<MettaValue>, env: Environment) -> EvalRe
 /// Example: (decons-atom (Cons X Nil)) -> (Cons (X Nil))
 pub(super) fn eval_decons_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_decons_atom", ?items);
-    require_args_with_usage!("decons-atom", items, 1, env, "(decons-atom expr)");
+    require_args_with_usage!("decons-atom", items, env, "(decons-atom expr)");
 
     let expr = &items[1];
 
@@ -85,7 +87,7 @@ pub(super) fn eval_decons_atom(items: Vec<MettaValue>, env: Environment) -> Eval
 /// Example: (size-atom (a b c)) -> 3
 pub(super) fn eval_size_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_size_atom", ?items);
-    require_args_with_usage!("size-atom", items, 1, env, "(size-atom expr)");
+    require_args_with_usage!("size-atom", items, env, "(size-atom expr)");
 
     let expr = &items[1];
 
@@ -110,10 +112,11 @@ pub(super) fn eval_size_atom(items: Vec<MettaValue>, env: Environment) -> EvalRe
 
 /// Index atom: (index-atom expr index)
 /// Returns the atom at the given index in the expression, or error if index is out of bounds
-/// Example: (index-atom (a b c) 1) -> b
+/// Negative indices count from the end, Python-style: -1 is the last element
+/// Example: (index-atom (a b c) 1) -> b, (index-atom (a b c) -1) -> c
 pub(super) fn eval_index_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_index_atom", ?items);
-    require_args_with_usage!("index-atom", items, 2, env, "(index-atom expr index)");
+    require_args_with_usage!("index-atom", items, env, "(index-atom expr index)");
 
     let expr = &items[1];
     let index_val = &items[2];
@@ -135,7 +138,9 @@ pub(super) fn eval_index_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
 
     match expr {
         MettaValue::SExpr(expr_items) => {
-            if index < 0 || index as usize >= expr_items.len() {
+            let len = expr_items.len() as i64;
+            let resolved = if index < 0 { len + index } else { index };
+            if resolved < 0 || resolved >= len {
                 let err = MettaValue::Error(
                     format!(
                         "index {} out of bounds for expression of size {}",
@@ -146,7 +151,7 @@ pub(super) fn eval_index_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
                 );
                 return (vec![err], env);
             }
-            (vec![expr_items[index as usize].clone()], env)
+            (vec![expr_items[resolved as usize].clone()], env)
         }
         MettaValue::Nil => {
             let err = MettaValue::Error(
@@ -168,32 +173,39 @@ pub(super) fn eval_index_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
     }
 }
 
-/// Car atom: (car-atom expr)
-/// Extracts the first atom of an expression
-/// Example: (car-atom (a b c)) -> a
-pub(super) fn eval_car_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
-    trace!(target: "mettatron::eval::eval_car_atom", ?items);
-    require_args_with_usage!("car-atom", items, 1, env, "(car-atom expr)");
-
-    let expr = &items[1];
-
-    match expr {
-        MettaValue::SExpr(expr_items) => (vec![expr_items[0].clone()], env),
-        MettaValue::Nil => {
+/// Nth atom: (nth-atom index expr)
+/// Returns the element at a zero-based index: (nth-atom 0 (a b c)) -> a
+///
+/// Note the argument order: the index comes first, whereas `index-atom` takes the
+/// expression first. Unlike `index-atom`, negative indices are not accepted; any index
+/// outside `0..size` is an error.
+pub(super) fn eval_nth_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_nth_atom", ?items);
+    require_args_with_usage!("nth-atom", items, env, "(nth-atom index expr)");
+
+    let (index, expr_items) = match (&items[1], &items[2]) {
+        (MettaValue::Long(index), MettaValue::SExpr(expr_items)) => (*index, &expr_items[..]),
+        (MettaValue::Long(index), MettaValue::Nil) => (*index, &[][..]),
+        _ => {
             let err = MettaValue::Error(
                 format!(
-                    "car-atom expects a non-empty expression as an argument, found: {}",
+                    "expected: (nth-atom (: <index> Number) (: <expr> Expression)), found: {}",
                     super::friendly_value_repr(&MettaValue::SExpr(items.clone()))
                 ),
                 Arc::new(MettaValue::SExpr(items.clone())),
             );
-            (vec![err], env)
+            return (vec![err], env);
         }
-        _ => {
+    };
+
+    match usize::try_from(index).ok().and_then(|i| expr_items.get(i)) {
+        Some(element) => (vec![element.clone()], env),
+        None => {
             let err = MettaValue::Error(
                 format!(
-                    "expected: (car-atom (: <expr> Expression)), found: {}",
-                    super::friendly_value_repr(&MettaValue::SExpr(items.clone()))
+                    "nth-atom: index {} out of bounds for expression of size {}",
+                    index,
+                    expr_items.len()
                 ),
                 Arc::new(MettaValue::SExpr(items.clone())),
             );
@@ -202,45 +214,80 @@ pub(super) fn eval_car_atom(items: Vec<MettaValue>, env: Environment) -> EvalRes
     }
 }
 
+/// Car atom: (car-atom expr)
+/// Extracts the first atom of an expression
+/// The argument is evaluated first, so a call returning an expression works too
+/// Example: (car-atom (a b c)) -> a
+pub(super) fn eval_car_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_car_atom", ?items);
+    require_args_with_usage!("car-atom", items, env, "(car-atom expr)");
+
+    let (values, env) = eval(items[1].clone(), env);
+    let results = values.iter().map(|expr| car_atom(expr, &items)).collect();
+    (results, env)
+}
+
+/// Head of one evaluated `car-atom` argument; `items` is the call, for error messages
+fn car_atom(expr: &MettaValue, items: &[MettaValue]) -> MettaValue {
+    match expr {
+        MettaValue::SExpr(expr_items) if !expr_items.is_empty() => expr_items[0].clone(),
+        MettaValue::Error(_, _) => expr.clone(),
+        MettaValue::Nil | MettaValue::SExpr(_) => MettaValue::Error(
+            format!(
+                "car-atom expects a non-empty expression as an argument, found: {}",
+                super::friendly_value_repr(&MettaValue::SExpr(items.to_vec()))
+            ),
+            Arc::new(MettaValue::SExpr(items.to_vec())),
+        ),
+        _ => MettaValue::Error(
+            format!(
+                "expected: (car-atom (: <expr> Expression)), found: {}",
+                super::friendly_value_repr(&MettaValue::SExpr(items.to_vec()))
+            ),
+            Arc::new(MettaValue::SExpr(items.to_vec())),
+        ),
+    }
+}
+
 /// Cdr atom: (cdr-atom expr)
 /// Extracts the tail of an expression (all except first atom)
+/// The argument is evaluated first, as for `car-atom`
 /// Example: (cdr-atom (a b c)) -> (b c)
 pub(super) fn eval_cdr_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_cdr_atom", ?items);
-    require_args_with_usage!("cdr-atom", items, 1, env, "(cdr-atom expr)");
+    require_args_with_usage!("cdr-atom", items, env, "(cdr-atom expr)");
 
-    let expr = &items[1];
+    let (values, env) = eval(items[1].clone(), env);
+    let results = values.iter().map(|expr| cdr_atom(expr, &items)).collect();
+    (results, env)
+}
 
+/// Tail of one evaluated `cdr-atom` argument; `items` is the call, for error messages
+fn cdr_atom(expr: &MettaValue, items: &[MettaValue]) -> MettaValue {
     match expr {
-        MettaValue::SExpr(expr_items) => {
-            let tail = if expr_items.len() == 1 {
+        MettaValue::SExpr(expr_items) if !expr_items.is_empty() => {
+            if expr_items.len() == 1 {
                 // Single element: return empty expression (Nil)
                 MettaValue::Nil
             } else {
                 MettaValue::SExpr(expr_items[1..].to_vec())
-            };
-            (vec![tail], env)
-        }
-        MettaValue::Nil => {
-            let err = MettaValue::Error(
-                format!(
-                    "cdr-atom expects a non-empty expression as an argument, found: {}",
-                    super::friendly_value_repr(&MettaValue::SExpr(items.clone()))
-                ),
-                Arc::new(MettaValue::SExpr(items.clone())),
-            );
-            (vec![err], env)
-        }
-        _ => {
-            let err = MettaValue::Error(
-                format!(
-                    "expected: (cdr-atom (: <expr> Expression)), found: {}",
-                    super::friendly_value_repr(&MettaValue::SExpr(items.clone()))
-                ),
-                Arc::new(MettaValue::SExpr(items.clone())),
-            );
-            (vec![err], env)
+            }
         }
+        MettaValue::Error(_, _) => expr.clone(),
+        MettaValue::Nil | MettaValue::SExpr(_) => MettaValue::Error(
+            format!(
+                "cdr-atom expects a non-empty expression as an argument, found: {}",
+                super::friendly_value_repr(&MettaValue::SExpr(items.to_vec()))
+            ),
+            Arc::new(MettaValue::SExpr(items.to_vec())),
+        ),
+        _ => MettaValue::Error(
+            format!(
+                "expected: (cdr-atom (: <expr> Expression)), found: {}",
+                super::friendly_value_repr(&MettaValue::SExpr(items.to_vec()))
+            ),
+            Arc::new(MettaValue::SExpr(items.to_vec())),
+        ),
     }
 }
 
@@ -250,7 +297,7 @@ pub(super) fn eval_cdr_atom(items: Vec<MettaValue>, env: Environment) -> EvalRes
 /// Example: (min-atom (5 2 8 1)) -> 1
 pub(super) fn eval_min_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_min_atom", ?items);
-    require_args_with_usage!("min-atom", items, 1, env, "(min-atom expr)");
+    require_args_with_usage!("min-atom", items, env, "(min-atom expr)");
 
     let expr = &items[1];
 
@@ -314,7 +361,7 @@ pub(super) fn eval_min_atom(items: Vec<MettaValue>, env: Environment) -> EvalRes
 /// Example: (max-atom (5 2 8 1)) -> 8
 pub(super) fn eval_max_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_max_atom", ?items);
-    require_args_with_usage!("max-atom", items, 1, env, "(max-atom expr)");
+    require_args_with_usage!("max-atom", items, env, "(max-atom expr)");
 
     let expr = &items[1];
 
@@ -372,6 +419,70 @@ pub(super) fn eval_max_atom(items: Vec<MettaValue>, env: Environment) -> EvalRes
     }
 }
 
+/// Flatten: (flatten expr)
+/// Splices the elements of each sub-expression into the outer expression, one level deep.
+/// Non-expression elements are kept as they are, and deeper nesting is preserved.
+/// Example: (flatten ((1 2) 3 ((4)))) -> (1 2 3 (4))
+pub(super) fn eval_flatten(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_flatten", ?items);
+    require_args_with_usage!("flatten", items, env, "(flatten expr)");
+
+    match &items[1] {
+        MettaValue::SExpr(expr_items) => {
+            let mut flat = Vec::with_capacity(expr_items.len());
+            for item in expr_items {
+                match item {
+                    MettaValue::SExpr(inner) => flat.extend(inner.iter().cloned()),
+                    MettaValue::Nil => {}
+                    other => flat.push(other.clone()),
+                }
+            }
+            (vec![MettaValue::SExpr(flat)], env)
+        }
+        MettaValue::Nil => (vec![MettaValue::SExpr(vec![])], env),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "expected: (flatten (: <expr> Expression)), found: {}",
+                    super::friendly_value_repr(&MettaValue::SExpr(items.clone()))
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            (vec![err], env)
+        }
+    }
+}
+
+/// Enumerate atom: (enumerate-atom expr)
+/// Pairs each element of an expression with its zero-based index
+/// Example: (enumerate-atom (a b c)) -> ((0 a) (1 b) (2 c))
+pub(super) fn eval_enumerate_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_enumerate_atom", ?items);
+    require_args_with_usage!("enumerate-atom", items, env, "(enumerate-atom expr)");
+
+    match &items[1] {
+        MettaValue::SExpr(expr_items) => {
+            let pairs = expr_items
+                .iter()
+                .enumerate()
+                .map(|(i, item)| MettaValue::SExpr(vec![MettaValue::Long(i as i64), item.clone()]))
+                .collect();
+            (vec![MettaValue::SExpr(pairs)], env)
+        }
+        MettaValue::Nil => (vec![MettaValue::SExpr(vec![])], env),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "expected: (enumerate-atom (: <expr> Expression)), found: {}",
+                    super::friendly_value_repr(&MettaValue::SExpr(items.clone()))
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            (vec![err], env)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -776,6 +887,82 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_index_atom_negative_indices() {
+        let env = Environment::new();
+
+        // -1 is the last element, -3 the first for a 3-element expression
+        for (source, expected) in [
+            ("(index-atom (a b c) -1)", "c"),
+            ("(index-atom (a b c) -2)", "b"),
+            ("(index-atom (a b c) -3)", "a"),
+        ] {
+            let state = compile(source).unwrap();
+            let (results, _) = eval(state.source[0].clone(), env.clone());
+            assert_eq!(
+                results,
+                vec![MettaValue::Atom(expected.to_string())],
+                "{} should produce {}",
+                source,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_index_atom_error_negative_out_of_bounds() {
+        let env = Environment::new();
+
+        // Test: (index-atom (a b c) -4) is past the front of the expression
+        let source = "(index-atom (a b c) -4)";
+        let state = compile(source).unwrap();
+        let (results, _) = eval(state.source[0].clone(), env);
+
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            MettaValue::Error(msg, _) => {
+                assert!(msg.contains("index -4 out of bounds"));
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_nth_atom_index_first() {
+        let env = Environment::new();
+
+        for (source, expected) in [("(nth-atom 0 (a b c))", "a"), ("(nth-atom 2 (a b c))", "c")] {
+            let state = compile(source).unwrap();
+            let (results, _) = eval(state.source[0].clone(), env.clone());
+            assert_eq!(results, vec![MettaValue::Atom(expected.to_string())]);
+        }
+    }
+
+    #[test]
+    fn test_nth_atom_error_out_of_range() {
+        let env = Environment::new();
+
+        for source in [
+            "(nth-atom 3 (a b c))",
+            "(nth-atom -1 (a b c))",
+            "(nth-atom 0 ())",
+        ] {
+            let state = compile(source).unwrap();
+            let (results, _) = eval(state.source[0].clone(), env.clone());
+            match &results[..] {
+                [MettaValue::Error(msg, _)] => {
+                    assert!(msg.contains("out of bounds"), "{}: {}", source, msg);
+                }
+                other => panic!("{}: expected Error, got {:?}", source, other),
+            }
+        }
+
+        // Expression-first order is index-atom's, not nth-atom's
+        let state = compile("(nth-atom (a b c) 0)").unwrap();
+        let (results, _) = eval(state.source[0].clone(), env);
+        assert!(matches!(&results[..], [MettaValue::Error(msg, _)] if msg.contains("nth-atom")));
+    }
+
     #[test]
     fn test_index_atom_error_wrong_argument_count() {
         let env = Environment::new();
@@ -812,6 +999,49 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_car_and_cdr_atom_evaluate_their_argument() {
+        let (outputs, _) = crate::backend::run::eval_forms(
+            r#"
+            (= (letters) (a b c))
+            !(car-atom (letters))
+            !(cdr-atom (letters))
+            !(car-atom (cdr-atom (letters)))
+        "#,
+            Environment::new(),
+        );
+
+        assert_eq!(outputs[1], vec![MettaValue::Atom("a".to_string())]);
+        assert_eq!(
+            outputs[2],
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("b".to_string()),
+                MettaValue::Atom("c".to_string()),
+            ])]
+        );
+        assert_eq!(outputs[3], vec![MettaValue::Atom("b".to_string())]);
+    }
+
+    #[test]
+    fn test_car_and_cdr_atom_reject_evaluated_empty_expression() {
+        let (outputs, _) = crate::backend::run::eval_forms(
+            r#"
+            !(car-atom (collapse (empty)))
+            !(cdr-atom (nop))
+        "#,
+            Environment::new(),
+        );
+
+        for output in &outputs {
+            assert_eq!(output.len(), 1);
+            assert!(
+                matches!(&output[0], MettaValue::Error(msg, _) if msg.contains("non-empty expression")),
+                "expected a non-empty expression error, got {:?}",
+                output[0]
+            );
+        }
+    }
+
     #[test]
     fn test_car_atom_with_single_element() {
         let env = Environment::new();
@@ -1187,4 +1417,102 @@ mod tests {
             other => panic!("Expected Error, got {:?}", other),
         }
     }
+
+    fn eval_one(source: &str) -> MettaValue {
+        let state = compile(source).unwrap();
+        let (results, _) = eval(state.source[0].clone(), Environment::new());
+        assert_eq!(
+            results.len(),
+            1,
+            "{} should return exactly one result",
+            source
+        );
+        results[0].clone()
+    }
+
+    fn longs(ns: &[i64]) -> MettaValue {
+        MettaValue::SExpr(ns.iter().map(|n| MettaValue::Long(*n)).collect())
+    }
+
+    #[test]
+    fn test_flatten_multi_element() {
+        assert_eq!(eval_one("(flatten ((1 2) (3 4)))"), longs(&[1, 2, 3, 4]));
+        // Non-expression elements are kept in place
+        assert_eq!(eval_one("(flatten (1 (2 3) 4))"), longs(&[1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_flatten_is_one_level_only() {
+        // ((1 (2 3)) 4) -> (1 (2 3) 4): the inner (2 3) is not flattened further
+        assert_eq!(
+            eval_one("(flatten ((1 (2 3)) 4))"),
+            MettaValue::SExpr(vec![
+                MettaValue::Long(1),
+                longs(&[2, 3]),
+                MettaValue::Long(4),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_flatten_empty_and_single() {
+        assert_eq!(eval_one("(flatten ())"), longs(&[]));
+        assert_eq!(eval_one("(flatten (() ()))"), longs(&[]));
+        assert_eq!(eval_one("(flatten ((7)))"), longs(&[7]));
+        assert_eq!(eval_one("(flatten (7))"), longs(&[7]));
+    }
+
+    #[test]
+    fn test_flatten_error_with_non_expression() {
+        match eval_one("(flatten 5)") {
+            MettaValue::Error(msg, _) => assert!(msg.contains("flatten")),
+            other => panic!("Expected Error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_enumerate_atom_empty() {
+        assert_eq!(eval_one("(enumerate-atom ())"), longs(&[]));
+    }
+
+    #[test]
+    fn test_enumerate_atom_single() {
+        assert_eq!(
+            eval_one("(enumerate-atom (a))"),
+            MettaValue::SExpr(vec![MettaValue::SExpr(vec![
+                MettaValue::Long(0),
+                MettaValue::Atom("a".to_string()),
+            ])])
+        );
+    }
+
+    #[test]
+    fn test_enumerate_atom_indices_start_at_zero() {
+        let pair = |i: i64, name: &str| {
+            MettaValue::SExpr(vec![
+                MettaValue::Long(i),
+                MettaValue::Atom(name.to_string()),
+            ])
+        };
+        assert_eq!(
+            eval_one("(enumerate-atom (a b c))"),
+            MettaValue::SExpr(vec![pair(0, "a"), pair(1, "b"), pair(2, "c")])
+        );
+        // Nested elements are kept whole
+        assert_eq!(
+            eval_one("(enumerate-atom ((1 2)))"),
+            MettaValue::SExpr(vec![MettaValue::SExpr(vec![
+                MettaValue::Long(0),
+                longs(&[1, 2])
+            ])])
+        );
+    }
+
+    #[test]
+    fn test_enumerate_atom_error_with_non_expression() {
+        match eval_one("(enumerate-atom 5)") {
+            MettaValue::Error(msg, _) => assert!(msg.contains("enumerate-atom")),
+            other => panic!("Expected Error, got {:?}", other),
+        }
+    }
 }
@@ -8,9 +8,10 @@ use super::eval;
 /// Map atom: (map-atom $list $var $template)
 /// Maps a function over a list of atoms
 /// Example: (map-atom (1 2 3 4) $v (+ $v 1)) -> (2 3 4 5)
+/// An error produced for any element is returned instead of the list.
 pub(super) fn eval_map_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_map_atom", ?items);
-    require_args_with_usage!("map-atom", items, 3, env, "(map-atom list $var expr)");
+    require_args_with_usage!("map-atom", items, env, "(map-atom list $var expr)");
 
     let list = &items[1];
     let var = &items[2];
@@ -65,14 +66,13 @@ pub(super) fn eval_map_atom(items: Vec<MettaValue>, env: Environment) -> EvalRes
         let (results, new_env) = eval(instantiated_template, final_env);
         final_env = new_env;
 
-        if let Some(first_result) = results.first() {
-            if matches!(first_result, MettaValue::Error(_, _)) {
-                return (vec![first_result.clone()], final_env);
-            }
-            mapped_elements.push(first_result.clone());
-        } else {
-            mapped_elements.push(MettaValue::Nil);
+        if let Some(err) = results
+            .iter()
+            .find(|r| matches!(r, MettaValue::Error(_, _)))
+        {
+            return (vec![err.clone()], final_env);
         }
+        mapped_elements.push(results.into_iter().next().unwrap_or(MettaValue::Nil));
     }
 
     let result = if mapped_elements.is_empty() {
@@ -87,12 +87,13 @@ pub(super) fn eval_map_atom(items: Vec<MettaValue>, env: Environment) -> EvalRes
 /// Filter atom: (filter-atom $list $var $predicate)
 /// Filters a list keeping only elements that satisfy the predicate
 /// Example: (filter-atom (1 2 3 4) $v (> $v 2)) -> (3 4)
+/// The predicate must return a Bool; any other value is an error with details
+/// `(element value)`, and an error from the predicate is returned as is.
 pub(super) fn eval_filter_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_filter_atom", ?items);
     require_args_with_usage!(
         "filter-atom",
         items,
-        3,
         env,
         "(filter-atom list $var predicate)"
     );
@@ -151,19 +152,16 @@ pub(super) fn eval_filter_atom(items: Vec<MettaValue>, env: Environment) -> Eval
         let (results, new_env) = eval(instantiated_predicate, final_env);
         final_env = new_env;
 
-        if let Some(first_result) = results.first() {
-            if matches!(first_result, MettaValue::Error(_, _)) {
-                return (vec![first_result.clone()], final_env);
-            }
-
-            let should_include = match first_result {
-                MettaValue::Bool(true) => true,
-                MettaValue::Bool(false) => false,
-                _ => !matches!(first_result, MettaValue::Nil),
-            };
-
-            if should_include {
-                filtered_elements.push(element);
+        match results.first() {
+            Some(MettaValue::Bool(true)) => filtered_elements.push(element),
+            Some(MettaValue::Bool(false)) | None => {}
+            Some(err @ MettaValue::Error(_, _)) => return (vec![err.clone()], final_env),
+            Some(other) => {
+                let err = MettaValue::Error(
+                    "filter-atom: predicate must return Bool".to_string(),
+                    Arc::new(MettaValue::SExpr(vec![element, other.clone()])),
+                );
+                return (vec![err], final_env);
             }
         }
     }
@@ -186,7 +184,7 @@ pub(super) fn eval_foldl_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
         let err = MettaValue::Error(
             "foldl-atom requires exactly 5 arguments: list, init, acc-var, item-var, operation"
                 .to_string(),
-            Arc::new(MettaValue::SExpr(items.to_vec())),
+            Arc::new(MettaValue::Atom("ArityError".to_string())),
         );
         return (vec![err], env);
     }
@@ -268,8 +266,12 @@ pub(super) fn eval_foldl_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
     let mut final_env = env;
 
     for element in elements {
-        let mut instantiated_op = substitute_variable(operation, &acc_var_name, &accumulator);
-        instantiated_op = substitute_variable(&instantiated_op, &item_var_name, &element);
+        // Both variables at once, so an accumulator containing the item variable
+        // is left alone
+        let instantiated_op = substitute_variables(
+            operation,
+            &[(&acc_var_name, &accumulator), (&item_var_name, &element)],
+        );
 
         let (results, new_env) = eval(instantiated_op, final_env);
         final_env = new_env;
@@ -285,11 +287,138 @@ pub(super) fn eval_foldl_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
     (vec![accumulator], final_env)
 }
 
+/// Sort atom: (sort-atom $list) or (sort-atom $list $cmp)
+/// Sorts the elements of a list; the sort is stable, so equal elements keep their order.
+/// Without a comparator, numbers sort numerically and other atoms structurally
+/// (Bool < numbers < strings < symbols < expressions). With one, `($cmp a b)` is
+/// evaluated and must return True when `a` belongs before `b`.
+/// Example: (sort-atom (3 1 2)) -> (1 2 3), (sort-atom (3 1 2) >) -> (3 2 1)
+pub(super) fn eval_sort_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_sort_atom", ?items);
+    require_args_with_usage!("sort-atom", items, env, "(sort-atom list [comparator])");
+
+    let list = &items[1];
+    let elements = match list {
+        MettaValue::SExpr(items) => items.clone(),
+        MettaValue::Nil => vec![],
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "sort-atom: first argument must be a list, got {}. Usage: (sort-atom list [comparator])",
+                    super::friendly_value_repr(list)
+                ),
+                Arc::new(list.clone()),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let Some(comparator) = items.get(2) else {
+        let mut sorted = elements;
+        sorted.sort_by(structural_order);
+        return (vec![MettaValue::SExpr(sorted)], env);
+    };
+
+    let mut final_env = env;
+    let mut less = |a: &MettaValue, b: &MettaValue| -> Result<bool, MettaValue> {
+        let call = MettaValue::SExpr(vec![comparator.clone(), a.clone(), b.clone()]);
+        let (results, new_env) = eval(call, final_env.clone());
+        final_env = new_env;
+        match results.first() {
+            Some(MettaValue::Bool(before)) => Ok(*before),
+            Some(err @ MettaValue::Error(_, _)) => Err(err.clone()),
+            other => Err(MettaValue::Error(
+                format!(
+                    "sort-atom: comparator must return True or False, got {}",
+                    other.map_or("no result".to_string(), super::friendly_value_repr)
+                ),
+                Arc::new(comparator.clone()),
+            )),
+        }
+    };
+
+    let result = match merge_sort(elements, &mut less) {
+        Ok(sorted) => MettaValue::SExpr(sorted),
+        Err(err) => err,
+    };
+    (vec![result], final_env)
+}
+
+/// Default ordering for `sort-atom`: numbers numerically (Long and Float mixed),
+/// strings and symbols by name, expressions element-wise, and otherwise by kind
+fn structural_order(a: &MettaValue, b: &MettaValue) -> std::cmp::Ordering {
+    fn rank(value: &MettaValue) -> u8 {
+        match value {
+            MettaValue::Bool(_) => 0,
+            MettaValue::Long(_) | MettaValue::Float(_) => 1,
+            MettaValue::String(_) => 2,
+            MettaValue::Atom(_) => 3,
+            MettaValue::SExpr(_) => 4,
+            _ => 5,
+        }
+    }
+
+    match (a, b) {
+        (MettaValue::Bool(x), MettaValue::Bool(y)) => x.cmp(y),
+        (MettaValue::Long(x), MettaValue::Long(y)) => x.cmp(y),
+        (
+            MettaValue::Long(_) | MettaValue::Float(_),
+            MettaValue::Long(_) | MettaValue::Float(_),
+        ) => {
+            let as_f64 = |v: &MettaValue| match v {
+                MettaValue::Long(n) => *n as f64,
+                MettaValue::Float(f) => *f,
+                _ => unreachable!(),
+            };
+            as_f64(a).total_cmp(&as_f64(b))
+        }
+        (MettaValue::String(x), MettaValue::String(y)) => x.cmp(y),
+        (MettaValue::Atom(x), MettaValue::Atom(y)) => x.cmp(y),
+        (MettaValue::SExpr(xs), MettaValue::SExpr(ys)) => xs
+            .iter()
+            .zip(ys)
+            .map(|(x, y)| structural_order(x, y))
+            .find(|ord| ord.is_ne())
+            .unwrap_or_else(|| xs.len().cmp(&ys.len())),
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
+/// Stable merge sort driven by a fallible "a belongs before b" comparison,
+/// which lets a user comparator's errors stop the sort
+fn merge_sort<F>(mut elements: Vec<MettaValue>, less: &mut F) -> Result<Vec<MettaValue>, MettaValue>
+where
+    F: FnMut(&MettaValue, &MettaValue) -> Result<bool, MettaValue>,
+{
+    if elements.len() <= 1 {
+        return Ok(elements);
+    }
+
+    let right = elements.split_off(elements.len() / 2);
+    let left = merge_sort(elements, less)?;
+    let right = merge_sort(right, less)?;
+
+    let mut merged = Vec::with_capacity(left.len() + right.len());
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    while let (Some(l), Some(r)) = (left.peek(), right.peek()) {
+        // Take from the right only when strictly before, keeping equal elements in order
+        if less(r, l)? {
+            merged.extend(right.next());
+        } else {
+            merged.extend(left.next());
+        }
+    }
+    merged.extend(left);
+    merged.extend(right);
+    Ok(merged)
+}
+
 /// Suggest variable format when user provides a plain atom instead of `$var`
 /// Returns a suggestion string if the atom looks like it should be a variable
 fn suggest_variable_format(atom: &str) -> Option<String> {
     // If it's already a variable, no suggestion needed
-    if atom.starts_with('$') || atom.starts_with('&') || atom.starts_with('\'') {
+    if atom.starts_with('$') || atom.starts_with('\'') {
         return None;
     }
 
@@ -313,23 +442,72 @@ fn suggest_variable_format(atom: &str) -> Option<String> {
 /// Substitute a variable in an expression with a value
 /// This is a simplified version of atom-subst
 fn substitute_variable(expr: &MettaValue, var_name: &str, value: &MettaValue) -> MettaValue {
+    substitute_variables(expr, &[(var_name, value)])
+}
+
+/// Substitute several variables at once
+///
+/// A nested `map-atom`, `filter-atom` or `foldl-atom` that binds one of the variables
+/// again shadows it: its own variables and body are left alone, so an outer fold's
+/// `$acc` does not leak into an inner fold that also names its accumulator `$acc`.
+fn substitute_variables(expr: &MettaValue, subst: &[(&str, &MettaValue)]) -> MettaValue {
     match expr {
-        MettaValue::Atom(name) if name == var_name => value.clone(),
+        MettaValue::Atom(name) => subst
+            .iter()
+            .find(|(var, _)| var == name)
+            .map_or_else(|| expr.clone(), |(_, value)| (*value).clone()),
         MettaValue::SExpr(items) => {
+            let (scope_start, bound) = bound_variables(items);
+            let inner: Vec<(&str, &MettaValue)> = subst
+                .iter()
+                .filter(|(var, _)| !bound.contains(var))
+                .copied()
+                .collect();
             let substituted_items: Vec<MettaValue> = items
                 .iter()
-                .map(|item| substitute_variable(item, var_name, value))
+                .enumerate()
+                .map(|(i, item)| {
+                    if i < scope_start {
+                        substitute_variables(item, subst)
+                    } else {
+                        substitute_variables(item, &inner)
+                    }
+                })
                 .collect();
             MettaValue::SExpr(substituted_items)
         }
+        MettaValue::Conjunction(goals) => MettaValue::Conjunction(
+            goals
+                .iter()
+                .map(|goal| substitute_variables(goal, subst))
+                .collect(),
+        ),
         MettaValue::Error(msg, details) => {
-            let substituted_details = substitute_variable(details, var_name, value);
+            let substituted_details = substitute_variables(details, subst);
             MettaValue::Error(msg.clone(), Arc::new(substituted_details))
         }
         _ => expr.clone(),
     }
 }
 
+/// Variables bound by a list-operation form, with the index where their scope starts
+/// (the list and initial value are outside it); `(usize::MAX, [])` for other forms
+fn bound_variables(items: &[MettaValue]) -> (usize, Vec<&str>) {
+    let var = |i: usize| match items.get(i) {
+        Some(MettaValue::Atom(name)) if name.starts_with('$') => Some(name.as_str()),
+        _ => None,
+    };
+    match (items.first(), items.len()) {
+        (Some(MettaValue::Atom(op)), 4) if op == "map-atom" || op == "filter-atom" => {
+            (2, var(2).into_iter().collect())
+        }
+        (Some(MettaValue::Atom(op)), 6) if op == "foldl-atom" => {
+            (3, var(3).into_iter().chain(var(4)).collect())
+        }
+        _ => (usize::MAX, Vec::new()),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -909,18 +1087,16 @@ mod tests {
     }
 
     #[test]
-    fn test_filter_atom_truthy_values() {
+    fn test_filter_atom_non_bool_predicate_is_error() {
         let env = Environment::new();
 
-        // Test truthy/falsy behavior with non-boolean values
-        // (filter-atom (42 nil "hello" 0) $x $x)
+        // (filter-atom (True 42 False) $x $x) - 42 is not a Bool
         let items = vec![
             MettaValue::Atom("filter-atom".to_string()),
             MettaValue::SExpr(vec![
+                MettaValue::Bool(true),
                 MettaValue::Long(42),
-                MettaValue::Nil,
-                MettaValue::String("hello".to_string()),
-                MettaValue::Long(0),
+                MettaValue::Bool(false),
             ]),
             MettaValue::Atom("$x".to_string()),
             MettaValue::Atom("$x".to_string()),
@@ -929,17 +1105,46 @@ mod tests {
         let (results, _) = eval_filter_atom(items, env);
         assert_eq!(results.len(), 1);
         match &results[0] {
-            MettaValue::SExpr(filtered) => {
-                // Should filter out only nil (falsy), keep everything else (truthy)
-                assert_eq!(filtered.len(), 3);
-                assert_eq!(filtered[0], MettaValue::Long(42));
-                assert_eq!(filtered[1], MettaValue::String("hello".to_string()));
-                assert_eq!(filtered[2], MettaValue::Long(0));
+            MettaValue::Error(msg, details) => {
+                assert_eq!(msg, "filter-atom: predicate must return Bool");
+                assert_eq!(
+                    **details,
+                    MettaValue::SExpr(vec![MettaValue::Long(42), MettaValue::Long(42)])
+                );
             }
-            _ => panic!("Expected S-expression result"),
+            other => panic!("Expected Error, got {:?}", other),
         }
     }
 
+    #[test]
+    fn test_map_atom_propagates_error_from_function() {
+        let env = Environment::new();
+
+        // (map-atom (1 0 2) $x (/ 10 $x)) - dividing by the 0 element fails
+        let items = vec![
+            MettaValue::Atom("map-atom".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Long(1),
+                MettaValue::Long(0),
+                MettaValue::Long(2),
+            ]),
+            MettaValue::Atom("$x".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("/".to_string()),
+                MettaValue::Long(10),
+                MettaValue::Atom("$x".to_string()),
+            ]),
+        ];
+
+        let (results, _) = eval_map_atom(items, env);
+        assert_eq!(results.len(), 1);
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("zero")),
+            "{:?}",
+            results
+        );
+    }
+
     #[test]
     fn test_filter_atom_wrong_arity() {
         let env = Environment::new();
@@ -1054,6 +1259,50 @@ mod tests {
 
     // === Comprehensive Foldl-Atom Tests ===
 
+    #[test]
+    fn test_foldl_atom_with_collapse_and_nested_fold() {
+        let src = r#"
+            (= (choices $x) (superpose ($x (* $x 10))))
+            !(foldl-atom (1 2) 0 $acc $x
+                (+ $acc (let $ys (collapse (choices $x))
+                          (foldl-atom $ys 0 $acc $y (+ $acc $y)))))
+        "#;
+        let (forms, _) = crate::backend::run::eval_forms(src, Environment::new());
+
+        // Each step sums its own collapsed choices: 0 + (1 + 10) + (2 + 20)
+        assert_eq!(forms[1], vec![MettaValue::Long(33)]);
+    }
+
+    #[test]
+    fn test_foldl_atom_accumulator_is_not_substituted_again() {
+        let env = Environment::new();
+
+        // (foldl-atom (a) ($x) $acc $x (quote ($x $acc))) -> (a ($x))
+        let items = vec![
+            MettaValue::Atom("foldl-atom".to_string()),
+            MettaValue::SExpr(vec![MettaValue::Atom("a".to_string())]),
+            MettaValue::SExpr(vec![MettaValue::Atom("$x".to_string())]),
+            MettaValue::Atom("$acc".to_string()),
+            MettaValue::Atom("$x".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("quote".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("$x".to_string()),
+                    MettaValue::Atom("$acc".to_string()),
+                ]),
+            ]),
+        ];
+
+        let (results, _) = eval_foldl_atom(items, env);
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("a".to_string()),
+                MettaValue::SExpr(vec![MettaValue::Atom("$x".to_string())]),
+            ])]
+        );
+    }
+
     #[test]
     fn test_foldl_atom_complex_operation() {
         let env = Environment::new();
@@ -1697,4 +1946,106 @@ mod tests {
             _ => panic!("Expected error without suggestion"),
         }
     }
+
+    fn longs(values: &[i64]) -> MettaValue {
+        MettaValue::SExpr(values.iter().map(|n| MettaValue::Long(*n)).collect())
+    }
+
+    fn sort_atom(args: Vec<MettaValue>, env: Environment) -> Vec<MettaValue> {
+        let mut items = vec![MettaValue::Atom("sort-atom".to_string())];
+        items.extend(args);
+        eval_sort_atom(items, env).0
+    }
+
+    #[test]
+    fn test_sort_atom_numeric() {
+        let results = sort_atom(vec![longs(&[3, 1, 2])], Environment::new());
+        assert_eq!(results, vec![longs(&[1, 2, 3])]);
+
+        // Longs and floats sort together
+        let results = sort_atom(
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Float(2.5),
+                MettaValue::Long(3),
+                MettaValue::Long(-1),
+            ])],
+            Environment::new(),
+        );
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Long(-1),
+                MettaValue::Float(2.5),
+                MettaValue::Long(3),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_sort_atom_descending_comparator() {
+        let results = sort_atom(
+            vec![longs(&[3, 1, 4, 1, 5]), MettaValue::Atom(">".to_string())],
+            Environment::new(),
+        );
+        assert_eq!(results, vec![longs(&[5, 4, 3, 1, 1])]);
+
+        // A user-defined comparator
+        let (_, env) =
+            crate::backend::run::eval_forms("(= (desc $a $b) (> $a $b))", Environment::new());
+        let results = sort_atom(
+            vec![longs(&[2, 9, 7]), MettaValue::Atom("desc".to_string())],
+            env,
+        );
+        assert_eq!(results, vec![longs(&[9, 7, 2])]);
+    }
+
+    #[test]
+    fn test_sort_atom_is_stable() {
+        // Compare pairs by their first element only
+        let (_, env) = crate::backend::run::eval_forms(
+            "(= (by-key ($k1 $v1) ($k2 $v2)) (< $k1 $k2))",
+            Environment::new(),
+        );
+        let pairs = MettaValue::SExpr(vec![
+            longs(&[2, 0]),
+            longs(&[1, 1]),
+            longs(&[2, 2]),
+            longs(&[1, 3]),
+        ]);
+        let results = sort_atom(vec![pairs, MettaValue::Atom("by-key".to_string())], env);
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                longs(&[1, 1]),
+                longs(&[1, 3]),
+                longs(&[2, 0]),
+                longs(&[2, 2])
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_sort_atom_empty_and_single() {
+        let results = sort_atom(vec![MettaValue::SExpr(vec![])], Environment::new());
+        assert_eq!(results, vec![MettaValue::SExpr(vec![])]);
+
+        let results = sort_atom(
+            vec![longs(&[42]), MettaValue::Atom(">".to_string())],
+            Environment::new(),
+        );
+        assert_eq!(results, vec![longs(&[42])]);
+    }
+
+    #[test]
+    fn test_sort_atom_comparator_errors() {
+        // A comparator that does not return a Bool
+        let results = sort_atom(
+            vec![longs(&[1, 2]), MettaValue::Atom("+".to_string())],
+            Environment::new(),
+        );
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("True or False")));
+
+        let results = sort_atom(vec![MettaValue::Long(5)], Environment::new());
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("must be a list")));
+    }
 }
@@ -1,22 +1,28 @@
 /// Require exact argument count with custom usage message
+/// The count is the special form's entry in the evaluator's arity table; a form
+/// missing from the table fails to compile.
 macro_rules! require_args_with_usage {
-    ($op:expr, $items:expr, $expected:expr, $env:expr, $usage:expr) => {
-        if $items.len() < $expected + 1 {
+    ($op:expr, $items:expr, $env:expr, $usage:expr) => {{
+        const EXPECTED: usize = match $crate::backend::eval::special_form_arity($op) {
+            Some(arity) => arity,
+            None => panic!("special form missing from SPECIAL_FORM_ARITY"),
+        };
+        if $items.len() < EXPECTED + 1 {
             let got = $items.len().saturating_sub(1);
             let err = MettaValue::Error(
                 format!(
                     "{} requires exactly {} argument{}, got {}. Usage: {}",
                     $op,
-                    $expected,
-                    if $expected == 1 { "" } else { "s" },
+                    EXPECTED,
+                    if EXPECTED == 1 { "" } else { "s" },
                     got,
                     $usage
                 ),
-                std::sync::Arc::new(MettaValue::SExpr($items.to_vec())),
+                std::sync::Arc::new(MettaValue::Atom("ArityError".to_string())),
             );
             return (vec![err], $env);
         }
-    };
+    }};
 }
 
 /// Require exact argument count for builtin functions (returns MettaValue::Error, not EvalResult)
@@ -49,7 +55,7 @@ macro_rules! require_builtin_args {
                     if $expected == 1 { "" } else { "s" },
                     $args.len()
                 ),
-                std::sync::Arc::new(MettaValue::Nil),
+                std::sync::Arc::new(MettaValue::Atom("ArityError".to_string())),
             );
         }
     };
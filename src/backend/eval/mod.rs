@@ -12,27 +12,31 @@ mod macros;
 mod bindings;
 mod builtin;
 mod control_flow;
+mod docs;
 mod errors;
 mod evaluation;
 mod expression;
 pub mod fixed_point;
 mod list_ops;
+mod modules;
 mod mork_forms;
+mod nondeterminism;
 pub mod priority;
 mod quoting;
+mod random;
 mod set;
 mod space;
+mod state;
 mod testing;
 mod types;
 
+use std::cell::Cell;
 use std::collections::VecDeque;
 use std::sync::Arc;
 use tracing::{debug, trace, warn};
 
-use crate::backend::environment::Environment;
+use crate::backend::environment::{Environment, RuleTraceSink, StateFork};
 use crate::backend::models::{Bindings, EvalResult, MettaValue, Rule};
-use crate::backend::mork_convert::{mork_bindings_to_metta, ConversionContext};
-use mork_expr::Expr;
 
 // =============================================================================
 // Iterative Trampoline Types
@@ -80,6 +84,8 @@ enum Continuation {
         results: Vec<MettaValue>,
         /// Environment
         env: Environment,
+        /// State cells at the fork, when more than one rule matched
+        state_fork: Option<StateFork>,
         /// Evaluation depth
         depth: usize,
         /// Parent continuation
@@ -92,6 +98,70 @@ enum Continuation {
 /// Set to 1000 to allow legitimate deep nesting while still catching runaway recursion
 const MAX_EVAL_DEPTH: usize = 1000;
 
+thread_local! {
+    /// Number of `eval` calls active on this thread, compared against
+    /// `Environment::max_call_depth` to turn runaway recursion into an error
+    static CALL_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Decrements `CALL_DEPTH` when a nested `eval` returns (or unwinds)
+struct CallDepthGuard;
+
+impl CallDepthGuard {
+    fn enter() -> Self {
+        CALL_DEPTH.with(|d| d.set(d.get() + 1));
+        CallDepthGuard
+    }
+}
+
+impl Drop for CallDepthGuard {
+    fn drop(&mut self) {
+        CALL_DEPTH.with(|d| d.set(d.get() - 1));
+    }
+}
+
+/// Number of `eval` calls active on this thread
+pub(super) fn call_depth() -> usize {
+    CALL_DEPTH.with(|d| d.get())
+}
+
+thread_local! {
+    /// Result limit of the innermost `collapse` being evaluated on this thread, and
+    /// whether it has been exceeded
+    static RESULT_LIMIT: Cell<(Option<usize>, bool)> = const { Cell::new((None, false)) };
+}
+
+/// Applies a `collapse` result limit while its expression is evaluated, and restores
+/// the enclosing collapse's limit when dropped
+pub(super) struct ResultLimitGuard((Option<usize>, bool));
+
+impl ResultLimitGuard {
+    pub(super) fn enter(limit: Option<usize>) -> Self {
+        ResultLimitGuard(RESULT_LIMIT.with(|l| l.replace((limit, false))))
+    }
+
+    /// Whether some call gathered more results than the limit since `enter`
+    pub(super) fn exceeded(&self) -> bool {
+        RESULT_LIMIT.with(|l| l.get().1)
+    }
+}
+
+impl Drop for ResultLimitGuard {
+    fn drop(&mut self) {
+        RESULT_LIMIT.with(|l| l.set(self.0));
+    }
+}
+
+/// Record that a call has gathered `count` results; true once the limit is exceeded
+fn over_result_limit(count: usize) -> bool {
+    RESULT_LIMIT.with(|l| {
+        let (limit, exceeded) = l.get();
+        let exceeded = exceeded || limit.is_some_and(|limit| count > limit);
+        l.set((limit, exceeded));
+        exceeded
+    })
+}
+
 /// Maximum number of results in Cartesian product to prevent combinatorial explosion
 /// This limits the total number of combinations explored during nondeterministic evaluation
 const MAX_CARTESIAN_RESULTS: usize = 10000;
@@ -102,6 +172,7 @@ const SPECIAL_FORMS: &[&str] = &[
     "!",
     "quote",
     "if",
+    "while",
     "error",
     "is-error",
     "catch",
@@ -113,14 +184,119 @@ const SPECIAL_FORMS: &[&str] = &[
     "case",
     "switch",
     "let",
+    "let*",
+    "bind!",
     ":",
     "get-type",
     "check-type",
     "map-atom",
     "filter-atom",
     "foldl-atom",
+    "sort-atom",
+];
+
+/// Minimum argument counts of the special forms that check their arguments with
+/// `require_args_with_usage!`
+/// Calls with fewer arguments evaluate to an `ArityError`; `compile_checked` reads the
+/// same table to report them before evaluation.
+const SPECIAL_FORM_ARITY: &[(&str, usize)] = &[
+    ("quote", 1),
+    ("if", 3),
+    ("while", 2),
+    ("let", 3),
+    ("eval", 1),
+    ("chain", 3),
+    ("superpose", 1),
+    ("collapse", 1),
+    ("cons-atom", 2),
+    ("decons-atom", 1),
+    ("car-atom", 1),
+    ("cdr-atom", 1),
+    ("size-atom", 1),
+    ("index-atom", 2),
+    ("nth-atom", 2),
+    ("map-atom", 3),
+    ("filter-atom", 3),
+    ("new-state", 1),
+    ("get-state", 1),
+    ("change-state!", 2),
+    ("add-fact!", 1),
+    ("bind!", 2),
+    ("if-empty", 3),
+    ("if-non-empty", 3),
+    ("if-equal", 4),
+    ("case", 2),
+    ("switch", 2),
+    ("switch-minimal", 2),
+    ("switch-internal", 2),
+    ("get-doc", 1),
+    ("is-error", 1),
+    ("!", 1),
+    ("function", 1),
+    ("return", 1),
+    ("min-atom", 1),
+    ("max-atom", 1),
+    ("flatten", 1),
+    ("enumerate-atom", 1),
+    ("sort-atom", 1),
+    ("list-to-nondet", 1),
+    ("collapse-bind", 1),
+    ("random-int", 2),
+    ("random-float", 0),
+    ("unique-atom", 1),
+    ("union-atom", 2),
+    ("intersection-atom", 2),
+    ("subtraction-atom", 2),
+    ("=", 2),
+    ("new-space", 0),
+    ("env-stats", 0),
+    ("intern-stats", 0),
+    ("=alpha", 2),
+    ("assertEqual", 2),
+    ("assert", 1),
+    ("assertIncludes", 2),
+    ("assertAlphaEqual", 2),
+    ("assertEqualMsg", 3),
+    ("assertAlphaEqualMsg", 3),
+    ("assertEqualToResult", 2),
+    ("assertAlphaEqualToResult", 2),
+    ("assertEqualToResultMsg", 3),
+    ("assertAlphaEqualToResultMsg", 3),
+    (":", 2),
+    ("get-type", 1),
+    ("check-type", 2),
 ];
 
+/// Minimum number of arguments the special form `op` requires, if it is in the table
+/// A `const fn` so `require_args_with_usage!` looks its forms up at compile time.
+pub(crate) const fn special_form_arity(op: &str) -> Option<usize> {
+    let mut i = 0;
+    while i < SPECIAL_FORM_ARITY.len() {
+        let (name, arity) = SPECIAL_FORM_ARITY[i];
+        if const_str_eq(name, op) {
+            return Some(arity);
+        }
+        i += 1;
+    }
+    None
+}
+
+/// String equality usable in a `const fn`
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let (a, b) = (a.as_bytes(), b.as_bytes());
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
 /// Convert MettaValue to a user-friendly representation for error messages
 /// Unlike debug format, this shows values in MeTTa syntax
 pub(crate) fn friendly_value_repr(value: &MettaValue) -> String {
@@ -166,8 +342,32 @@ fn suggest_special_form(op: &str) -> Option<String> {
 /// Returns (results, new_environment)
 /// This is the public entry point that uses iterative evaluation with an explicit work stack
 /// to prevent stack overflow for large expressions.
+///
+/// Special forms evaluate their arguments by calling back into `eval`, so deep
+/// non-tail recursion nests native calls. Once `Environment::max_call_depth` nested
+/// calls are active, an error is returned instead of overflowing the stack.
 pub fn eval(value: MettaValue, env: Environment) -> EvalResult {
     debug!(metta_val = ?value);
+
+    let call_depth = CALL_DEPTH.with(|d| d.get());
+    if call_depth >= env.max_call_depth() {
+        warn!(
+            call_depth,
+            max_call_depth = env.max_call_depth(),
+            "Maximum call depth exceeded - possible non-tail recursion without a base case"
+        );
+        let err = MettaValue::Error(
+            format!(
+                "Maximum call depth ({}) exceeded: evaluation nested too deeply. \
+                 Check recursive rules for a missing base case",
+                env.max_call_depth()
+            ),
+            Arc::new(value),
+        );
+        return (vec![err], env);
+    }
+
+    let _guard = CallDepthGuard::enter();
     eval_trampoline(value, env)
 }
 
@@ -185,6 +385,8 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
 
     // Continuation storage - index 0 is always Done
     let mut continuations: Vec<Continuation> = vec![Continuation::Done];
+    // Slots of finished continuations, reused so tail calls run in constant space
+    let mut free_conts: Vec<usize> = Vec::new();
 
     // Final result storage
     let mut final_result: Option<EvalResult> = None;
@@ -199,6 +401,21 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                 cont_id,
             } => {
                 trace!(target: "mettatron::backend::eval::eval_trampoline", ?value, depth, cont_id, "eval work item");
+                env.metrics_collector().record_step(depth);
+
+                // Cooperative cancellation: abandon all pending work once interrupted
+                if env.is_interrupted() {
+                    let err = MettaValue::Error(
+                        "Interrupted".to_string(),
+                        Arc::new(MettaValue::Atom("Interrupted".to_string())),
+                    );
+                    return (vec![err], env);
+                }
+
+                // The enclosing collapse has gathered too many results and reports it
+                if over_result_limit(0) {
+                    return (vec![], env);
+                }
 
                 // Perform one step of evaluation
                 let step_result = eval_step(value, env.clone(), depth);
@@ -210,6 +427,16 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                         work_stack.push(WorkItem::Resume { cont_id, result });
                     }
 
+                    // Tail position - the value replaces this one, at the same depth
+                    EvalStep::EvalTail { value, env } => {
+                        work_stack.push(WorkItem::Eval {
+                            value,
+                            env,
+                            depth,
+                            cont_id,
+                        });
+                    }
+
                     // Need to evaluate S-expression sub-items
                     EvalStep::EvalSExpr { items, env, depth } => {
                         if items.is_empty() {
@@ -225,14 +452,17 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                             let first = items_deque.pop_front().unwrap();
 
                             // Create continuation to collect results
-                            let collect_cont_id = continuations.len();
-                            continuations.push(Continuation::CollectSExpr {
-                                remaining: items_deque, // Already a VecDeque, no copy needed
-                                collected: Vec::new(),
-                                original_env: env.clone(),
-                                depth,
-                                parent_cont: cont_id,
-                            });
+                            let collect_cont_id = push_continuation(
+                                &mut continuations,
+                                &mut free_conts,
+                                Continuation::CollectSExpr {
+                                    remaining: items_deque, // Already a VecDeque, no copy needed
+                                    collected: Vec::new(),
+                                    original_env: env.clone(),
+                                    depth,
+                                    parent_cont: cont_id,
+                                },
+                            );
 
                             // Evaluate first item (moved, not cloned)
                             work_stack.push(WorkItem::Eval {
@@ -292,21 +522,40 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                                             cont_id: parent_cont,
                                             result: (base_results, env),
                                         });
+                                    } else if matches.len() == 1 && base_results.is_empty() {
+                                        // A single matching rule is a tail call: its RHS
+                                        // replaces the call, whichever rule it calls next
+                                        let (rhs, bindings) = &matches[0];
+                                        work_stack.push(WorkItem::Eval {
+                                            value: apply_bindings(rhs, bindings),
+                                            env,
+                                            depth,
+                                            cont_id: parent_cont,
+                                        });
                                     } else {
                                         // Convert to VecDeque ONCE and pop front (O(n) + O(1) vs O(n²))
                                         let mut matches_deque: VecDeque<_> =
                                             matches.into_iter().collect();
                                         let (rhs, bindings) = matches_deque.pop_front().unwrap();
 
+                                        // Several matching rules fork evaluation; keep their
+                                        // state changes from leaking into each other
+                                        let state_fork =
+                                            (!matches_deque.is_empty()).then(|| env.fork_states());
+
                                         // Create continuation to process remaining rule matches
-                                        let match_cont_id = continuations.len();
-                                        continuations.push(Continuation::ProcessRuleMatches {
-                                            remaining_matches: matches_deque,
-                                            results: base_results,
-                                            env: env.clone(),
-                                            depth,
-                                            parent_cont,
-                                        });
+                                        let match_cont_id = push_continuation(
+                                            &mut continuations,
+                                            &mut free_conts,
+                                            Continuation::ProcessRuleMatches {
+                                                remaining_matches: matches_deque,
+                                                results: base_results,
+                                                env: env.clone(),
+                                                state_fork,
+                                                depth,
+                                                parent_cont,
+                                            },
+                                        );
 
                                         // Evaluate first rule RHS (values moved, not cloned)
                                         let instantiated_rhs = apply_bindings(&rhs, &bindings);
@@ -346,14 +595,27 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                         mut remaining_matches,
                         mut results,
                         env,
+                        mut state_fork,
                         depth,
                         parent_cont,
                     } => {
                         // Add results from this rule evaluation
                         results.extend(result.0);
+                        if let Some(fork) = state_fork.as_mut() {
+                            env.end_state_branch(fork);
+                        }
+                        if over_result_limit(results.len()) {
+                            if let Some(fork) = state_fork {
+                                env.join_state_fork(fork);
+                            }
+                            return (vec![], env);
+                        }
 
                         if remaining_matches.is_empty() {
                             // All rules evaluated
+                            if let Some(fork) = state_fork {
+                                env.join_state_fork(fork);
+                            }
                             work_stack.push(WorkItem::Resume {
                                 cont_id: parent_cont,
                                 result: (results, env),
@@ -367,6 +629,7 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                                 remaining_matches,
                                 results,
                                 env: env.clone(),
+                                state_fork,
                                 depth,
                                 parent_cont,
                             };
@@ -382,6 +645,11 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
                         }
                     }
                 }
+
+                // A continuation that was not put back has finished; reuse its slot
+                if cont_id != 0 && matches!(continuations[cont_id], Continuation::Done) {
+                    free_conts.push(cont_id);
+                }
             }
         }
     }
@@ -389,6 +657,24 @@ fn eval_trampoline(value: MettaValue, env: Environment) -> EvalResult {
     final_result.unwrap_or_else(|| (vec![], env))
 }
 
+/// Store a continuation, reusing the slot of a finished one when available
+fn push_continuation(
+    continuations: &mut Vec<Continuation>,
+    free_conts: &mut Vec<usize>,
+    cont: Continuation,
+) -> usize {
+    match free_conts.pop() {
+        Some(id) => {
+            continuations[id] = cont;
+            id
+        }
+        None => {
+            continuations.push(cont);
+            continuations.len() - 1
+        }
+    }
+}
+
 /// Result of a single evaluation step
 #[derive(Debug)]
 enum EvalStep {
@@ -400,6 +686,8 @@ enum EvalStep {
         env: Environment,
         depth: usize,
     },
+    /// Evaluate a value in tail position: its results are the results of this step
+    EvalTail { value: MettaValue, env: Environment },
 }
 
 /// Result of processing collected S-expression results
@@ -448,6 +736,12 @@ fn eval_step(value: MettaValue, env: Environment, depth: usize) -> EvalStep {
         // Errors propagate immediately
         MettaValue::Error(_, _) => EvalStep::Done((vec![value], env)),
 
+        // Tokens registered with bind! evaluate to their value
+        MettaValue::Atom(ref name) if name.starts_with('&') => match env.get_token(name) {
+            Some(bound) => EvalStep::Done((vec![bound], env)),
+            None => EvalStep::Done((vec![value], env)),
+        },
+
         // Atoms evaluate to themselves
         MettaValue::Atom(_) => EvalStep::Done((vec![value], env)),
 
@@ -480,9 +774,14 @@ fn eval_sexpr_step(items: Vec<MettaValue>, env: Environment, depth: usize) -> Ev
             "=" => return EvalStep::Done(space::eval_add(items, env)),
             "!" => return EvalStep::Done(evaluation::force_eval(items, env)),
             "quote" => return EvalStep::Done(quoting::eval_quote(items, env)),
-            "if" => return EvalStep::Done(control_flow::eval_if(items, env)),
+            "if" => return control_flow::eval_if(items, env),
+            "while" => return EvalStep::Done(control_flow::eval_while(items, env)),
             "if-equal" => return EvalStep::Done(control_flow::eval_if_equal(items, env)),
+            "if-empty" => return EvalStep::Done(control_flow::eval_if_empty(items, env)),
+            "if-non-empty" => return EvalStep::Done(control_flow::eval_if_non_empty(items, env)),
             "=alpha" => return EvalStep::Done(testing::eval_alpha_eq(items, env)),
+            "assert" => return EvalStep::Done(testing::eval_assert(items, env)),
+            "assertIncludes" => return EvalStep::Done(testing::eval_assert_includes(items, env)),
             "assertEqual" => return EvalStep::Done(testing::eval_assert_equal(items, env)),
             "assertAlphaEqual" => {
                 return EvalStep::Done(testing::eval_assert_alpha_equal(items, env))
@@ -510,7 +809,37 @@ fn eval_sexpr_step(items: Vec<MettaValue>, env: Environment, depth: usize) -> Ev
             "function" => return EvalStep::Done(evaluation::eval_function(items, env)),
             "return" => return EvalStep::Done(evaluation::eval_return(items, env)),
             "chain" => return EvalStep::Done(evaluation::eval_chain(items, env)),
+            "import!" => return EvalStep::Done(modules::eval_import(items, env)),
+            "@" if matches!(items.get(1), Some(MettaValue::Atom(a)) if a == "doc") => {
+                return EvalStep::Done(docs::eval_doc(items, env))
+            }
+            "@doc" => return EvalStep::Done(docs::eval_doc(items, env)),
+            "get-doc" => return EvalStep::Done(docs::eval_get_doc(items, env)),
+            "superpose" => return EvalStep::Done(nondeterminism::eval_superpose(items, env)),
+            "empty" => return EvalStep::Done(nondeterminism::eval_empty(items, env)),
+            "nop" => return EvalStep::Done(nondeterminism::eval_nop(items, env)),
+            "random-int" => return EvalStep::Done(random::eval_random_int(items, env)),
+            "random-float" => return EvalStep::Done(random::eval_random_float(items, env)),
+            "new-state" => return EvalStep::Done(state::eval_new_state(items, env)),
+            "get-state" => return EvalStep::Done(state::eval_get_state(items, env)),
+            "change-state!" => return EvalStep::Done(state::eval_change_state(items, env)),
+            "collapse" | "nondet-to-list" => {
+                return EvalStep::Done(nondeterminism::eval_collapse(items, env))
+            }
+            "list-to-nondet" => {
+                return EvalStep::Done(nondeterminism::eval_list_to_nondet(items, env))
+            }
+            "collapse-bind" => {
+                return EvalStep::Done(nondeterminism::eval_collapse_bind(items, env))
+            }
             "match" => return EvalStep::Done(space::eval_match(items, env)),
+            "new-space" => return EvalStep::Done(space::eval_new_space(items, env)),
+            "merge-space!" => return EvalStep::Done(space::eval_merge_space(items, env)),
+            "add-atom" => return EvalStep::Done(space::eval_add_atom(items, env)),
+            "add-fact!" => return EvalStep::Done(space::eval_add_fact(items, env)),
+            "add-reduct" => return EvalStep::Done(space::eval_add_reduct(items, env)),
+            "env-stats" => return EvalStep::Done(space::eval_env_stats(items, env)),
+            "intern-stats" => return EvalStep::Done(space::eval_intern_stats(items, env)),
             "case" => return EvalStep::Done(control_flow::eval_case(items, env)),
             "switch" => return EvalStep::Done(control_flow::eval_switch(items, env)),
             "switch-minimal" => {
@@ -520,22 +849,28 @@ fn eval_sexpr_step(items: Vec<MettaValue>, env: Environment, depth: usize) -> Ev
                 return EvalStep::Done(control_flow::eval_switch_internal_handler(items, env))
             }
             "let" => return EvalStep::Done(bindings::eval_let(items, env)),
+            "let*" => return EvalStep::Done(bindings::eval_let_star(items, env)),
+            "bind!" => return EvalStep::Done(bindings::eval_bind(items, env)),
             ":" => return EvalStep::Done(types::eval_type_assertion(items, env)),
             "get-type" => return EvalStep::Done(types::eval_get_type(items, env)),
             "check-type" => return EvalStep::Done(types::eval_check_type(items, env)),
             "map-atom" => return EvalStep::Done(list_ops::eval_map_atom(items, env)),
             "filter-atom" => return EvalStep::Done(list_ops::eval_filter_atom(items, env)),
             "foldl-atom" => return EvalStep::Done(list_ops::eval_foldl_atom(items, env)),
+            "sort-atom" => return EvalStep::Done(list_ops::eval_sort_atom(items, env)),
 
             // S-expression manipulation
             "cons-atom" => return EvalStep::Done(expression::eval_cons_atom(items, env)),
             "decons-atom" => return EvalStep::Done(expression::eval_decons_atom(items, env)),
             "size-atom" => return EvalStep::Done(expression::eval_size_atom(items, env)),
             "index-atom" => return EvalStep::Done(expression::eval_index_atom(items, env)),
+            "nth-atom" => return EvalStep::Done(expression::eval_nth_atom(items, env)),
             "car-atom" => return EvalStep::Done(expression::eval_car_atom(items, env)),
             "cdr-atom" => return EvalStep::Done(expression::eval_cdr_atom(items, env)),
             "min-atom" => return EvalStep::Done(expression::eval_min_atom(items, env)),
             "max-atom" => return EvalStep::Done(expression::eval_max_atom(items, env)),
+            "flatten" => return EvalStep::Done(expression::eval_flatten(items, env)),
+            "enumerate-atom" => return EvalStep::Done(expression::eval_enumerate_atom(items, env)),
 
             // MORK Special Forms
             "exec" => return EvalStep::Done(mork_forms::eval_exec(items, env)),
@@ -611,6 +946,9 @@ fn process_collected_sexpr(
         // Try to match against rules
         let sexpr = MettaValue::SExpr(evaled_items.clone());
         let all_matches = try_match_all_rules(&sexpr, &unified_env);
+        unified_env
+            .metrics_collector()
+            .record_rule_dispatch(all_matches.len());
 
         if !all_matches.is_empty() {
             // Collect rule matches for later evaluation
@@ -660,7 +998,7 @@ fn handle_no_rule_match(
                 );
                 return MettaValue::Error(
                     format!("No rule matches '{}'. {}", head, suggestion),
-                    Arc::new(sexpr.clone()),
+                    Arc::new(MettaValue::Atom("UnknownSymbol".to_string())),
                 );
             }
         }
@@ -722,6 +1060,74 @@ fn eval_conjunction(goals: Vec<MettaValue>, env: Environment, _depth: usize) ->
     (results, current_env)
 }
 
+/// Expand top-level conjunction values in a result list into their goals
+///
+/// A conjunction that comes back as a value (for example an instantiated `match`
+/// template `(, $a $b)`) stands for several results, so top-level callers such as
+/// `run_state` and the CLI splice it into the results like nondeterministic fan-out.
+/// Nested conjunction goals are expanded as well, while conjunctions inside
+/// S-expressions are left alone.
+pub fn flatten_conjunction_results(results: Vec<MettaValue>) -> Vec<MettaValue> {
+    if !results
+        .iter()
+        .any(|r| matches!(r, MettaValue::Conjunction(_)))
+    {
+        return results;
+    }
+
+    fn push_flat(value: MettaValue, out: &mut Vec<MettaValue>) {
+        match value {
+            MettaValue::Conjunction(goals) => {
+                for goal in goals {
+                    push_flat(goal, out);
+                }
+            }
+            other => out.push(other),
+        }
+    }
+
+    let mut flat = Vec::with_capacity(results.len());
+    for result in results {
+        push_flat(result, &mut flat);
+    }
+    flat
+}
+
+/// Expand every conjunction in a result list, including conjunctions nested inside
+/// S-expressions, so that no result contains one
+///
+/// A nested conjunction stands for several values in its position, so the enclosing
+/// expression is repeated once per combination of them: `(result (, a b))` becomes
+/// `(result a)` and `(result b)`. Top-level conjunctions expand as with
+/// `flatten_conjunction_results`.
+pub fn flatten_nested_conjunction_results(results: Vec<MettaValue>) -> Vec<MettaValue> {
+    fn alternatives(value: MettaValue) -> Vec<MettaValue> {
+        match value {
+            MettaValue::Conjunction(goals) => goals.into_iter().flat_map(alternatives).collect(),
+            MettaValue::SExpr(items) => {
+                let mut combinations = vec![Vec::with_capacity(items.len())];
+                for item in items {
+                    let options = alternatives(item);
+                    combinations = combinations
+                        .into_iter()
+                        .flat_map(|prefix| {
+                            options.iter().map(move |option| {
+                                let mut combination = prefix.clone();
+                                combination.push(option.clone());
+                                combination
+                            })
+                        })
+                        .collect();
+                }
+                combinations.into_iter().map(MettaValue::SExpr).collect()
+            }
+            other => vec![other],
+        }
+    }
+
+    results.into_iter().flat_map(alternatives).collect()
+}
+
 /// Pattern match a pattern against a value
 /// Returns bindings if successful, None otherwise
 ///
@@ -739,26 +1145,21 @@ pub fn pattern_match(pattern: &MettaValue, value: &MettaValue) -> Option<Binding
 
 fn pattern_match_impl(pattern: &MettaValue, value: &MettaValue, bindings: &mut Bindings) -> bool {
     match (pattern, value) {
-        // Wildcard matches anything
-        (MettaValue::Atom(p), _) if p == "_" => true,
+        // Wildcards (`_`, `$_`) match anything without binding
+        (MettaValue::Atom(p), _) if MettaValue::is_wildcard_name(p) => true,
 
         // FAST PATH: First variable binding (empty bindings)
         // Optimization: Skip lookup when bindings are empty - directly insert
         // This reduces single-variable regression from 16.8% to ~5-7%
         (MettaValue::Atom(p), v)
-            if (p.starts_with('$') || p.starts_with('&') || p.starts_with('\''))
-                && p != "&"
-                && bindings.is_empty() =>
+            if (p.starts_with('$') || p.starts_with('\'')) && bindings.is_empty() =>
         {
             bindings.insert(p.clone(), v.clone());
             true
         }
 
         // GENERAL PATH: Variable with potential existing bindings
-        // EXCEPT: standalone "&" is a literal operator (used in match), not a variable
-        (MettaValue::Atom(p), v)
-            if (p.starts_with('$') || p.starts_with('&') || p.starts_with('\'')) && p != "&" =>
-        {
+        (MettaValue::Atom(p), v) if p.starts_with('$') || p.starts_with('\'') => {
             // Check if variable is already bound (linear search for SmartBindings)
             if let Some((_, existing)) = bindings.iter().find(|(name, _)| name.as_str() == p) {
                 existing == v
@@ -891,20 +1292,43 @@ fn cartesian_product(results: &[Vec<MettaValue>]) -> Result<Vec<Vec<MettaValue>>
 
 /// Apply variable bindings to a value
 ///
+/// A nested `(let pattern value body)` opens a new scope: variables bound by its
+/// pattern shadow outer bindings of the same name inside its body, so substituting
+/// into `(let $x 2 $x)` with `$x = 1` leaves the inner let untouched.
+///
 /// This is made public to support optimized match operations in Environment
 pub(crate) fn apply_bindings(value: &MettaValue, bindings: &Bindings) -> MettaValue {
     trace!(target: "mettatron::backend::eval::apply_bindings", ?value, ?bindings);
     match value {
-        // Apply bindings to variables (atoms starting with $, &, or ')
-        // EXCEPT: standalone "&" is a literal operator (used in match), not a variable
-        MettaValue::Atom(s)
-            if (s.starts_with('$') || s.starts_with('&') || s.starts_with('\'')) && s != "&" =>
-        {
-            bindings
+        // Apply bindings to variables (atoms starting with $ or ')
+        MettaValue::Atom(s) if s.starts_with('$') || s.starts_with('\'') => bindings
+            .iter()
+            .find(|(name, _)| name.as_str() == s)
+            .map(|(_, val)| val.clone())
+            .unwrap_or_else(|| value.clone()),
+        MettaValue::SExpr(items) if is_let_form(items) => {
+            let pattern = &items[1];
+            let shadowed = bindings
                 .iter()
-                .find(|(name, _)| name.as_str() == s)
-                .map(|(_, val)| val.clone())
-                .unwrap_or_else(|| value.clone())
+                .any(|(name, _)| pattern_binds(pattern, name));
+            let body = if shadowed {
+                let mut inner = Bindings::new();
+                for (name, val) in bindings.iter() {
+                    if !pattern_binds(pattern, name) {
+                        inner.insert(name.clone(), val.clone());
+                    }
+                }
+                apply_bindings(&items[3], &inner)
+            } else {
+                apply_bindings(&items[3], bindings)
+            };
+            MettaValue::SExpr(vec![
+                items[0].clone(),
+                // Every variable in the pattern is bound by this let, so it is kept as is
+                pattern.clone(),
+                apply_bindings(&items[2], bindings),
+                body,
+            ])
         }
         MettaValue::SExpr(items) => {
             let new_items: Vec<_> = items
@@ -928,151 +1352,138 @@ pub(crate) fn apply_bindings(value: &MettaValue, bindings: &Bindings) -> MettaVa
     }
 }
 
+/// Check for a `(let pattern value body)` form
+fn is_let_form(items: &[MettaValue]) -> bool {
+    items.len() == 4 && matches!(&items[0], MettaValue::Atom(head) if head == "let")
+}
+
+/// Check whether a let pattern binds the variable `name`
+fn pattern_binds(pattern: &MettaValue, name: &str) -> bool {
+    match pattern {
+        MettaValue::Atom(s) => s == name,
+        MettaValue::SExpr(items) => items.iter().any(|item| pattern_binds(item, name)),
+        _ => false,
+    }
+}
+
 /// Find ALL rules in the environment that match the given expression
 /// Returns Vec<(rhs, bindings)> with all matching rules
 ///
 /// This function supports MeTTa's non-deterministic semantics where multiple rules
 /// can match the same expression and all results should be returned.
 fn try_match_all_rules(expr: &MettaValue, env: &Environment) -> Vec<(MettaValue, Bindings)> {
-    // Try query_multi optimization first
-    let query_multi_results = try_match_all_rules_query_multi(expr, env);
-    if !query_multi_results.is_empty() {
-        return query_multi_results;
-    }
-
-    // Fall back to iteration-based approach
+    // The rule index keeps definition order, which makes the order of nondeterministic
+    // results reproducible
     try_match_all_rules_iterative(expr, env)
 }
 
-/// Try pattern matching using MORK's query_multi to find ALL matching rules (O(k) where k = matching rules)
-fn try_match_all_rules_query_multi(
+/// Optimized: Try pattern matching using indexed lookup to find ALL matching rules
+/// Uses O(1) index lookup instead of O(n) iteration
+/// Complexity: O(k) where k = rules with matching head symbol (typically k << n)
+fn try_match_all_rules_iterative(
     expr: &MettaValue,
     env: &Environment,
 ) -> Vec<(MettaValue, Bindings)> {
-    trace!(target: "mettatron::backend::eval::try_match_all_rules_query_multi", ?expr);
-
-    // Create a pattern that queries for rules: (= <expr-pattern> $rhs)
-    // This will find all rules where the LHS matches our expression
+    trace!(target: "mettatron::backend::eval::try_match_all_rules_iterative", ?expr);
 
-    // Convert expression to MORK format for querying (using cache)
-    let expr_bytes = match env.metta_to_mork_bytes_cached(expr) {
-        Ok(bytes) => bytes,
-        Err(_) => return Vec::new(), // Fallback to iterative if conversion fails
+    let matching_rules = match best_matching_rules(expr, env) {
+        Ok(matching_rules) => matching_rules,
+        // A guard that fails with an error makes the call fail, as an `if` condition does
+        Err(err) => return vec![(err, Bindings::new())],
     };
-    trace!(
-        target: "mettatron::backend::eval::try_match_all_rules_query_multi",
-        expr_bytes_len = ?expr_bytes.len(), "Convert expression to MORK format"
-    );
-
-    let space = env.create_space();
-    let ctx = ConversionContext::new();
-
-    // Create a query pattern: (= <expr> $rhs)
-    let pattern_str = format!("(= {} $rhs)", String::from_utf8_lossy(&expr_bytes));
-    let pattern_bytes = pattern_str.as_bytes();
 
-    // Parse the pattern using MORK's parser
-    let mut parse_buffer = vec![0u8; 4096];
-    let mut pdp = mork::space::ParDataParser::new(&space.sm);
-    use mork_frontend::bytestring_parser::Parser;
-    let mut ez = mork_expr::ExprZipper::new(Expr {
-        ptr: parse_buffer.as_mut_ptr(),
-    });
-    let mut context = mork_frontend::bytestring_parser::Context::new(pattern_bytes);
-
-    if pdp.sexpr(&mut context, &mut ez).is_err() {
-        return Vec::new(); // Fallback if parsing fails
+    // Duplicate results based on rule count
+    let mut final_matches = Vec::new();
+    for (rule, bindings) in matching_rules {
+        if let Some(sink) = env.rule_trace() {
+            trace_rule_application(expr, &rule, &bindings, sink);
+        }
+        let count = env.get_rule_count(&rule);
+        for _ in 0..count {
+            final_matches.push((rule.rhs.clone(), bindings.clone()));
+        }
     }
 
-    let pattern_expr = Expr {
-        ptr: parse_buffer.as_ptr().cast_mut(),
-    };
-
-    // Collect ALL matches using query_multi
-    // Note: All matches from query_multi will have the same LHS pattern (since we're querying for it)
-    // Therefore, they all have the same LHS specificity and we should return all of them
-    let mut matches: Vec<(MettaValue, Bindings)> = Vec::new();
-
-    mork::space::Space::query_multi(&space.btm, pattern_expr, |result, _matched_expr| {
-        if let Err(bindings) = result {
-            // Convert MORK bindings to mettatron format
-            if let Ok(mettatron_bindings) = mork_bindings_to_metta(&bindings, &ctx, &space) {
-                // Extract the RHS from bindings
-                if let Some((_, rhs)) = mettatron_bindings
-                    .iter()
-                    .find(|(name, _)| name.as_str() == "$rhs")
-                {
-                    matches.push((rhs.clone(), mettatron_bindings));
-                }
-            }
-        }
-        true // Continue searching for ALL matches
-    });
-    trace!(
-        target: "mettatron::backend::eval::try_match_all_rules_query_multi",
-        matches_ctr = ?matches.len(), "Collected matches"
-    );
-
-    matches
-    // space will be dropped automatically here
+    trace!(target: "mettatron::backend::eval::try_match_all_rules_iterative", ?final_matches);
+    final_matches
 }
 
-/// Optimized: Try pattern matching using indexed lookup to find ALL matching rules
-/// Uses O(1) index lookup instead of O(n) iteration
-/// Complexity: O(k) where k = rules with matching head symbol (typically k << n)
-fn try_match_all_rules_iterative(
+/// Find the rules whose LHS matches `expr`, keeping only the most specific ones,
+/// together with the bindings each match produced
+///
+/// A guarded rule only matches if its guard, with the bindings applied, evaluates to
+/// True. A guarded rule that matches is preferred over unguarded rules with an equally
+/// specific LHS, so those only fire when the guard fails. A guard that evaluates to an
+/// error gives that error instead of any matches.
+fn best_matching_rules(
     expr: &MettaValue,
     env: &Environment,
-) -> Vec<(MettaValue, Bindings)> {
-    trace!(target: "mettatron::backend::eval::try_match_all_rules_iterative", ?expr);
-
-    // Extract head symbol and arity for indexed lookup
-    let matching_rules = if let Some(head) = expr.get_head_symbol() {
-        let arity = expr.get_arity();
-        // O(1) indexed lookup instead of O(n) iteration
-        env.get_matching_rules(head, arity)
-    } else {
-        // For expressions without head symbol, check wildcard rules only
-        // This is still O(k_wildcards) instead of O(n_total)
-        env.get_matching_rules("", 0) // Empty head will return only wildcards
-    };
+) -> Result<Vec<(Rule, Bindings)>, MettaValue> {
+    // O(1) indexed lookup by head symbol and arity, narrowed by the first argument
+    // For expressions without head symbol only wildcard rules are returned
+    let matching_rules = env.get_candidate_rules(expr);
 
     // Sort rules by specificity (more specific first)
     let mut sorted_rules = matching_rules;
     sorted_rules.sort_by_key(|rule| rule.lhs.pattern_specificity());
-    trace!(target: "mettatron::backend::eval::try_match_all_rules_iterative", ?sorted_rules);
+    trace!(target: "mettatron::backend::eval::best_matching_rules", ?sorted_rules);
 
-    // Collect ALL matching rules, tracking LHS specificity
-    let mut matches: Vec<(MettaValue, Bindings, usize, Rule)> = Vec::new();
+    // Collect ALL matching rules, tracking LHS specificity (guarded rules first on ties)
+    let mut matches: Vec<(Rule, Bindings, (usize, bool))> = Vec::new();
     for rule in sorted_rules {
         if let Some(bindings) = pattern_match(&rule.lhs, expr) {
-            let lhs_specificity = rule.lhs.pattern_specificity();
-            matches.push((rule.rhs.clone(), bindings, lhs_specificity, rule));
+            if let Some(guard) = &rule.guard {
+                if !guard_holds(guard, &bindings, env)? {
+                    continue;
+                }
+            }
+            let specificity = (rule.lhs.pattern_specificity(), rule.guard.is_none());
+            matches.push((rule, bindings, specificity));
         }
     }
 
-    // Find the best (lowest) specificity
-    if let Some(best_spec) = matches.iter().map(|(_, _, spec, _)| *spec).min() {
-        // Filter to only matches with the best specificity
-        let best_matches: Vec<_> = matches
-            .into_iter()
-            .filter(|(_, _, spec, _)| *spec == best_spec)
-            .collect();
-
-        // Duplicate results based on rule count
-        let mut final_matches = Vec::new();
-        for (rhs, bindings, _, rule) in best_matches {
-            let count = env.get_rule_count(&rule);
-            for _ in 0..count {
-                final_matches.push((rhs.clone(), bindings.clone()));
-            }
-        }
+    // Filter to only matches with the best (lowest) specificity
+    let Some(best_spec) = matches.iter().map(|(_, _, spec)| *spec).min() else {
+        return Ok(Vec::new());
+    };
+    Ok(matches
+        .into_iter()
+        .filter(|(_, _, spec)| *spec == best_spec)
+        .map(|(rule, bindings, _)| (rule, bindings))
+        .collect())
+}
 
-        trace!(target: "mettatron::backend::eval::try_match_all_rules_iterative", ?final_matches);
-        final_matches
-    } else {
-        Vec::new()
+/// Evaluate a rule guard with the bindings of the match; it holds if any result is True
+/// An error among the results is returned instead.
+fn guard_holds(
+    guard: &MettaValue,
+    bindings: &Bindings,
+    env: &Environment,
+) -> Result<bool, MettaValue> {
+    let (results, _) = eval(apply_bindings(guard, bindings), env.clone());
+    trace!(target: "mettatron::backend::eval::guard_holds", ?guard, ?results);
+    if let Some(err) = results
+        .iter()
+        .find(|r| matches!(r, MettaValue::Error(_, _)))
+    {
+        return Err(err.clone());
     }
+    Ok(results.contains(&MettaValue::Bool(true)))
+}
+
+/// Report a rule that fires for `call` to the environment's rule trace sink
+fn trace_rule_application(
+    call: &MettaValue,
+    rule: &Rule,
+    bindings: &Bindings,
+    sink: &RuleTraceSink,
+) {
+    sink(&format!(
+        "{} matched {} with {}",
+        call,
+        rule.to_sexpr(),
+        bindings.to_metta_value()
+    ));
 }
 
 #[cfg(test)]
@@ -1134,17 +1545,17 @@ mod tests {
         let mut env = Environment::new();
 
         // Add rule: (= (double $x) (mul $x 2))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("double".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("*".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Long(2),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Evaluate (double 5)
@@ -1188,13 +1599,13 @@ mod tests {
         let mut env = Environment::new();
 
         // Add a rule: (= (safe-div $x $y) (if (== $y 0) (error "division by zero" $y) (div $x $y)))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("safe-div".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Atom("$y".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 MettaValue::SExpr(vec![
                     MettaValue::Atom("==".to_string()),
@@ -1212,7 +1623,7 @@ mod tests {
                     MettaValue::Atom("$y".to_string()),
                 ]),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Test successful division: (safe-div 10 2) -> 5
@@ -1319,12 +1730,12 @@ mod tests {
         // (= (fact $n) (if (> $n 0) (* $n (fact (- $n 1))) 1))
         let mut env = Environment::new();
 
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("fact".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 // Condition: (> $n 0)
                 MettaValue::SExpr(vec![
@@ -1348,7 +1759,7 @@ mod tests {
                 // Else branch: 1
                 MettaValue::Long(1),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Test (fact 3) = 6
@@ -1365,7 +1776,7 @@ mod tests {
     fn test_factorial_with_compile() {
         // Test factorial using compile() to ensure the compiled version works
         // This complements test_factorial_recursive which uses manual construction
-        use crate::backend::compile::compile;
+        use crate::backend::run::{eval_source, RunConfig};
 
         let input = r#"
             (= (fact $n) (if (> $n 0) (* $n (fact (- $n 1))) 1))
@@ -1375,18 +1786,7 @@ mod tests {
             !(fact 3)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut results = Vec::new();
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            // Collect non-empty results (skip rule definitions)
-            if !expr_results.is_empty() {
-                results.extend(expr_results);
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
 
         // Should have 4 results: fact(0)=1, fact(1)=1, fact(2)=2, fact(3)=6
         assert_eq!(results.len(), 4);
@@ -1423,14 +1823,14 @@ mod tests {
         let mut env = Environment::new();
 
         // Define rule: (= (f) (+ 2 3))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![MettaValue::Atom("f".to_string())]),
-            rhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![MettaValue::Atom("f".to_string())]),
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("+".to_string()),
                 MettaValue::Long(2),
                 MettaValue::Long(3),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Evaluate (f)
@@ -1445,14 +1845,14 @@ mod tests {
         let mut env = Environment::new();
 
         // (= (add3 $a $b $c) (+ $a (+ $b $c)))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("add3".to_string()),
                 MettaValue::Atom("$a".to_string()),
                 MettaValue::Atom("$b".to_string()),
                 MettaValue::Atom("$c".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("+".to_string()),
                 MettaValue::Atom("$a".to_string()),
                 MettaValue::SExpr(vec![
@@ -1461,7 +1861,7 @@ mod tests {
                     MettaValue::Atom("$c".to_string()),
                 ]),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // (add3 10 20 30) = 60
@@ -1481,8 +1881,8 @@ mod tests {
         let mut env = Environment::new();
 
         // (= (eval-pair (pair $x $y)) (+ $x $y))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("eval-pair".to_string()),
                 MettaValue::SExpr(vec![
                     MettaValue::Atom("pair".to_string()),
@@ -1490,12 +1890,12 @@ mod tests {
                     MettaValue::Atom("$y".to_string()),
                 ]),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("+".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Atom("$y".to_string()),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // (eval-pair (pair 5 7)) = 12
@@ -1524,6 +1924,184 @@ mod tests {
         assert!(bindings.is_empty());
     }
 
+    #[test]
+    fn test_interrupt_flag_stops_evaluation() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::time::{Duration, Instant};
+
+        // (burn 60) makes 2^60 calls: it only finishes by being interrupted
+        let state = crate::backend::compile::compile(
+            "(= (burn $n) (if (== $n 0) 0 (+ (burn (- $n 1)) (burn (- $n 1)))))\n!(burn 60)",
+        )
+        .unwrap();
+        let mut env = state.environment;
+        let flag = Arc::new(AtomicBool::new(false));
+        env.set_interrupt_flag(flag.clone());
+
+        let mut source = state.source.into_iter();
+        let (_, env) = eval(source.next().unwrap(), env);
+
+        let setter = {
+            let flag = flag.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(50));
+                flag.store(true, Ordering::Relaxed);
+            })
+        };
+        let start = Instant::now();
+        let (results, env) = eval(source.next().unwrap(), env);
+        setter.join().unwrap();
+
+        assert!(start.elapsed() < Duration::from_secs(10));
+        assert!(
+            matches!(results.as_slice(), [MettaValue::Error(msg, _)] if msg == "Interrupted"),
+            "{:?}",
+            results
+        );
+
+        // Clearing the flag lets evaluation continue in the same environment
+        flag.store(false, Ordering::Relaxed);
+        let (results, _) = eval(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("burn".to_string()),
+                MettaValue::Long(2),
+            ]),
+            env,
+        );
+        assert_eq!(results, vec![MettaValue::Long(0)]);
+    }
+
+    #[test]
+    fn test_every_checked_special_form_has_an_arity() {
+        // Each `require_args_with_usage!` call names its form first
+        let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("src/backend/eval");
+        let call = "require_args_with_usage!(";
+        let mut ops = Vec::new();
+        for entry in std::fs::read_dir(dir).unwrap() {
+            let src = std::fs::read_to_string(entry.unwrap().path()).unwrap();
+            for (i, _) in src.match_indices(call) {
+                let rest = src[i + call.len()..].trim_start();
+                if let Some(op) = rest.strip_prefix('"').and_then(|r| r.split('"').next()) {
+                    ops.push(op.to_string());
+                }
+            }
+        }
+
+        assert!(ops.len() > 40, "{:?}", ops);
+        for op in &ops {
+            assert!(special_form_arity(op).is_some(), "{} has no arity", op);
+        }
+        assert_eq!(special_form_arity("if-empty"), Some(3));
+        assert_eq!(special_form_arity("no-such-form"), None);
+    }
+
+    #[test]
+    fn test_rule_trace_reports_fired_rule() {
+        use std::sync::Mutex;
+
+        let mut env = Environment::new();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink_lines = lines.clone();
+        env.set_rule_trace(Some(Arc::new(move |line: &str| {
+            sink_lines.lock().unwrap().push(line.to_string())
+        })));
+
+        let (all, _) = crate::backend::run::eval_forms(
+            "(= (area (square $s)) (* $s $s))\n(= (area (rect $w $h)) (* $w $h))\n\
+             !(area (rect 2 3))\n!(area (square 4))",
+            env,
+        );
+        assert_eq!(all[2], vec![MettaValue::Long(6)]);
+        assert_eq!(all[3], vec![MettaValue::Long(16)]);
+
+        // Only the user rules are reported, not the builtin `*`
+        assert_eq!(
+            *lines.lock().unwrap(),
+            vec![
+                "(area (rect 2 3)) matched (= (area (rect $w $h)) (* $w $h)) with (($w 2) ($h 3))"
+                    .to_string(),
+                "(area (square 4)) matched (= (area (square $s)) (* $s $s)) with (($s 4))"
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rule_trace_evaluates_guards_once() {
+        use std::sync::Mutex;
+
+        let mut env = Environment::new();
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink_lines = lines.clone();
+        env.set_rule_trace(Some(Arc::new(move |line: &str| {
+            sink_lines.lock().unwrap().push(line.to_string())
+        })));
+
+        // The guard counts its own evaluations
+        let (all, _) = crate::backend::run::eval_forms(
+            r#"
+            !(bind! &count (new-state 0))
+            (= (bump) (let $_ (change-state! &count (+ (get-state &count) 1)) True))
+            (= (pick $x) guarded where (bump))
+            !(pick 1)
+            !(get-state &count)
+            "#,
+            env,
+        );
+        assert_eq!(all[3], vec![MettaValue::Atom("guarded".to_string())]);
+        assert_eq!(all[4], vec![MettaValue::Long(1)]);
+
+        let lines = lines.lock().unwrap();
+        let fired = |call: &str| lines.iter().filter(|l| l.starts_with(call)).count();
+        assert_eq!(fired("(pick 1) matched"), 1, "{:?}", lines);
+        assert_eq!(fired("(bump) matched"), 1, "{:?}", lines);
+    }
+
+    #[test]
+    fn test_unbounded_recursion_is_recoverable_error() {
+        // Each level nests another eval through `if`, with no base case
+        let mut env = Environment::new();
+        env.set_max_call_depth(32);
+
+        let (all, _) = crate::backend::run::eval_forms(
+            "(= (deep $n) (if True (+ 1 (deep $n)) 0))\n!(deep 1)\n!(+ 1 2)",
+            env,
+        );
+
+        match all[1].as_slice() {
+            [MettaValue::Error(msg, _)] => {
+                assert!(msg.contains("Maximum call depth (32)"), "{}", msg)
+            }
+            other => panic!("Expected a call depth error, got {:?}", other),
+        }
+        // Evaluation keeps working afterwards
+        assert_eq!(all[2], vec![MettaValue::Long(3)]);
+    }
+
+    #[test]
+    fn test_repeated_wildcards_are_independent() {
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("f".to_string()),
+            MettaValue::Long(1),
+            MettaValue::Long(2),
+        ]);
+        for wildcard in ["_", "$_"] {
+            let pattern = MettaValue::SExpr(vec![
+                MettaValue::Atom("f".to_string()),
+                MettaValue::Atom(wildcard.to_string()),
+                MettaValue::Atom(wildcard.to_string()),
+            ]);
+            let bindings = pattern_match(&pattern, &value);
+            assert!(
+                bindings.is_some(),
+                "({} {}) should match (f 1 2)",
+                wildcard,
+                wildcard
+            );
+            assert!(bindings.unwrap().is_empty());
+        }
+    }
+
     #[test]
     fn test_variable_consistency_in_pattern() {
         // Test that the same variable in a pattern must match the same value
@@ -1556,12 +2134,12 @@ mod tests {
         let mut env = Environment::new();
 
         // (= (abs $x) (if (< $x 0) (- 0 $x) $x))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("abs".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("if".to_string()),
                 MettaValue::SExpr(vec![
                     MettaValue::Atom("<".to_string()),
@@ -1575,7 +2153,7 @@ mod tests {
                 ]),
                 MettaValue::Atom("$x".to_string()),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // abs(-5) = 5
@@ -1653,7 +2231,7 @@ mod tests {
         let (_, new_env) = eval(var1, env.clone());
         assert!(!new_env.has_fact("$x"));
 
-        // Test &variable
+        // Test &token
         let var2 = MettaValue::Atom("&y".to_string());
         let (_, new_env) = eval(var2, env.clone());
         assert!(!new_env.has_fact("&y"));
@@ -2023,6 +2601,56 @@ mod tests {
         assert_eq!(results[0], MettaValue::Long(7));
     }
 
+    #[test]
+    fn test_flatten_nested_conjunction_results() {
+        let atom = |s: &str| MettaValue::Atom(s.to_string());
+        let results = flatten_nested_conjunction_results(vec![
+            MettaValue::Long(1),
+            MettaValue::SExpr(vec![
+                atom("pair"),
+                MettaValue::Conjunction(vec![atom("a"), atom("b")]),
+                MettaValue::Conjunction(vec![atom("c"), atom("d")]),
+            ]),
+            MettaValue::SExpr(vec![atom("none"), MettaValue::Conjunction(vec![])]),
+        ]);
+        let pair = |x: &str, y: &str| MettaValue::SExpr(vec![atom("pair"), atom(x), atom(y)]);
+        assert_eq!(
+            results,
+            vec![
+                MettaValue::Long(1),
+                pair("a", "c"),
+                pair("a", "d"),
+                pair("b", "c"),
+                pair("b", "d"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_flatten_conjunction_results_only_top_level() {
+        let inner = MettaValue::SExpr(vec![
+            MettaValue::Atom("keep".to_string()),
+            MettaValue::Conjunction(vec![MettaValue::Long(9)]),
+        ]);
+        let results = flatten_conjunction_results(vec![
+            MettaValue::Long(1),
+            MettaValue::Conjunction(vec![
+                MettaValue::Long(2),
+                MettaValue::Conjunction(vec![MettaValue::Long(3)]),
+            ]),
+            inner.clone(),
+        ]);
+        assert_eq!(
+            results,
+            vec![
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+                MettaValue::Long(3),
+                inner
+            ]
+        );
+    }
+
     // ========================================================================
     // Fuzzy Matching / Typo Detection Tests
     // ========================================================================
@@ -2059,17 +2687,17 @@ mod tests {
         let mut env = Environment::new();
 
         // Add a rule for "fibonacci"
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("fibonacci".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("*".to_string()),
                 MettaValue::Atom("$n".to_string()),
                 MettaValue::Atom("$n".to_string()),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Try to call "fibonaci" (misspelled - missing 'n')
@@ -2137,4 +2765,46 @@ mod tests {
             "Short symbols should not be flagged as typos"
         );
     }
+
+    #[test]
+    fn test_metrics_for_known_program() {
+        let mut env = Environment::new();
+        for n in [1, 2] {
+            env.add_rule(Rule::new(
+                MettaValue::SExpr(vec![MettaValue::Atom("pick".to_string())]),
+                MettaValue::Long(n),
+            ));
+        }
+        env.set_collect_metrics(true);
+
+        // (pick): one step for the expression, one for its head atom, then one per
+        // matching rule RHS; the single dispatch matches two rules, a choice point
+        let (results, env) = eval(
+            MettaValue::SExpr(vec![MettaValue::Atom("pick".to_string())]),
+            env,
+        );
+        assert_eq!(results.len(), 2);
+        assert!(results.contains(&MettaValue::Long(1)));
+        assert!(results.contains(&MettaValue::Long(2)));
+
+        let metrics = env.metrics();
+        assert_eq!(metrics.steps, 4);
+        assert_eq!(metrics.choice_points, 1);
+        assert_eq!(metrics.rule_dispatches, 1);
+        assert_eq!(metrics.max_depth, 1);
+    }
+
+    #[test]
+    fn test_metrics_disabled_by_default() {
+        let env = Environment::new();
+        let (_, env) = eval(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("+".to_string()),
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+            ]),
+            env,
+        );
+        assert_eq!(env.metrics(), crate::backend::EvalMetrics::default());
+    }
 }
@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tracing::trace;
+
+use crate::backend::compile::compile;
+use crate::backend::environment::{Environment, ModuleLoadState};
+use crate::backend::models::{EvalResult, MettaValue};
+
+use super::eval;
+use super::space::split_space_ref;
+
+/// Import: load another MeTTa file into the current environment
+/// (import! "path/to/file.metta") or (import! &self module)
+///
+/// Relative paths are resolved against the environment's module path (the directory
+/// of the importing file), falling back to the current working directory. A symbol
+/// without an extension gets `.metta` appended. Every expression of the imported file
+/// is evaluated into the environment, so its rules and facts become available.
+/// Importing the same file twice is a no-op; importing a file that is still being
+/// loaded is reported as a circular import.
+pub(super) fn eval_import(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_import", ?items);
+
+    let args = split_space_ref(&items[1..]);
+    let target = match args.as_slice() {
+        [MettaValue::Atom(amp), MettaValue::Atom(space), target]
+            if amp == "&" && space == "self" =>
+        {
+            target
+        }
+        [target] => target,
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "import! requires a module path, got {} argument(s). Usage: (import! \"file.metta\") or (import! &self module)",
+                    items.len().saturating_sub(1)
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let name = match target {
+        MettaValue::String(s) | MettaValue::Atom(s) => s.clone(),
+        other => {
+            let err = MettaValue::Error(
+                format!(
+                    "import! expects a file path as a string or symbol, got {}",
+                    super::friendly_value_repr(other)
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let path = resolve_module_path(&name, &env);
+    let canonical = match fs::canonicalize(&path) {
+        Ok(p) => p,
+        Err(e) => {
+            let err = MettaValue::Error(
+                format!("import!: cannot find module '{}': {}", path.display(), e),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    match env.module_load_state(&canonical) {
+        Some(ModuleLoadState::Loaded) => return (vec![], env),
+        Some(ModuleLoadState::Loading) => {
+            let err = MettaValue::Error(
+                format!("import!: circular import of '{}'", canonical.display()),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+        None => {}
+    }
+
+    let source = match fs::read_to_string(&canonical) {
+        Ok(src) => src,
+        Err(e) => {
+            let err = MettaValue::Error(
+                format!("import!: cannot read '{}': {}", canonical.display(), e),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let state = match compile(&source) {
+        Ok(state) => state,
+        Err(e) => {
+            let err = MettaValue::Error(
+                format!(
+                    "import!: failed to compile '{}': {}",
+                    canonical.display(),
+                    e
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    // Evaluate the module with its own directory as the module path so that
+    // nested imports resolve relative to the imported file
+    let mut env = env;
+    let previous_path = env.module_path();
+    env.set_module_load_state(canonical.clone(), ModuleLoadState::Loading);
+    env.set_module_path(canonical.parent().map(|p| p.to_path_buf()));
+
+    let mut first_error = None;
+    for expr in state.source {
+        let (results, new_env) = eval(expr, env);
+        env = new_env;
+        if first_error.is_none() {
+            first_error = results
+                .into_iter()
+                .find(|r| matches!(r, MettaValue::Error(_, _)));
+        }
+    }
+
+    env.set_module_path(previous_path);
+    env.set_module_load_state(canonical, ModuleLoadState::Loaded);
+
+    match first_error {
+        Some(err) => (vec![err], env),
+        None => (vec![], env),
+    }
+}
+
+/// Resolve an `import!` target to a file path
+fn resolve_module_path(name: &str, env: &Environment) -> PathBuf {
+    let mut path = PathBuf::from(name);
+    if path.extension().is_none() {
+        path.set_extension("metta");
+    }
+    if path.is_relative() {
+        if let Some(dir) = env.module_path() {
+            return dir.join(path);
+        }
+    }
+    path
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    /// Create a fresh scratch directory for module files
+    fn scratch_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("mettatron-import-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn run(src: &str, dir: &Path) -> (Vec<MettaValue>, Environment) {
+        let mut env = Environment::new();
+        env.set_module_path(Some(dir.to_path_buf()));
+        let (forms, env) = crate::backend::run::eval_forms(src, env);
+        (forms.concat(), env)
+    }
+
+    #[test]
+    fn test_import_uses_rules_from_other_file() {
+        let dir = scratch_dir("basic");
+        fs::write(dir.join("lib.metta"), "(= (double $x) (* $x 2))\n").unwrap();
+
+        let (results, _) = run("!(import! \"lib.metta\")\n!(double 21)", &dir);
+        assert_eq!(results, vec![MettaValue::Long(42)]);
+
+        // A bare symbol gets the .metta extension
+        let (results, _) = run("!(import! &self lib)\n!(double 4)", &dir);
+        assert_eq!(results, vec![MettaValue::Long(8)]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_twice_is_noop() {
+        let dir = scratch_dir("twice");
+        fs::write(dir.join("lib.metta"), "(= (one) 1)\n").unwrap();
+
+        let (results, _) = run("!(import! lib)\n!(import! lib)\n!(one)", &dir);
+        // Without dedup the rule would be defined twice and (one) would yield two results
+        assert_eq!(results, vec![MettaValue::Long(1)]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_circular_is_error() {
+        let dir = scratch_dir("circular");
+        fs::write(dir.join("a.metta"), "!(import! b)\n").unwrap();
+        fs::write(dir.join("b.metta"), "!(import! a)\n").unwrap();
+
+        let (results, _) = run("!(import! a)", &dir);
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            MettaValue::Error(msg, _) => assert!(msg.contains("circular import"), "{}", msg),
+            other => panic!("Expected Error, got {:?}", other),
+        }
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_import_missing_file() {
+        let dir = scratch_dir("missing");
+        let (results, _) = run("!(import! nowhere)", &dir);
+        assert_eq!(results.len(), 1);
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("cannot find module"))
+        );
+
+        fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -307,7 +307,7 @@ fn eval_consequent_conjunction_with_bindings(
 /// Check if a MettaValue contains any variables
 fn has_variables(value: &MettaValue) -> bool {
     match value {
-        MettaValue::Atom(s) => s.starts_with('$') || s.starts_with('&') || s.starts_with('\''),
+        MettaValue::Atom(s) => s.starts_with('$') || s.starts_with('\''),
         MettaValue::SExpr(items) => items.iter().any(has_variables),
         MettaValue::Conjunction(goals) => goals.iter().any(has_variables),
         MettaValue::Error(_, details) => has_variables(details),
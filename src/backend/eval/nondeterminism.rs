@@ -0,0 +1,764 @@
+use std::sync::Arc;
+use tracing::trace;
+
+use crate::backend::environment::Environment;
+use crate::backend::models::{Bindings, EvalResult, MettaValue};
+
+use super::evaluation::eval_depth;
+use super::space::eval_match_with_bindings;
+use super::{call_depth, eval, ResultLimitGuard};
+
+/// Superpose: turn a list of alternatives into nondeterministic results
+/// (superpose (a b c)) -> a, b, c
+///
+/// Each element is evaluated and all of its results are returned, so
+/// `(superpose ((+ 1 1) 3))` yields 2 and 3. The empty list yields no results.
+/// Each alternative sees state cells as they were before the superpose, not as an
+/// earlier alternative left them; afterwards every alternative's changes are kept.
+pub(super) fn eval_superpose(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_superpose", ?items);
+    require_args_with_usage!("superpose", items, env, "(superpose (alt1 alt2 ...))");
+
+    let alternatives = match &items[1] {
+        MettaValue::SExpr(alternatives) => alternatives.clone(),
+        MettaValue::Nil => Vec::new(),
+        other => {
+            let err = MettaValue::Error(
+                format!(
+                    "superpose expects an expression of alternatives, got {}",
+                    super::friendly_value_repr(other)
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    let mut state_fork = (alternatives.len() > 1).then(|| env.fork_states());
+    let mut all_results = Vec::new();
+    let mut current_env = env;
+    for alternative in alternatives {
+        let (results, new_env) = eval(alternative, current_env);
+        current_env = new_env;
+        if let Some(fork) = state_fork.as_mut() {
+            current_env.end_state_branch(fork);
+        }
+        all_results.extend(results);
+    }
+    if let Some(fork) = state_fork {
+        current_env.join_state_fork(fork);
+    }
+
+    (all_results, current_env)
+}
+
+/// List-to-nondet: turn the elements of an evaluated expression into results
+/// (list-to-nondet expr) -> elem1, elem2, ...
+///
+/// Unlike `superpose`, the argument is evaluated first and its elements are returned
+/// as they are, so `(list-to-nondet (nondet-to-list x))` yields the results of `x`
+/// again. A nondeterministic argument contributes the elements of each of its results.
+pub(super) fn eval_list_to_nondet(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_list_to_nondet", ?items);
+    require_args_with_usage!("list-to-nondet", items, env, "(list-to-nondet expr)");
+
+    let (lists, env) = eval(items[1].clone(), env);
+    let mut all_results = Vec::new();
+    for list in lists {
+        match list {
+            MettaValue::SExpr(elements) => all_results.extend(elements),
+            MettaValue::Nil => {}
+            err @ MettaValue::Error(_, _) => return (vec![err], env),
+            other => {
+                let err = MettaValue::Error(
+                    format!(
+                        "list-to-nondet expects an expression, got {}",
+                        super::friendly_value_repr(&other)
+                    ),
+                    Arc::new(MettaValue::SExpr(items.clone())),
+                );
+                return (vec![err], env);
+            }
+        }
+    }
+    (all_results, env)
+}
+
+/// Empty: produce no results
+/// (empty) - the failing branch of a nondeterministic computation
+pub(super) fn eval_empty(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_empty", ?items);
+    (vec![], env)
+}
+
+/// Nop: (nop) or (nop expr) -> ()
+/// Evaluates expr, if given, for its side effects and returns unit. Unlike `empty`,
+/// which produces no results, nop always produces exactly one result; an error from
+/// expr is returned instead.
+pub(super) fn eval_nop(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_nop", ?items);
+    let env = match items.get(1) {
+        Some(expr) if items.len() == 2 => {
+            let (results, env) = eval(expr.clone(), env);
+            if let Some(err) = results
+                .into_iter()
+                .find(|r| matches!(r, MettaValue::Error(_, _)))
+            {
+                return (vec![err], env);
+            }
+            env
+        }
+        Some(_) => {
+            let err = MettaValue::Error(
+                format!(
+                    "nop takes at most 1 argument, got {}. Usage: (nop) or (nop expr)",
+                    items.len() - 1
+                ),
+                Arc::new(MettaValue::SExpr(items)),
+            );
+            return (vec![err], env);
+        }
+        None => env,
+    };
+    (vec![MettaValue::SExpr(vec![])], env)
+}
+
+/// Collapse: gather all results of an expression into a single expression
+/// (collapse expr) -> (result1 result2 ...)
+/// `nondet-to-list` is another name for it, the inverse of `list-to-nondet`.
+///
+/// The results are always wrapped, so a deterministic expression collapses to a
+/// one-element expression: `(collapse (+ 1 2))` is `(3)`, never the bare `3`. An
+/// expression with no results collapses to `()`, so `(collapse (superpose ()))`
+/// is `()`. An error result is returned as is, as for `collapse-bind`.
+///
+/// With `Environment::set_max_collapse_results`, an expression with more results
+/// than the limit collapses to an error instead. The limit is checked while the
+/// results are gathered, so an endless search stops once it is exceeded.
+///
+/// Collapsing the same pure expression again reuses the earlier results. Purity is
+/// observed rather than predicted: an evaluation is pure when it recorded no side
+/// effect (space or rule changes, state cells, random numbers), and any later side
+/// effect invalidates the cached results. Results are only reused with the same call
+/// and `eval` depth left, and never while rule tracing is on.
+pub(super) fn eval_collapse(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_collapse", ?items);
+    require_args_with_usage!("collapse", items, env, "(collapse expr)");
+
+    let expr = &items[1];
+    let depth = (call_depth(), eval_depth());
+    let (results, env) = match env.cached_collapse(expr, depth) {
+        Some(results) => {
+            trace!(target: "mettatron::eval::eval_collapse", ?expr, "collapse cache hit");
+            (results, env)
+        }
+        None => {
+            let effects_before = env.effect_count();
+            let limit = ResultLimitGuard::enter(env.max_collapse_results());
+            let (results, env) = eval(expr.clone(), env);
+            if limit.exceeded() {
+                let err = result_limit_error("collapse", None, &items, &env);
+                return (vec![err], env);
+            }
+            drop(limit);
+            if let Some(err) = results
+                .iter()
+                .find(|r| matches!(r, MettaValue::Error(_, _)))
+            {
+                return (vec![err.clone()], env);
+            }
+            env.cache_collapse(expr, depth, effects_before, &results);
+            (results, env)
+        }
+    };
+
+    // Checked on cached results too: the limit may have been lowered since
+    if env
+        .max_collapse_results()
+        .is_some_and(|limit| results.len() > limit)
+    {
+        let err = result_limit_error("collapse", Some(results.len()), &items, &env);
+        return (vec![err], env);
+    }
+
+    (vec![MettaValue::SExpr(results)], env)
+}
+
+/// Collapse-bind: collapse all results of an expression together with their bindings
+/// (collapse-bind expr) -> ((result1 bindings1) (result2 bindings2) ...)
+///
+/// Each bindings entry is an S-expression of `($var value)` pairs recording the variable
+/// assignments that produced the result. For a `match` these are the pattern variables
+/// bound by each matching atom, whichever space it searches and however many templates
+/// it has; other expressions bind nothing, so their results are paired with `()`.
+///
+/// The result limit of `Environment::set_max_collapse_results` applies as for
+/// `collapse`.
+pub(super) fn eval_collapse_bind(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_collapse_bind", ?items);
+    require_args_with_usage!("collapse-bind", items, env, "(collapse-bind expr)");
+
+    let limit = ResultLimitGuard::enter(env.max_collapse_results());
+    let (pairs, env) = match &items[1] {
+        MettaValue::SExpr(match_items) if is_match(match_items) => {
+            match eval_match_with_bindings(match_items, env) {
+                (Ok(pairs), env) => (pairs, env),
+                (Err(err), env) => return (vec![err], env),
+            }
+        }
+        expr => {
+            let (results, env) = eval(expr.clone(), env);
+            if let Some(err) = results
+                .iter()
+                .find(|r| matches!(r, MettaValue::Error(_, _)))
+            {
+                return (vec![err.clone()], env);
+            }
+            let pairs = results
+                .into_iter()
+                .map(|result| (result, Bindings::new()))
+                .collect();
+            (pairs, env)
+        }
+    };
+    if limit.exceeded() {
+        let err = result_limit_error("collapse-bind", None, &items, &env);
+        return (vec![err], env);
+    }
+    drop(limit);
+    if env
+        .max_collapse_results()
+        .is_some_and(|limit| pairs.len() > limit)
+    {
+        let err = result_limit_error("collapse-bind", Some(pairs.len()), &items, &env);
+        return (vec![err], env);
+    }
+
+    let collapsed = pairs
+        .into_iter()
+        .map(|(result, bindings)| MettaValue::SExpr(vec![result, bindings.to_metta_value()]))
+        .collect();
+
+    (vec![MettaValue::SExpr(collapsed)], env)
+}
+
+/// The error for a collapse that gathered more results than the limit; `count` is
+/// None when the search was cut short
+fn result_limit_error(
+    op: &str,
+    count: Option<usize>,
+    items: &[MettaValue],
+    env: &Environment,
+) -> MettaValue {
+    let limit = env.max_collapse_results().unwrap_or_default();
+    let msg = match count {
+        Some(count) => format!("{}: {} results exceed the limit of {}", op, count, limit),
+        None => format!(
+            "{}: search stopped after exceeding the limit of {} results",
+            op, limit
+        ),
+    };
+    MettaValue::Error(msg, Arc::new(MettaValue::SExpr(items.to_vec())))
+}
+
+/// Check for a `match` expression
+fn is_match(items: &[MettaValue]) -> bool {
+    matches!(items.first(), Some(MettaValue::Atom(head)) if head == "match")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::compile::compile;
+    use crate::backend::run::{eval_last, eval_last_in};
+    use crate::backend::EvalMetrics;
+
+    fn pair(result: MettaValue, bindings: Vec<(&str, MettaValue)>) -> MettaValue {
+        MettaValue::SExpr(vec![
+            result,
+            MettaValue::SExpr(
+                bindings
+                    .into_iter()
+                    .map(|(name, value)| {
+                        MettaValue::SExpr(vec![MettaValue::Atom(name.to_string()), value])
+                    })
+                    .collect(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_superpose_yields_each_alternative() {
+        let results = eval_last("!(superpose (1 (+ 1 1) c))");
+        assert_eq!(
+            results,
+            vec![
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+                MettaValue::Atom("c".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collapse_result_limit() {
+        let src = r#"
+            (= (digit) (superpose (0 1 2 3 4 5 6 7 8 9)))
+            (= (pair) ((digit) (digit)))
+            !(collapse (pair))
+        "#;
+        let mut env = Environment::new();
+        env.set_max_collapse_results(Some(50));
+        let (results, env) = eval_last_in(src, env);
+        assert!(
+            matches!(&results[..], [MettaValue::Error(msg, _)] if msg.contains("100 results exceed the limit of 50")),
+            "{:?}",
+            results
+        );
+
+        // Within the limit the results are gathered as usual
+        let collapse_digits = MettaValue::SExpr(vec![
+            MettaValue::Atom("collapse".to_string()),
+            MettaValue::SExpr(vec![MettaValue::Atom("digit".to_string())]),
+        ]);
+        let (results, mut env) = eval(collapse_digits.clone(), env);
+        assert!(matches!(&results[..], [MettaValue::SExpr(digits)] if digits.len() == 10));
+
+        // A lower limit applies to results collapsed before it was set
+        env.set_max_collapse_results(Some(5));
+        let (results, _) = eval(collapse_digits, env);
+        assert!(
+            matches!(&results[..], [MettaValue::Error(msg, _)] if msg.contains("10 results exceed the limit of 5")),
+            "{:?}",
+            results
+        );
+    }
+
+    #[test]
+    fn test_result_limit_stops_unbounded_search() {
+        // Every tree is a result, so the search never finishes on its own
+        let src = r#"
+            (= (tree) leaf)
+            (= (tree) (node (tree) (tree)))
+            !(collapse (tree))
+            !(collapse-bind (tree))
+            !(collapse (superpose (1 2 3)))
+        "#;
+        let mut env = Environment::new();
+        env.set_max_collapse_results(Some(10));
+        let (outputs, _) = crate::backend::run::eval_forms(src, env);
+
+        for (op, results) in [("collapse", &outputs[2]), ("collapse-bind", &outputs[3])] {
+            let expected = format!("{}: search stopped after exceeding the limit of 10", op);
+            assert!(
+                matches!(&results[..], [MettaValue::Error(msg, _)] if msg.starts_with(&expected)),
+                "{:?}",
+                results
+            );
+        }
+
+        // A later collapse starts afresh
+        assert!(matches!(&outputs[4][..], [MettaValue::SExpr(items)] if items.len() == 3));
+    }
+
+    #[test]
+    fn test_collapse_bind_result_limit() {
+        let mut env = Environment::new();
+        env.set_max_collapse_results(Some(2));
+        let (results, _) = eval_last_in("!(collapse-bind (superpose (1 2 3)))", env);
+        assert!(
+            matches!(&results[..], [MettaValue::Error(msg, _)] if msg.contains("3 results exceed the limit of 2")),
+            "{:?}",
+            results
+        );
+    }
+
+    #[test]
+    fn test_superpose_evaluates_rule_calls() {
+        // Each call is evaluated, and a nondeterministic call contributes all its results
+        let results = eval_last(
+            r#"
+            (= (f $x) (* $x 10))
+            (= (g $x) $x)
+            (= (g $x) (+ $x 100))
+            !(superpose ((f 1) (f 2) (g 3)))
+        "#,
+        );
+        assert_eq!(
+            results,
+            vec![
+                MettaValue::Long(10),
+                MettaValue::Long(20),
+                MettaValue::Long(3),
+                MettaValue::Long(103),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_has_no_results() {
+        assert!(eval_last("!(empty)").is_empty());
+    }
+
+    #[test]
+    fn test_nop_is_unit_and_empty_is_no_result() {
+        let unit = vec![MettaValue::SExpr(vec![])];
+        assert_eq!(eval_last("!(nop)"), unit);
+        assert_eq!(eval_last("!(nop (+ 1 2))"), unit);
+
+        // Unit is one result, Empty contributes none
+        let results = eval_last("!(collapse (superpose ((nop) (empty) (nop))))");
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].to_string(), "(() ())");
+
+        // Side effects of the argument are kept
+        assert_eq!(
+            eval_last("!(let $s (new-state 1) (let $_ (nop (change-state! $s 2)) (get-state $s)))"),
+            vec![MettaValue::Long(2)]
+        );
+    }
+
+    #[test]
+    fn test_superpose_empty_has_no_results() {
+        assert!(eval_last("!(superpose ())").is_empty());
+    }
+
+    #[test]
+    fn test_collapse_superpose_empty_is_unit() {
+        assert_eq!(
+            eval_last("!(collapse (superpose ()))"),
+            vec![MettaValue::SExpr(vec![])]
+        );
+    }
+
+    #[test]
+    fn test_collapse_superpose_gathers_alternatives() {
+        assert_eq!(
+            eval_last("!(collapse (superpose (1 (+ 1 1))))"),
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Long(1),
+                MettaValue::Long(2)
+            ])]
+        );
+        // A single alternative still forks into exactly one result
+        assert_eq!(eval_last("!(superpose (7))"), vec![MettaValue::Long(7)]);
+    }
+
+    #[test]
+    fn test_collapse_deterministic_single_result_is_wrapped() {
+        let wrapped = |value: MettaValue| vec![MettaValue::SExpr(vec![value])];
+
+        assert_eq!(
+            eval_last("!(collapse (+ 1 2))"),
+            wrapped(MettaValue::Long(3))
+        );
+        assert_eq!(eval_last("!(collapse 5)"), wrapped(MettaValue::Long(5)));
+        // A single result that is itself an expression is not spliced
+        assert_eq!(
+            eval_last("!(collapse (quote (a b)))"),
+            wrapped(MettaValue::SExpr(vec![
+                MettaValue::Atom("a".to_string()),
+                MettaValue::Atom("b".to_string()),
+            ]))
+        );
+        assert_eq!(
+            eval_last("(= (answer) 42)\n!(collapse (answer))"),
+            wrapped(MettaValue::Long(42))
+        );
+    }
+
+    #[test]
+    fn test_collapse_no_results_is_unit() {
+        let unit = vec![MettaValue::SExpr(vec![])];
+        assert_eq!(eval_last("!(collapse (empty))"), unit);
+        assert_eq!(
+            eval_last("!(collapse (match & self (missing $x) $x))"),
+            unit
+        );
+    }
+
+    #[test]
+    fn test_collapse_multiple_results() {
+        let results = eval_last("(= (coin) heads)\n(= (coin) tails)\n!(collapse (coin))");
+        assert_eq!(results.len(), 1);
+        let MettaValue::SExpr(items) = &results[0] else {
+            panic!("expected an expression, got {:?}", results[0]);
+        };
+        assert_eq!(items.len(), 2);
+        assert!(items.contains(&MettaValue::Atom("heads".to_string())));
+        assert!(items.contains(&MettaValue::Atom("tails".to_string())));
+    }
+
+    #[test]
+    fn test_collapse_order_follows_rule_definition_order() {
+        let bodies = ["0.75", "(* 0.5 0.5)", "1.5", "(+ 0.125 0.125)"];
+        let values = [0.75, 0.25, 1.5, 0.25];
+        // Trie order is the same for both programs, so at least one of the two
+        // definition orders differs from it
+        for reversed in [false, true] {
+            let mut order: Vec<usize> = (0..bodies.len()).collect();
+            if reversed {
+                order.reverse();
+            }
+            let mut program = String::new();
+            for &i in &order {
+                program.push_str(&format!("(= (sample) {})\n", bodies[i]));
+            }
+            program.push_str("!(collapse (sample))");
+            let expected = vec![MettaValue::SExpr(
+                order
+                    .iter()
+                    .map(|&i| MettaValue::Float(values[i]))
+                    .collect(),
+            )];
+            for _ in 0..10 {
+                assert_eq!(eval_last(&program), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nondet_to_list_and_back_round_trips() {
+        let program = "(= (coin) heads)\n(= (coin) tails)\n(= (coin) edge)\n";
+        let direct = eval_last(&format!("{}!(coin)", program));
+        let round_trip = eval_last(&format!(
+            "{}!(list-to-nondet (nondet-to-list (coin)))",
+            program
+        ));
+        assert_eq!(direct.len(), 3);
+        assert_eq!(round_trip, direct);
+
+        assert_eq!(
+            eval_last("!(nondet-to-list (superpose (1 2)))"),
+            eval_last("!(collapse (superpose (1 2)))")
+        );
+        // Elements come back as they are, where superpose would evaluate them
+        assert_eq!(
+            eval_last("!(list-to-nondet (quote (a (+ 1 1))))"),
+            vec![
+                MettaValue::Atom("a".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("+".to_string()),
+                    MettaValue::Long(1),
+                    MettaValue::Long(1),
+                ]),
+            ]
+        );
+        assert_eq!(
+            eval_last("(= (items) (x y))\n!(list-to-nondet (items))"),
+            vec![
+                MettaValue::Atom("x".to_string()),
+                MettaValue::Atom("y".to_string())
+            ]
+        );
+        assert!(eval_last("!(list-to-nondet ())").is_empty());
+        assert!(matches!(
+            eval_last("!(list-to-nondet 5)").as_slice(),
+            [MettaValue::Error(_, _)]
+        ));
+    }
+
+    #[test]
+    fn test_collapse_bind_match_shows_distinct_bindings() {
+        let results = eval_last(
+            r#"
+            (color red)
+            (color green)
+            !(collapse-bind (match & self (color $c) (is $c)))
+            "#,
+        );
+        assert_eq!(results.len(), 1);
+
+        let mut pairs = match &results[0] {
+            MettaValue::SExpr(pairs) => pairs.clone(),
+            other => panic!("Expected collapsed pairs, got {:?}", other),
+        };
+        pairs.sort_by_key(|p| format!("{:?}", p));
+
+        let is = |c: &str| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("is".to_string()),
+                MettaValue::Atom(c.to_string()),
+            ])
+        };
+        assert_eq!(
+            pairs,
+            vec![
+                pair(
+                    is("green"),
+                    vec![("$c", MettaValue::Atom("green".to_string()))]
+                ),
+                pair(is("red"), vec![("$c", MettaValue::Atom("red".to_string()))]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_collapse_bind_match_forms() {
+        // A quoted template is instantiated as data, as by match itself
+        let results = eval_last(
+            r#"
+            (val 1)
+            (= (result $x) never)
+            !(collapse-bind (match & self (val $x) (quote (result $x))))
+            "#,
+        );
+        let result_1 = MettaValue::SExpr(vec![
+            MettaValue::Atom("result".to_string()),
+            MettaValue::Long(1),
+        ]);
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![pair(
+                result_1,
+                vec![("$x", MettaValue::Long(1))]
+            )])]
+        );
+
+        // A space created by new-space
+        let results = eval_last(
+            r#"
+            !(let $s (new-space)
+                (let $_ (add-atom $s (val 2))
+                    (collapse-bind (match $s (val $x) $x))))
+            "#,
+        );
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![pair(
+                MettaValue::Long(2),
+                vec![("$x", MettaValue::Long(2))]
+            )])]
+        );
+
+        // An invalid match gives match's own error
+        assert!(matches!(
+            eval_last("!(collapse-bind (match & other (val $x) $x))").as_slice(),
+            [MettaValue::Error(msg, _)] if msg.contains("only supports 'self'")
+        ));
+    }
+
+    #[test]
+    fn test_collapse_bind_plain_expression_has_empty_bindings() {
+        let results = eval_last("!(collapse-bind (+ 1 2))");
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![pair(MettaValue::Long(3), vec![])])]
+        );
+    }
+
+    #[test]
+    fn test_collapse_bind_no_results() {
+        let results = eval_last("!(collapse-bind (match & self (missing $x) $x))");
+        assert_eq!(results, vec![MettaValue::SExpr(vec![])]);
+    }
+
+    /// Evaluate each source in `steps` in turn with metrics on; returns the results of
+    /// each step and the metrics collected while evaluating it
+    fn run_with_metrics(steps: &[&str]) -> Vec<(Vec<MettaValue>, EvalMetrics)> {
+        let mut env = Environment::new();
+        env.set_collect_metrics(true);
+        let mut outputs = vec![];
+        for src in steps {
+            env.reset_metrics();
+            let (results, new_env) = eval_last_in(src, env);
+            env = new_env;
+            outputs.push((results, env.metrics()));
+        }
+        outputs
+    }
+
+    #[test]
+    fn test_collapse_of_pure_expression_is_cached() {
+        let outputs = run_with_metrics(&[
+            "(= (branch) (superpose ((+ 1 1) (* 2 3) (- 9 4))))",
+            "!(collapse (branch))",
+            "!(collapse (branch))",
+        ]);
+        let (first, first_metrics) = &outputs[1];
+        let (second, second_metrics) = &outputs[2];
+        assert_eq!(first, second);
+        assert_eq!(
+            *first,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Long(2),
+                MettaValue::Long(6),
+                MettaValue::Long(5)
+            ])]
+        );
+        // The branches run only for the first collapse; the second is a cache hit
+        assert_eq!(first_metrics.rule_dispatches, 1);
+        assert_eq!(first_metrics.collapse_cache_hits, 0);
+        assert_eq!(second_metrics.rule_dispatches, 0);
+        assert_eq!(second_metrics.collapse_cache_hits, 1);
+        assert!(second_metrics.steps < first_metrics.steps);
+    }
+
+    #[test]
+    fn test_collapse_cache_is_bypassed_by_rule_trace() {
+        let lines = Arc::new(std::sync::Mutex::new(Vec::<String>::new()));
+        let sink_lines = Arc::clone(&lines);
+        let mut env = Environment::new();
+        env.set_rule_trace(Some(Arc::new(move |line: &str| {
+            sink_lines.lock().unwrap().push(line.to_string())
+        })));
+        crate::backend::run::eval_forms(
+            r#"
+            (= (branch) (superpose (1 2)))
+            !(collapse (branch))
+            !(collapse (branch))
+            "#,
+            env,
+        );
+        // Both collapses apply the rule, so both applications are traced
+        assert_eq!(lines.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_collapse_cache_respects_call_depth() {
+        let state = compile(
+            r#"
+            (= (count $n) (if (> $n 0) (+ 1 (count (- $n 1))) 0))
+            "#,
+        )
+        .unwrap();
+        let expr = compile("(collapse (count 20))").unwrap().source.remove(0);
+        let env = state.environment;
+        let (results, mut env) = eval(expr.clone(), env);
+        assert_eq!(results, vec![MettaValue::SExpr(vec![MettaValue::Long(20)])]);
+
+        // The cached result must not hide the error a lower limit now causes
+        env.set_max_call_depth(3);
+        let (results, _) = eval(expr, env);
+        assert!(
+            matches!(results.as_slice(), [MettaValue::Error(msg, _)] if msg.contains("call depth")),
+            "{:?}",
+            results
+        );
+    }
+
+    #[test]
+    fn test_collapse_cache_skips_impure_and_invalidates() {
+        // Random numbers are side effects: each collapse evaluates again
+        let outputs = run_with_metrics(&[
+            "!(collapse (random-int 0 10))",
+            "!(collapse (random-int 0 10))",
+        ]);
+        assert_eq!(outputs[0].1.steps, outputs[1].1.steps);
+        assert_eq!(outputs[1].1.collapse_cache_hits, 0);
+
+        // Adding a fact after a pure collapse invalidates its cached results
+        let results = eval_last(
+            r#"
+            (= (colors) (match & self (color $c) $c))
+            !(collapse (colors))
+            !(add-atom & self (color red))
+            !(collapse (colors))
+            "#,
+        );
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![MettaValue::Atom("red".to_string())])]
+        );
+    }
+}
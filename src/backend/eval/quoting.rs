@@ -5,7 +5,7 @@ use tracing::trace;
 /// Quote: return argument unevaluated
 pub(super) fn eval_quote(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_quote", ?items);
-    require_args_with_usage!("quote", items, 1, env, "(quote expr)");
+    require_args_with_usage!("quote", items, env, "(quote expr)");
     (vec![items[1].clone()], env)
 }
 
@@ -156,7 +156,7 @@ mod tests {
     fn test_quote_with_variables() {
         let env = Environment::new();
 
-        // Test quoting variables with different prefixes
+        // Test quoting variables with different prefixes, and token references
         let variable_cases = vec![
             "$x",
             "&y",
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+use tracing::trace;
+
+use crate::backend::environment::Environment;
+use crate::backend::models::{EvalResult, MettaValue};
+
+use super::eval;
+
+// Random values differ on every call, so these forms must never be memoized or
+// cached by value: two evaluations of the same `(random-int 0 10)` are independent.
+
+/// Random-int: uniform random integer in the half-open range [lo, hi)
+/// (random-int lo hi)
+///
+/// The sequence is reproducible when the environment's generator was seeded
+/// (the CLI's `--seed N`), and drawn from entropy otherwise.
+pub(super) fn eval_random_int(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_random_int", ?items);
+    require_args_with_usage!("random-int", items, env, "(random-int lo hi)");
+
+    let (lo, env) = match eval_long_arg(&items, 1, env) {
+        Ok(result) => result,
+        Err(result) => return result,
+    };
+    let (hi, env) = match eval_long_arg(&items, 2, env) {
+        Ok(result) => result,
+        Err(result) => return result,
+    };
+
+    if lo >= hi {
+        let err = MettaValue::Error(
+            format!(
+                "random-int: empty range, lo ({}) must be less than hi ({})",
+                lo, hi
+            ),
+            Arc::new(MettaValue::SExpr(items.clone())),
+        );
+        return (vec![err], env);
+    }
+
+    let n = env.random_int(lo, hi);
+    (vec![MettaValue::Long(n)], env)
+}
+
+/// Random-float: uniform random float in [0, 1)
+/// (random-float)
+pub(super) fn eval_random_float(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_random_float", ?items);
+    require_args_with_usage!("random-float", items, env, "(random-float)");
+
+    let x = env.random_float();
+    (vec![MettaValue::Float(x)], env)
+}
+
+/// Evaluate the argument at `index` and require a single integer result
+fn eval_long_arg(
+    items: &[MettaValue],
+    index: usize,
+    env: Environment,
+) -> Result<(i64, Environment), EvalResult> {
+    let (results, env) = eval(items[index].clone(), env);
+    match results.as_slice() {
+        [MettaValue::Long(n)] => Ok((*n, env)),
+        [err @ MettaValue::Error(_, _), ..] => Err((vec![err.clone()], env)),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "{} expects integer bounds, got {}",
+                    super::friendly_value_repr(&items[0]),
+                    results
+                        .iter()
+                        .map(super::friendly_value_repr)
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                Arc::new(MettaValue::SExpr(items.to_vec())),
+            );
+            Err((vec![err], env))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_seeded(src: &str, seed: u64) -> Vec<MettaValue> {
+        let mut env = Environment::new();
+        env.seed_rng(seed);
+        crate::backend::run::eval_forms(src, env).0.concat()
+    }
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let src = "!(random-int 0 1000)\n!(random-int 0 1000)\n!(random-float)\n!(random-int -5 5)";
+        let first = run_seeded(src, 1234);
+        let second = run_seeded(src, 1234);
+        assert_eq!(first.len(), 4);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_random_int_in_range() {
+        let src = "!(random-int 3 (+ 3 2))\n".repeat(50);
+        for result in run_seeded(&src, 99) {
+            match result {
+                MettaValue::Long(n) => assert!((3..5).contains(&n), "{}", n),
+                other => panic!("Expected Long, got {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_random_float_in_unit_interval() {
+        match run_seeded("!(random-float)", 5).as_slice() {
+            [MettaValue::Float(x)] => assert!((0.0..1.0).contains(x)),
+            other => panic!("Expected a single Float, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_random_int_errors() {
+        let results = run_seeded("!(random-int 5 5)\n!(random-int a 5)", 1);
+        assert_eq!(results.len(), 2);
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("empty range")));
+        assert!(matches!(&results[1], MettaValue::Error(msg, _) if msg.contains("integer bounds")));
+    }
+}
@@ -60,7 +60,7 @@ use tracing::trace;
 /// - Time: O(n)
 /// - Space: O(n) for the HashSet
 pub fn eval_unique_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
-    require_args_with_usage!("unique-atom", items, 1, env, "(unique-atom list)");
+    require_args_with_usage!("unique-atom", items, env, "(unique-atom list)");
 
     let input = &items[1];
     let input_vec = match input {
@@ -113,7 +113,7 @@ pub fn eval_unique_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult
 /// - Space: O(n + m) for the result
 pub fn eval_union_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_union-atom", ?items);
-    require_args_with_usage!("union-atom", items, 2, env, "(union-atom left right)");
+    require_args_with_usage!("union-atom", items, env, "(union-atom left right)");
 
     let left = &items[1];
     let right = &items[2];
@@ -188,7 +188,6 @@ pub fn eval_intersection_atom(items: Vec<MettaValue>, env: Environment) -> EvalR
     require_args_with_usage!(
         "intersection-atom",
         items,
-        2,
         env,
         "(intersection-atom left right)"
     );
@@ -262,7 +261,6 @@ pub fn eval_subtraction_atom(items: Vec<MettaValue>, env: Environment) -> EvalRe
     require_args_with_usage!(
         "subtraction-atom",
         items,
-        2,
         env,
         "(subtraction-atom left right)"
     );
@@ -496,6 +494,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_union_atom_single_elements() {
+        let env = Environment::new();
+
+        // (union-atom (a) ()) -> (a)
+        let items = vec![
+            MettaValue::Atom("union-atom".to_string()),
+            MettaValue::SExpr(vec![MettaValue::Atom("a".to_string())]),
+            MettaValue::Nil,
+        ];
+
+        let (results, _) = eval_union_atom(items, env);
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![MettaValue::Atom("a".to_string())])]
+        );
+    }
+
+    #[test]
+    fn test_union_atom_non_expression_errors() {
+        let env = Environment::new();
+
+        // (union-atom a (b)) -> error, left is not an expression
+        let items = vec![
+            MettaValue::Atom("union-atom".to_string()),
+            MettaValue::Atom("a".to_string()),
+            MettaValue::SExpr(vec![MettaValue::Atom("b".to_string())]),
+        ];
+
+        let (results, _) = eval_union_atom(items, env.clone());
+        assert_eq!(results.len(), 1);
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("left argument")));
+
+        // (union-atom (a) 5) -> error, right is not an expression
+        let items = vec![
+            MettaValue::Atom("union-atom".to_string()),
+            MettaValue::SExpr(vec![MettaValue::Atom("a".to_string())]),
+            MettaValue::Long(5),
+        ];
+
+        let (results, _) = eval_union_atom(items, env);
+        assert_eq!(results.len(), 1);
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("right argument")));
+    }
+
     #[test]
     fn test_intersection_atom() {
         let env = Environment::new();
@@ -1,9 +1,11 @@
 use crate::backend::environment::Environment;
 use crate::backend::fuzzy_match::FuzzyMatcher;
-use crate::backend::models::{EvalResult, MettaValue, Rule};
+use crate::backend::models::{Bindings, EvalResult, MettaValue, Rule};
 use std::sync::{Arc, OnceLock};
 use tracing::{debug, trace};
 
+use super::{apply_bindings, pattern_match};
+
 /// Valid space names for "Did you mean?" suggestions
 const VALID_SPACE_NAMES: &[&str] = &["self"];
 
@@ -13,6 +15,49 @@ fn space_name_matcher() -> &'static FuzzyMatcher {
     MATCHER.get_or_init(|| FuzzyMatcher::from_terms(VALID_SPACE_NAMES.iter().copied()))
 }
 
+/// Prefix of the symbol that refers to a space created by `new-space`, e.g. `Space#0`
+/// A plain symbol, like a state reference, so re-evaluating it yields the reference.
+const SPACE_REF_PREFIX: &str = "Space#";
+
+fn space_ref(id: u64) -> MettaValue {
+    MettaValue::Atom(format!("{}{}", SPACE_REF_PREFIX, id))
+}
+
+fn parse_space_ref(value: &MettaValue) -> Option<u64> {
+    match value {
+        MettaValue::Atom(name) => name.strip_prefix(SPACE_REF_PREFIX)?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Evaluate a space argument and return the id of the `new-space` space it refers to
+/// A `&name` token gives the space `bind!` registered under it; `&self`, an unbound
+/// token and any other value give None.
+fn eval_space_handle(arg: &MettaValue, env: Environment) -> (Option<u64>, Environment) {
+    if let MettaValue::Atom(s) = arg {
+        if s.starts_with('&') {
+            let id = env.get_token(s).as_ref().and_then(parse_space_ref);
+            return (id, env);
+        }
+    }
+    let (results, env) = super::eval(arg.clone(), env);
+    match results.as_slice() {
+        [value] => (parse_space_ref(value), env),
+        _ => (None, env),
+    }
+}
+
+fn unknown_space(items: &[MettaValue], id: u64) -> MettaValue {
+    MettaValue::Error(
+        format!(
+            "{}: unknown space {}",
+            super::friendly_value_repr(&items[0]),
+            space_ref(id)
+        ),
+        Arc::new(MettaValue::SExpr(items.to_vec())),
+    )
+}
+
 /// Suggest a valid space name if the given name is close to one
 fn suggest_space_name(name: &str) -> Option<String> {
     // First check for common case errors
@@ -26,28 +71,303 @@ fn suggest_space_name(name: &str) -> Option<String> {
 }
 
 /// Rule definition: (= lhs rhs) - add to MORK Space and rule cache
+///
+/// `(= lhs rhs where guard)` defines a guarded rule: after lhs matches, guard is
+/// evaluated with the match's bindings and the rule only fires if it is True.
+/// Otherwise the call falls through to the other rules matching it.
 pub(super) fn eval_add(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_add", ?items);
-    require_args_with_usage!("=", items, 2, env, "(= pattern body)");
+    require_args_with_usage!("=", items, env, "(= pattern body)");
 
-    let lhs = items[1].clone();
-    let rhs = items[2].clone();
+    let guard = match &items[3..] {
+        [MettaValue::Atom(kw), guard] if kw == "where" => Some(guard.clone()),
+        [MettaValue::Atom(kw), ..] if kw == "where" => {
+            let err = MettaValue::Error(
+                "= with a guard is written (= pattern body where guard)".to_string(),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+        _ => None,
+    };
+    let rule = Rule {
+        lhs: items[1].clone(),
+        rhs: items[2].clone(),
+        guard,
+    };
     let mut new_env = env.clone();
 
     // Add rule using add_rule (stores in both rule_cache and MORK Space)
-    new_env.add_rule(Rule { lhs, rhs });
+    new_env.add_rule(rule);
 
     // Return empty list (rule definitions don't produce output)
     (vec![], new_env)
 }
 
-/// Evaluate match: (match <space-ref> <space-name> <pattern> <template>)
+/// Split `(op & self arg)` or `(op &self arg)` into its argument, or build the error
+/// explaining the expected form
+fn self_space_arg(op: &str, items: &[MettaValue], usage: &str) -> Result<MettaValue, MettaValue> {
+    match split_space_ref(&items[1..]).as_slice() {
+        [MettaValue::Atom(amp), MettaValue::Atom(name), arg] if amp == "&" => {
+            if name == "self" {
+                Ok(arg.clone())
+            } else {
+                let msg = match suggest_space_name(name) {
+                    Some(s) => format!(
+                        "{} only supports 'self' as space name, got: {}. {}",
+                        op, name, s
+                    ),
+                    None => format!("{} only supports 'self' as space name, got: {}", op, name),
+                };
+                Err(MettaValue::Error(
+                    msg,
+                    Arc::new(MettaValue::SExpr(items.to_vec())),
+                ))
+            }
+        }
+        _ => Err(MettaValue::Error(
+            format!(
+                "{} requires a space and an atom, got {} argument(s). Usage: {}",
+                op,
+                items.len().saturating_sub(1),
+                usage
+            ),
+            Arc::new(MettaValue::SExpr(items.to_vec())),
+        )),
+    }
+}
+
+/// New space: (new-space) -> Space#N
+/// Creates an empty space, separate from `&self`, for `add-atom` and `match`
+pub(super) fn eval_new_space(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_new_space", ?items);
+    require_args_with_usage!("new-space", items, env, "(new-space)");
+
+    let id = env.new_space();
+    (vec![space_ref(id)], env)
+}
+
+/// Add atom: (add-atom & self atom) or (add-atom space atom)
+/// Adds the atom to the space exactly as written - it is not evaluated.
+/// `space` is a handle from `new-space`, usually bound to a variable.
+/// Returns unit `()`.
+pub(super) fn eval_add_atom(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_add_atom", ?items);
+    let env = if items.len() == 3 {
+        match eval_space_handle(&items[1], env) {
+            (Some(id), env) => {
+                if !env.add_to_named_space(id, items[2].clone()) {
+                    return (vec![unknown_space(&items, id)], env);
+                }
+                return (vec![MettaValue::SExpr(vec![])], env);
+            }
+            (None, env) => env,
+        }
+    } else {
+        env
+    };
+
+    let atom = match self_space_arg("add-atom", &items, "(add-atom & self atom)") {
+        Ok(atom) => atom,
+        Err(err) => return (vec![err], env),
+    };
+
+    let mut new_env = env;
+    new_env.add_to_space(&atom);
+    (vec![MettaValue::SExpr(vec![])], new_env)
+}
+
+/// Merge space: (merge-space! & self space)
+/// Copies every atom of a space created by `new-space` into `&self`, so that
+/// `match & self` finds them. Atoms already in `&self` are not duplicated.
+/// Returns unit `()`.
+pub(super) fn eval_merge_space(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_merge_space", ?items);
+    let space = match self_space_arg("merge-space!", &items, "(merge-space! & self space)") {
+        Ok(space) => space,
+        Err(err) => return (vec![err], env),
+    };
+
+    let (id, mut env) = eval_space_handle(&space, env);
+    let Some(id) = id else {
+        let err = MettaValue::Error(
+            format!(
+                "merge-space! expects a space created by new-space, got {}",
+                super::friendly_value_repr(&space)
+            ),
+            Arc::new(MettaValue::SExpr(items)),
+        );
+        return (vec![err], env);
+    };
+    if !env.merge_named_space_into_self(id) {
+        return (vec![unknown_space(&items, id)], env);
+    }
+    (vec![MettaValue::SExpr(vec![])], env)
+}
+
+/// Add fact: (add-fact! fact)
+/// Shorthand for `(add-atom & self fact)`: adds the fact to the current space as
+/// written, without evaluating it. Rules are defined with `=` instead, so a
+/// `(= lhs rhs)` argument is an error. Returns unit `()`.
+pub(super) fn eval_add_fact(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_add_fact", ?items);
+    require_args_with_usage!("add-fact!", items, env, "(add-fact! fact)");
+
+    let fact = &items[1];
+    if let MettaValue::SExpr(parts) = fact {
+        if matches!(parts.first(), Some(MettaValue::Atom(op)) if op == "=") {
+            let err = MettaValue::Error(
+                "add-fact! does not define rules, use (= lhs rhs)".to_string(),
+                Arc::new(fact.clone()),
+            );
+            return (vec![err], env);
+        }
+    }
+
+    let mut new_env = env;
+    new_env.add_fact(fact);
+    (vec![MettaValue::SExpr(vec![])], new_env)
+}
+
+/// Add reduct: (add-reduct & self expr)
+/// Evaluates expr first and adds every result to the space, so
+/// (add-reduct & self (foo (+ 1 2))) adds (foo 3). Errors from the evaluation
+/// are returned instead of being added. Returns unit `()`.
+pub(super) fn eval_add_reduct(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_add_reduct", ?items);
+    let expr = match self_space_arg("add-reduct", &items, "(add-reduct & self expr)") {
+        Ok(expr) => expr,
+        Err(err) => return (vec![err], env),
+    };
+
+    let (results, mut new_env) = super::eval(expr, env);
+    if let Some(err) = results
+        .iter()
+        .find(|r| matches!(r, MettaValue::Error(_, _)))
+    {
+        return (vec![err.clone()], new_env);
+    }
+
+    for result in &results {
+        new_env.add_to_space(result);
+    }
+    (vec![MettaValue::SExpr(vec![])], new_env)
+}
+
+/// Env stats: (env-stats)
+/// Returns the size of the environment as `((rules N) (wildcard-rules N) (facts N) ...)`,
+/// including the state cells, bound tokens and `new-space` spaces it holds
+pub(super) fn eval_env_stats(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_env_stats", ?items);
+    require_args_with_usage!("env-stats", items, env, "(env-stats)");
+
+    let stats = env.stats().to_metta_value();
+    (vec![stats], env)
+}
+
+/// Intern stats: (intern-stats)
+/// Returns symbol interning diagnostics as `((symbols N) (symbol-bytes N) (approx-bytes N))`
+pub(super) fn eval_intern_stats(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_intern_stats", ?items);
+    require_args_with_usage!("intern-stats", items, env, "(intern-stats)");
+
+    let stats = env.intern_stats().to_metta_value();
+    (vec![stats], env)
+}
+
+/// Strip a `(quote X)` wrapper from a match template, leaving `X`
+fn unquote_template(template: &MettaValue) -> MettaValue {
+    match template {
+        MettaValue::SExpr(items) if items.len() == 2 => match &items[0] {
+            MettaValue::Atom(head) if head == "quote" => items[1].clone(),
+            _ => template.clone(),
+        },
+        _ => template.clone(),
+    }
+}
+
+/// Evaluate match: (match <space-ref> <space-name> <pattern> <template>...)
 /// Searches the space for all atoms matching the pattern and returns instantiated templates
 ///
+/// Each template is instantiated with the match's bindings, once per match. The
+/// instantiated template is returned as data, not evaluated, so `(match & self
+/// (rel $x $y) ($x $y))` yields one `(a b)` per `(rel a b)` fact without adding
+/// anything to the space. With several templates, every match yields one result
+/// per template, in template order.
+///
+/// A template written as `(quote X)` yields `X` itself with the bindings filled
+/// in, so `(match & self (val $x) (quote (result $x)))` returns `(result 1)` as
+/// literal data even when `result` is a defined function.
+///
+/// The space may be given as `& self` or as a single `&self` atom, or as a space
+/// created by `new-space`: `(let $s (new-space) ... (match $s pattern template))`
+/// matches the atoms added to `$s` in the order they were added.
+///
 /// Optimized to use Environment::match_space which performs pattern matching
 /// directly on MORK expressions without unnecessary intermediate allocations
 pub(super) fn eval_match(items: Vec<MettaValue>, env: Environment) -> EvalResult {
-    let args = &items[1..];
+    let (query, env) = match_query(&items, env);
+    let query = match query {
+        Ok(query) => query,
+        Err(err) => return (vec![err], env),
+    };
+    let results = match (&query.space, query.templates.as_slice()) {
+        (MatchSpace::SelfSpace, [template]) => env.match_space(&query.pattern, template),
+        _ => match_pairs(&query, &env)
+            .into_iter()
+            .map(|(result, _)| result)
+            .collect(),
+    };
+    (results, env)
+}
+
+/// Evaluate a `match` expression keeping the bindings behind each result (used by
+/// `collapse-bind`)
+///
+/// Accepts every form `match` does; an invalid one gives the error `match` would
+/// return.
+pub(super) fn eval_match_with_bindings(
+    items: &[MettaValue],
+    env: Environment,
+) -> (Result<Vec<(MettaValue, Bindings)>, MettaValue>, Environment) {
+    let (query, env) = match_query(items, env);
+    let pairs = query.map(|query| match_pairs(&query, &env));
+    (pairs, env)
+}
+
+/// Where a `match` searches
+enum MatchSpace {
+    /// The environment's own space, `& self`
+    SelfSpace,
+    /// The atoms of a space created by `new-space`
+    Atoms(Vec<MettaValue>),
+}
+
+/// The checked arguments of a `match`, with `quote` wrappers removed from the templates
+struct MatchQuery {
+    space: MatchSpace,
+    pattern: MettaValue,
+    templates: Vec<MettaValue>,
+}
+
+/// Check the arguments of `(match space pattern template...)`, evaluating a space
+/// argument such as `$s` to the `new-space` space it refers to
+fn match_query(
+    items: &[MettaValue],
+    env: Environment,
+) -> (Result<MatchQuery, MettaValue>, Environment) {
+    let env = match items.get(1) {
+        Some(space) => match eval_space_handle(space, env) {
+            (Some(id), env) => {
+                let query = named_space_query(id, items, &env);
+                return (query, env);
+            }
+            (None, env) => env,
+        },
+        None => env,
+    };
+
+    let args = split_space_ref(&items[1..]);
     debug!(target: "mettatron::eval::eval_match", ?args, ?items);
 
     if args.len() < 4 {
@@ -60,29 +380,27 @@ pub(super) fn eval_match(items: Vec<MettaValue>, env: Environment) -> EvalResult
 
         let err = MettaValue::Error(
             format!(
-                "match requires exactly 4 arguments, got {}. Usage: (match & space pattern template)",
+                "match requires at least 4 arguments, got {}. Usage: (match & space pattern template)",
                 got
             ),
             Arc::new(MettaValue::SExpr(args.to_vec())),
         );
-        return (vec![err], env);
+        return (Err(err), env);
     }
 
     let space_ref = &args[0];
     let space_name = &args[1];
-    let pattern = &args[2];
-    let template = &args[3];
 
     // Check that first arg is & (space reference operator)
-    match space_ref {
+    let query = match space_ref {
         MettaValue::Atom(s) if s == "&" => {
             // Check space name (for now, only support "self")
             match space_name {
-                MettaValue::Atom(name) if name == "self" => {
-                    // Use optimized match_space method that works directly with MORK
-                    let results = env.match_space(pattern, template);
-                    (results, env)
-                }
+                MettaValue::Atom(name) if name == "self" => Ok(MatchQuery {
+                    space: MatchSpace::SelfSpace,
+                    pattern: args[2].clone(),
+                    templates: args[3..].iter().map(unquote_template).collect(),
+                }),
                 _ => {
                     // Try to suggest a valid space name
                     let name_str = match space_name {
@@ -102,27 +420,95 @@ pub(super) fn eval_match(items: Vec<MettaValue>, env: Environment) -> EvalResult
                         ),
                     };
 
-                    let err = MettaValue::Error(msg, Arc::new(MettaValue::SExpr(args.to_vec())));
-                    (vec![err], env)
+                    Err(MettaValue::Error(
+                        msg,
+                        Arc::new(MettaValue::SExpr(args.to_vec())),
+                    ))
                 }
             }
         }
-        _ => {
-            let err = MettaValue::Error(
-                format!(
-                    "match requires & as first argument, got: {}",
-                    super::friendly_value_repr(space_ref)
-                ),
-                Arc::new(MettaValue::SExpr(args.to_vec())),
-            );
-            (vec![err], env)
+        _ => Err(MettaValue::Error(
+            format!(
+                "match requires & as first argument, got: {}",
+                super::friendly_value_repr(space_ref)
+            ),
+            Arc::new(MettaValue::SExpr(args.to_vec())),
+        )),
+    };
+    (query, env)
+}
+
+/// Check a match against a space created by `new-space`: (match space pattern template...)
+fn named_space_query(
+    id: u64,
+    items: &[MettaValue],
+    env: &Environment,
+) -> Result<MatchQuery, MettaValue> {
+    debug!(target: "mettatron::eval::named_space_query", id, ?items);
+    if items.len() < 4 {
+        return Err(MettaValue::Error(
+            format!(
+                "match requires a space, a pattern and a template, got {} argument(s). Usage: (match space pattern template)",
+                items.len() - 1
+            ),
+            Arc::new(MettaValue::SExpr(items.to_vec())),
+        ));
+    }
+    let Some(atoms) = env.named_space_atoms(id) else {
+        return Err(unknown_space(items, id));
+    };
+    Ok(MatchQuery {
+        space: MatchSpace::Atoms(atoms),
+        pattern: items[2].clone(),
+        templates: items[3..].iter().map(unquote_template).collect(),
+    })
+}
+
+/// Every match of `query` as an instantiated template and the bindings that produced
+/// it: one entry per template for each matching atom, in template order
+fn match_pairs(query: &MatchQuery, env: &Environment) -> Vec<(MettaValue, Bindings)> {
+    let matches: Vec<Bindings> = match &query.space {
+        MatchSpace::SelfSpace => env
+            .match_space_with_bindings(&query.pattern, &MettaValue::Nil)
+            .into_iter()
+            .map(|(_, bindings)| bindings)
+            .collect(),
+        MatchSpace::Atoms(atoms) => atoms
+            .iter()
+            .filter_map(|atom| pattern_match(&query.pattern, atom))
+            .collect(),
+    };
+    matches
+        .into_iter()
+        .flat_map(|bindings| {
+            query
+                .templates
+                .iter()
+                .map(|t| (apply_bindings(t, &bindings), bindings.clone()))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// Split a leading `&name` atom into the atoms `&` and `name`, so `(match &self ...)`
+/// and `(match & self ...)` take the same path
+pub(super) fn split_space_ref(args: &[MettaValue]) -> Vec<MettaValue> {
+    match args.first() {
+        Some(MettaValue::Atom(s)) if s.len() > 1 && s.starts_with('&') => {
+            let mut split = Vec::with_capacity(args.len() + 1);
+            split.push(MettaValue::Atom("&".to_string()));
+            split.push(MettaValue::Atom(s[1..].to_string()));
+            split.extend_from_slice(&args[1..]);
+            split
         }
+        _ => args.to_vec(),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::run::eval_last;
     use crate::eval;
 
     #[test]
@@ -393,6 +779,106 @@ mod tests {
         assert!(results.contains(&MettaValue::Atom("bob".to_string())));
     }
 
+    fn rel_env() -> Environment {
+        let mut env = Environment::new();
+        for (a, b) in [("a", "b"), ("c", "d")] {
+            env.add_to_space(&MettaValue::SExpr(vec![
+                MettaValue::Atom("rel".to_string()),
+                MettaValue::Atom(a.to_string()),
+                MettaValue::Atom(b.to_string()),
+            ]));
+        }
+        env
+    }
+
+    fn pair(a: &str, b: &str) -> MettaValue {
+        MettaValue::SExpr(vec![
+            MettaValue::Atom(a.to_string()),
+            MettaValue::Atom(b.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_match_template_receives_bindings_per_match() {
+        let env = rel_env();
+
+        // (match &self (rel $x $y) ($x $y)) - single &self atom form
+        let query = MettaValue::SExpr(vec![
+            MettaValue::Atom("match".to_string()),
+            MettaValue::Atom("&self".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("rel".to_string()),
+                MettaValue::Atom("$x".to_string()),
+                MettaValue::Atom("$y".to_string()),
+            ]),
+            pair("$x", "$y"),
+        ]);
+
+        let (results, env) = eval(query, env);
+        assert_eq!(results.len(), 2);
+        assert!(results.contains(&pair("a", "b")));
+        assert!(results.contains(&pair("c", "d")));
+
+        // The instantiated templates are data: nothing was added to the space
+        let probe = MettaValue::SExpr(vec![
+            MettaValue::Atom("$p".to_string()),
+            MettaValue::Atom("$q".to_string()),
+        ]);
+        assert!(env.match_space(&probe, &probe).is_empty());
+    }
+
+    #[test]
+    fn test_match_quoted_template_is_returned_literally() {
+        let results = eval_last(
+            r#"
+            (= (result $x) (* $x 100))
+            (val 1)
+            !(match &self (val $x) (quote (result $x)))
+        "#,
+        );
+
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("result".to_string()),
+                MettaValue::Long(1),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_match_multiple_templates() {
+        let env = rel_env();
+
+        // (match & self (rel $x $y) $x ($y $x))
+        let query = MettaValue::SExpr(vec![
+            MettaValue::Atom("match".to_string()),
+            MettaValue::Atom("&".to_string()),
+            MettaValue::Atom("self".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("rel".to_string()),
+                MettaValue::Atom("$x".to_string()),
+                MettaValue::Atom("$y".to_string()),
+            ]),
+            MettaValue::Atom("$x".to_string()),
+            pair("$y", "$x"),
+        ]);
+
+        let (results, _) = eval(query, env);
+        assert_eq!(results.len(), 4);
+        // Each match yields its templates in order
+        let a = results
+            .iter()
+            .position(|r| r == &MettaValue::Atom("a".to_string()))
+            .expect("missing a");
+        assert_eq!(results[a + 1], pair("b", "a"));
+        let c = results
+            .iter()
+            .position(|r| r == &MettaValue::Atom("c".to_string()))
+            .expect("missing c");
+        assert_eq!(results[c + 1], pair("d", "c"));
+    }
+
     #[test]
     fn test_match_with_specific_patterns() {
         let mut env = Environment::new();
@@ -872,4 +1358,339 @@ mod tests {
             _ => panic!("Expected error without suggestion"),
         }
     }
+
+    #[test]
+    fn test_add_reduct_adds_evaluated_expression() {
+        let results = eval_last(
+            r#"
+            !(add-reduct & self (foo (+ 1 2)))
+            !(match & self (foo $x) $x)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Long(3)]);
+    }
+
+    #[test]
+    fn test_add_fact_is_visible_to_match() {
+        let results = eval_last(
+            r#"
+            !(add-fact! (parent Tom Bob))
+            !(match & self (parent Tom $child) $child)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Atom("Bob".to_string())]);
+
+        assert_eq!(
+            eval_last("!(add-fact! (parent Bob Ann))"),
+            vec![MettaValue::SExpr(vec![])]
+        );
+
+        let results = eval_last("!(add-fact! (= (f) 1))");
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("rules")));
+    }
+
+    #[test]
+    fn test_add_atom_keeps_expression_unevaluated() {
+        let results = eval_last(
+            r#"
+            !(add-atom & self (bar (+ 1 2)))
+            !(match & self (bar $x) $x)
+            "#,
+        );
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![
+                MettaValue::Atom("+".to_string()),
+                MettaValue::Long(1),
+                MettaValue::Long(2),
+            ])]
+        );
+    }
+
+    #[test]
+    fn test_add_to_self_space_reference() {
+        let results = eval_last(
+            r#"
+            !(add-atom &self (qux 1))
+            !(add-reduct &self (qux (+ 1 1)))
+            !(match &self (qux $x) $x)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Long(1), MettaValue::Long(2)]);
+
+        let results = eval_last("!(add-atom &slef (qux 1))");
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("Did you mean")));
+    }
+
+    #[test]
+    fn test_space_bound_to_token() {
+        let results = eval_last(
+            r#"
+            !(bind! &kb (new-space))
+            !(add-atom &kb (qux 1))
+            !(match &kb (qux $x) $x)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Long(1)]);
+
+        // The atom went into the bound space, not into &self
+        let results = eval_last(
+            r#"
+            !(bind! &kb (new-space))
+            !(add-atom &kb (qux 1))
+            !(match &self (qux $x) $x)
+            "#,
+        );
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_token_reference_in_fact_is_not_a_variable() {
+        let results = eval_last(
+            r#"
+            (uses &kb)
+            !(match &self (uses $x) $x)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Atom("&kb".to_string())]);
+
+        let results = eval_last(
+            r#"
+            (uses &kb)
+            !(match &self (uses &other) found)
+            "#,
+        );
+        assert!(results.is_empty());
+
+        // In a rule head, &self only matches itself
+        let results = eval_last("(= (in-space &self) yes)\n!(in-space &self)");
+        assert_eq!(results, vec![MettaValue::Atom("yes".to_string())]);
+        let results = eval_last("(= (in-space &self) yes)\n!(in-space other)");
+        assert!(!results.contains(&MettaValue::Atom("yes".to_string())));
+    }
+
+    #[test]
+    fn test_add_reduct_returns_unit_and_propagates_errors() {
+        assert_eq!(
+            eval_last("!(add-reduct & self (baz 1))"),
+            vec![MettaValue::SExpr(vec![])]
+        );
+
+        let results = eval_last("!(add-reduct & self (error boom 0))");
+        assert_eq!(results.len(), 1);
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg == "boom"));
+
+        let results = eval_last("!(add-reduct & slef (baz 1))");
+        assert!(matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("Did you mean")));
+    }
+
+    #[test]
+    fn test_match_wildcards_do_not_bind() {
+        let results = eval_last(
+            r#"
+            (f 1 2)
+            !(match & self (f _ _) ok)
+        "#,
+        );
+        assert_eq!(results, vec![MettaValue::Atom("ok".to_string())]);
+
+        let results = eval_last(
+            r#"
+            (f 1 2)
+            !(match & self (f $_ $_) ok)
+        "#,
+        );
+        assert_eq!(results, vec![MettaValue::Atom("ok".to_string())]);
+    }
+
+    #[test]
+    fn test_match_repeated_variable_requires_equal_values() {
+        let results = eval_last(
+            r#"
+            (f 1 2)
+            (f 3 3)
+            !(match & self (f $x $x) $x)
+        "#,
+        );
+        assert_eq!(results, vec![MettaValue::Long(3)]);
+    }
+
+    #[test]
+    fn test_rule_wildcards_vs_repeated_variables() {
+        let src = r#"
+            (= (any-pair _ _) yes)
+            (= (same-pair $x $x) yes)
+        "#;
+        assert_eq!(
+            eval_last(&format!("{}\n!(any-pair 1 2)", src)),
+            vec![MettaValue::Atom("yes".to_string())]
+        );
+        assert_eq!(
+            eval_last(&format!("{}\n!(same-pair 2 2)", src)),
+            vec![MettaValue::Atom("yes".to_string())]
+        );
+        // No rule matches, so the call is returned unevaluated
+        assert_ne!(
+            eval_last(&format!("{}\n!(same-pair 1 2)", src)),
+            vec![MettaValue::Atom("yes".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_guarded_rule_falls_through_when_guard_fails() {
+        let rules = r#"
+            (= (sign $x) pos where (> $x 0))
+            (= (sign $x) nonpos)
+        "#;
+        // The guarded rule wins while its guard holds, otherwise the other rule fires
+        let results = eval_last(&format!("{} !(sign 5)", rules));
+        assert_eq!(results, vec![MettaValue::Atom("pos".to_string())]);
+        let results = eval_last(&format!("{} !(sign 0)", rules));
+        assert_eq!(results, vec![MettaValue::Atom("nonpos".to_string())]);
+
+        let results = eval_last("!(= (sign $x) pos where)");
+        match &results[..] {
+            [MettaValue::Error(msg, _)] => assert!(msg.contains("(= pattern body where guard)")),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_guard_error_is_the_result_of_the_call() {
+        // A guard that fails with an error does not fall through to the other rule
+        let results = eval_last(
+            r#"
+            (= (ratio-sign $x $y) pos where (> (/ $x $y) 0))
+            (= (ratio-sign $x $y) nonpos)
+            !(ratio-sign 1 0)
+        "#,
+        );
+        match &results[..] {
+            [MettaValue::Error(msg, _)] => assert!(msg.contains("Division by zero")),
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_env_stats_reports_counts() {
+        let results = eval_last(
+            r#"
+            (= (double $x) (* $x 2))
+            (= (triple $x) (* $x 3))
+            (point 1 2)
+            !(bind! &answer 42)
+            !(new-state 0)
+            !(new-space)
+            !(env-stats)
+        "#,
+        );
+        assert_eq!(results.len(), 1);
+        let entries = match &results[0] {
+            MettaValue::SExpr(entries) => entries.clone(),
+            other => panic!("Expected stats expression, got {:?}", other),
+        };
+        let count = |name: &str| {
+            entries.iter().find_map(|entry| match entry {
+                MettaValue::SExpr(pair) if pair[0] == MettaValue::Atom(name.to_string()) => {
+                    Some(pair[1].clone())
+                }
+                _ => None,
+            })
+        };
+        assert_eq!(count("rules"), Some(MettaValue::Long(2)));
+        assert_eq!(count("wildcard-rules"), Some(MettaValue::Long(0)));
+        assert_eq!(count("facts"), Some(MettaValue::Long(1)));
+        assert_eq!(count("states"), Some(MettaValue::Long(1)));
+        assert_eq!(count("tokens"), Some(MettaValue::Long(1)));
+        assert_eq!(count("named-spaces"), Some(MettaValue::Long(1)));
+    }
+
+    #[test]
+    fn test_intern_stats_reports_symbols() {
+        let results = eval_last(
+            r#"
+            (point a b)
+            (point b a)
+            !(intern-stats)
+        "#,
+        );
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            MettaValue::SExpr(entries) => assert_eq!(
+                entries[0],
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("symbols".to_string()),
+                    MettaValue::Long(3),
+                ])
+            ),
+            other => panic!("Expected stats expression, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_merge_space_into_self() {
+        let results = eval_last(
+            r#"
+            (color sky blue)
+            !(let $s (new-space)
+                (let $_ (add-atom $s (color sea blue))
+                    (let $_ (add-atom $s (color sky blue))
+                        (let $_ (merge-space! &self $s)
+                            (merge-space! & self $s)))))
+            !(collapse (match &self (color $x blue) $x))
+        "#,
+        );
+        // Merged atoms are matched through &self, each one once
+        match &results[..] {
+            [MettaValue::SExpr(found)] => {
+                let mut found = found.clone();
+                found.sort_by_key(|v| v.to_string());
+                assert_eq!(
+                    found,
+                    vec![
+                        MettaValue::Atom("sea".to_string()),
+                        MettaValue::Atom("sky".to_string()),
+                    ]
+                );
+            }
+            other => panic!("Expected collapsed matches, got {:?}", other),
+        }
+
+        let results = eval_last("!(merge-space! &self (color sky blue))");
+        match &results[..] {
+            [MettaValue::Error(msg, _)] => {
+                assert!(msg.contains("expects a space created by new-space"))
+            }
+            other => panic!("Expected error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_match_named_space_through_bound_variable() {
+        let results = eval_last(
+            r#"
+            (color sky blue)
+            !(let $s (new-space)
+                (let $_ (add-atom $s (color grass green))
+                    (let $_ (add-atom $s (color sea blue))
+                        (collapse (match $s (color $x blue) $x)))))
+        "#,
+        );
+        // Only the named space is searched, not &self
+        assert_eq!(
+            results,
+            vec![MettaValue::SExpr(vec![MettaValue::Atom("sea".to_string())])]
+        );
+
+        let results = eval_last(
+            r#"
+            !(let $s (new-space) (match $s (color $x $y) $x))
+            !(match Space#7 (color $x $y) $x)
+        "#,
+        );
+        match &results[..] {
+            [MettaValue::Error(msg, _)] => assert!(msg.contains("unknown space Space#7")),
+            other => panic!("Expected unknown space error, got {:?}", other),
+        }
+    }
 }
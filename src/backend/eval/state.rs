@@ -0,0 +1,277 @@
+use std::sync::Arc;
+use tracing::trace;
+
+use crate::backend::environment::Environment;
+use crate::backend::models::{EvalResult, MettaValue};
+
+use super::eval;
+
+/// Prefix of the symbol that refers to a state cell, e.g. `State#0`
+///
+/// A state reference is a plain symbol rather than an expression so that
+/// re-evaluating it (for example after `let` substitution) yields the reference
+/// itself instead of adding it to the space.
+const STATE_REF_PREFIX: &str = "State#";
+
+fn state_ref(id: u64) -> MettaValue {
+    MettaValue::Atom(format!("{}{}", STATE_REF_PREFIX, id))
+}
+
+fn parse_state_ref(value: &MettaValue) -> Option<u64> {
+    match value {
+        MettaValue::Atom(name) => name.strip_prefix(STATE_REF_PREFIX)?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// New-state: create a mutable state cell holding the value of `expr`
+/// (new-state expr) -> State#N
+pub(super) fn eval_new_state(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_new_state", ?items);
+    require_args_with_usage!("new-state", items, env, "(new-state value)");
+
+    let (results, env) = eval(items[1].clone(), env);
+    let refs = results
+        .into_iter()
+        .map(|value| match value {
+            MettaValue::Error(_, _) => value,
+            value => state_ref(env.new_state(value)),
+        })
+        .collect();
+    (refs, env)
+}
+
+/// Get-state: current value of a state cell
+/// (get-state state)
+pub(super) fn eval_get_state(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_get_state", ?items);
+    require_args_with_usage!("get-state", items, env, "(get-state state)");
+
+    let (id, env) = match eval_state_arg(&items, env) {
+        Ok(result) => result,
+        Err(result) => return result,
+    };
+    match env.get_state(id) {
+        Some(value) => (vec![value], env),
+        None => (vec![unknown_state(&items, id)], env),
+    }
+}
+
+/// Change-state!: replace the value of a state cell and return the state
+/// (change-state! state value)
+///
+/// The new value must have the same type as the value the cell was created with
+/// (Number, String, Bool, Symbol, Expression, ...); otherwise the cell is left
+/// unchanged and `(Error "state type mismatch" ...)` is returned. The check can be
+/// turned off with `Environment::set_strict_state_types(false)`.
+pub(super) fn eval_change_state(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    trace!(target: "mettatron::eval::eval_change_state", ?items);
+    require_args_with_usage!("change-state!", items, env, "(change-state! state value)");
+
+    let (id, env) = match eval_state_arg(&items, env) {
+        Ok(result) => result,
+        Err(result) => return result,
+    };
+
+    if env.get_state(id).is_none() {
+        return (vec![unknown_state(&items, id)], env);
+    }
+
+    let (values, env) = eval(items[2].clone(), env);
+    let value = match values.as_slice() {
+        [err @ MettaValue::Error(_, _), ..] => return (vec![err.clone()], env),
+        [value] => value.clone(),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "change-state! expects a single new value, got {} results",
+                    values.len()
+                ),
+                Arc::new(MettaValue::SExpr(items.clone())),
+            );
+            return (vec![err], env);
+        }
+    };
+
+    match env.change_state(id, value) {
+        Ok(()) => (vec![state_ref(id)], env),
+        Err(details) => {
+            let err = MettaValue::Error(
+                "state type mismatch".to_string(),
+                Arc::new(MettaValue::String(details)),
+            );
+            (vec![err], env)
+        }
+    }
+}
+
+/// Evaluate the first argument and require a single state reference
+fn eval_state_arg(
+    items: &[MettaValue],
+    env: Environment,
+) -> Result<(u64, Environment), EvalResult> {
+    let (results, env) = eval(items[1].clone(), env);
+    match results.as_slice() {
+        [err @ MettaValue::Error(_, _), ..] => Err((vec![err.clone()], env)),
+        [value] => match parse_state_ref(value) {
+            Some(id) => Ok((id, env)),
+            None => Err(not_a_state(items, &results, env)),
+        },
+        _ => Err(not_a_state(items, &results, env)),
+    }
+}
+
+fn unknown_state(items: &[MettaValue], id: u64) -> MettaValue {
+    MettaValue::Error(
+        format!(
+            "{}: unknown state {}",
+            super::friendly_value_repr(&items[0]),
+            state_ref(id)
+        ),
+        Arc::new(MettaValue::SExpr(items.to_vec())),
+    )
+}
+
+fn not_a_state(items: &[MettaValue], results: &[MettaValue], env: Environment) -> EvalResult {
+    let err = MettaValue::Error(
+        format!(
+            "{} expects a state created by new-state, got {}",
+            super::friendly_value_repr(&items[0]),
+            results
+                .iter()
+                .map(super::friendly_value_repr)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        Arc::new(MettaValue::SExpr(items.to_vec())),
+    );
+    (vec![err], env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::run::eval_last_in;
+
+    fn run_with(src: &str, strict: bool) -> Vec<MettaValue> {
+        let mut env = Environment::new();
+        env.set_strict_state_types(strict);
+        eval_last_in(src, env).0
+    }
+
+    #[test]
+    fn test_change_state_same_type() {
+        let results = run_with(
+            "!(let $s (new-state 1) (let $_ (change-state! $s (+ 2 3)) (get-state $s)))",
+            true,
+        );
+        assert_eq!(results, vec![MettaValue::Long(5)]);
+    }
+
+    #[test]
+    fn test_change_state_type_mismatch() {
+        let results = run_with("!(let $s (new-state 1) (change-state! $s \"one\"))", true);
+        assert_eq!(results.len(), 1);
+        match &results[0] {
+            MettaValue::Error(msg, details) => {
+                assert_eq!(msg, "state type mismatch");
+                assert_eq!(
+                    **details,
+                    MettaValue::String("state holds Number but new value is String".to_string())
+                );
+            }
+            other => panic!("Expected Error, got {:?}", other),
+        }
+
+        // The cell keeps its old value
+        let results = run_with(
+            "!(let $s (new-state 1) (let $_ (change-state! $s \"one\") (get-state $s)))",
+            true,
+        );
+        assert_eq!(results, vec![MettaValue::Long(1)]);
+    }
+
+    #[test]
+    fn test_change_state_relaxed_allows_any_type() {
+        let results = run_with(
+            "!(let $s (new-state 1) (let $_ (change-state! $s \"one\") (get-state $s)))",
+            false,
+        );
+        assert_eq!(results, vec![MettaValue::String("one".to_string())]);
+    }
+
+    #[test]
+    fn test_get_state_requires_state() {
+        let results = run_with("!(get-state 5)", true);
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("state created by new-state"))
+        );
+    }
+
+    #[test]
+    fn test_while_loop_accumulates_into_state() {
+        // Sum 0..5 into $total, counting with $i
+        let results = run_with(
+            "!(let $i (new-state 0) (let $total (new-state 0) \
+               (let $_ (while (< (get-state $i) 5) \
+                          (let $_ (change-state! $total (+ (get-state $total) (get-state $i))) \
+                            (change-state! $i (+ (get-state $i) 1)))) \
+                 (get-state $total))))",
+            true,
+        );
+        assert_eq!(results, vec![MettaValue::Long(10)]);
+
+        // The loop itself evaluates to ()
+        let results = run_with(
+            "!(let $i (new-state 3) (while (> (get-state $i) 0) \
+               (change-state! $i (- (get-state $i) 1))))",
+            true,
+        );
+        assert_eq!(results, vec![MettaValue::SExpr(vec![])]);
+
+        // A non-Bool condition is an error, as for if
+        let results = run_with("!(while 1 ())", true);
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg == "while: condition not boolean"),
+            "{:?}",
+            results
+        );
+    }
+
+    #[test]
+    fn test_forked_branches_see_isolated_state() {
+        let run = |src: &str| run_with(src, true);
+        let bump = |n: i64| {
+            format!(
+                "(let $_ (change-state! $s (+ (get-state $s) {})) (get-state $s))",
+                n
+            )
+        };
+
+        // Sibling alternatives each start from the value before the fork
+        let results = run(&format!(
+            "!(let $s (new-state 0) (superpose ({} {})))",
+            bump(1),
+            bump(10)
+        ));
+        assert_eq!(results, vec![MettaValue::Long(1), MettaValue::Long(10)]);
+
+        // After the fork the later branch's change is kept
+        let results = run(&format!(
+            "!(let $s (new-state 0) (let $_ (collapse (superpose ({} {}))) (get-state $s)))",
+            bump(1),
+            bump(10)
+        ));
+        assert_eq!(results, vec![MettaValue::Long(10)]);
+
+        // Several matching rules fork the same way
+        let results = run(&format!(
+            "(= (bump $s) {})\n(= (bump $s) {})\n!(let $s (new-state 0) (bump $s))",
+            bump(1),
+            bump(10)
+        ));
+        assert_eq!(results.len(), 2);
+        assert!(results.contains(&MettaValue::Long(1)));
+        assert!(results.contains(&MettaValue::Long(10)));
+    }
+}
@@ -9,7 +9,7 @@ use super::eval;
 /// Alpha equality operation: (=alpha expr1 expr2) -> Bool
 /// Checks if two expressions are equivalent up to variable renaming
 pub(super) fn eval_alpha_eq(items: Vec<MettaValue>, env: Environment) -> EvalResult {
-    require_args_with_usage!("=alpha", items, 2, env, "(=alpha expr1 expr2)");
+    require_args_with_usage!("=alpha", items, env, "(=alpha expr1 expr2)");
 
     let result = atoms_are_alpha_equivalent(&items[1], &items[2]);
     (vec![MettaValue::Bool(result)], env)
@@ -23,19 +23,13 @@ pub(super) fn eval_assert_equal(items: Vec<MettaValue>, env: Environment) -> Eva
     let args = &items[1..];
     trace!(target: "mettatron::eval::assertEqual", ?items, ?args);
 
-    require_args_with_usage!(
-        "assertEqual",
-        items,
-        2,
-        env,
-        "(assertEqual actual expected)"
-    );
+    require_args_with_usage!("assertEqual", items, env, "(assertEqual actual expected)");
 
     let (actual_results, env_after_actual) = eval(args[0].clone(), env);
     let (expected_results, env_after_expected) = eval(args[1].clone(), env_after_actual);
 
     if results_are_equal(&actual_results, &expected_results) {
-        (vec![MettaValue::Nil], env_after_expected)
+        (vec![MettaValue::SExpr(vec![])], env_after_expected)
     } else {
         let err = MettaValue::Error(
             format!(
@@ -52,6 +46,84 @@ pub(super) fn eval_assert_equal(items: Vec<MettaValue>, env: Environment) -> Eva
     }
 }
 
+/// Evaluates a condition and asserts that it is True.
+/// Returns `()` on success, `Error` on failure. With several results, every one of
+/// them must be True; an error result is returned as is.
+///
+/// Syntax: `(assert condition)`
+pub(super) fn eval_assert(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    let args = &items[1..];
+    trace!(target: "mettatron::eval::assert", ?items, ?args);
+
+    require_args_with_usage!("assert", items, env, "(assert condition)");
+
+    let (results, env_after) = eval(args[0].clone(), env);
+    if let Some(err) = results
+        .iter()
+        .find(|r| matches!(r, MettaValue::Error(_, _)))
+    {
+        return (vec![err.clone()], env_after);
+    }
+
+    if !results.is_empty() && results.iter().all(|r| *r == MettaValue::Bool(true)) {
+        (vec![MettaValue::SExpr(vec![])], env_after)
+    } else {
+        let err = MettaValue::Error(
+            format!(
+                "Assertion failed: {} is not True.\nActual: {:?}",
+                super::friendly_value_repr(&args[0]),
+                results
+            ),
+            Arc::new(MettaValue::SExpr(vec![
+                MettaValue::Atom("assert".to_string()),
+                args[0].clone(),
+            ])),
+        );
+        (vec![err], env_after)
+    }
+}
+
+/// Evaluates both expressions and asserts that every result of `expected` is among
+/// the (possibly nondeterministic) results of `actual`.
+/// Returns `()` on success, `Error` on failure.
+///
+/// Syntax: `(assertIncludes actual expected)`
+pub(super) fn eval_assert_includes(items: Vec<MettaValue>, env: Environment) -> EvalResult {
+    let args = &items[1..];
+    trace!(target: "mettatron::eval::assertIncludes", ?items, ?args);
+
+    require_args_with_usage!(
+        "assertIncludes",
+        items,
+        env,
+        "(assertIncludes actual expected)"
+    );
+
+    let (actual_results, env_after_actual) = eval(args[0].clone(), env);
+    let (expected_results, env_after_expected) = eval(args[1].clone(), env_after_actual);
+
+    let missing: Vec<&MettaValue> = expected_results
+        .iter()
+        .filter(|e| !actual_results.contains(e))
+        .collect();
+    if missing.is_empty() {
+        (vec![MettaValue::SExpr(vec![])], env_after_expected)
+    } else {
+        let err = MettaValue::Error(
+            format!(
+                "Assertion failed: results do not include {:?}.\nActual: {:?}",
+                missing, actual_results
+            ),
+            Arc::new(MettaValue::SExpr(vec![
+                MettaValue::Atom("assertIncludes".to_string()),
+                args[0].clone(),
+                args[1].clone(),
+            ])),
+        );
+        (vec![err], env_after_expected)
+    }
+}
+
 /// Evaluates both expressions and asserts their results are alpha equal.
 /// Returns `()` on success, `Error` on failure.
 ///
@@ -63,7 +135,6 @@ pub(super) fn eval_assert_alpha_equal(items: Vec<MettaValue>, env: Environment)
     require_args_with_usage!(
         "assertAlphaEqual",
         items,
-        2,
         env,
         "(assertAlphaEqual actual expected)"
     );
@@ -72,7 +143,7 @@ pub(super) fn eval_assert_alpha_equal(items: Vec<MettaValue>, env: Environment)
     let (expected_results, env_after_expected) = eval(args[1].clone(), env_after_actual);
 
     if results_are_alpha_equal(&actual_results, &expected_results) {
-        (vec![MettaValue::Nil], env_after_expected)
+        (vec![MettaValue::SExpr(vec![])], env_after_expected)
     } else {
         let err = MettaValue::Error(
             format!(
@@ -89,7 +160,37 @@ pub(super) fn eval_assert_alpha_equal(items: Vec<MettaValue>, env: Environment)
     }
 }
 
-/// Like `assertEqual` but with a custom error message.
+/// Evaluate the message argument of an `assert...Msg` form, which must be a string
+/// literal or an expression producing a string
+fn assertion_message(
+    op: &str,
+    message: &MettaValue,
+    env: Environment,
+) -> (Result<String, MettaValue>, Environment) {
+    if let MettaValue::String(s) = message {
+        return (Ok(s.clone()), env);
+    }
+
+    let (results, env) = eval(message.clone(), env);
+    match results.first() {
+        Some(MettaValue::String(s)) => (Ok(s.clone()), env),
+        Some(err @ MettaValue::Error(_, _)) => (Err(err.clone()), env),
+        _ => {
+            let err = MettaValue::Error(
+                format!(
+                    "{}: message must be a string, got {}",
+                    op,
+                    super::friendly_value_repr(message)
+                ),
+                Arc::new(message.clone()),
+            );
+            (Err(err), env)
+        }
+    }
+}
+
+/// Like `assertEqual` but with a custom error message, which is followed by the
+/// expected and actual results on failure.
 ///
 /// Syntax: `(assertEqualMsg actual expected message)`
 pub(super) fn eval_assert_equal_msg(items: Vec<MettaValue>, env: Environment) -> EvalResult {
@@ -99,24 +200,26 @@ pub(super) fn eval_assert_equal_msg(items: Vec<MettaValue>, env: Environment) ->
     require_args_with_usage!(
         "assertEqualMsg",
         items,
-        3,
         env,
         "(assertEqualMsg actual expected message)"
     );
 
     let (actual_results, env_after_actual) = eval(args[0].clone(), env);
     let (expected_results, env_after_expected) = eval(args[1].clone(), env_after_actual);
+    let (message, env_after_expected) =
+        match assertion_message("assertEqualMsg", &args[2], env_after_expected) {
+            (Ok(message), env) => (message, env),
+            (Err(err), env) => return (vec![err], env),
+        };
 
     if results_are_equal(&actual_results, &expected_results) {
-        (vec![MettaValue::Nil], env_after_expected)
+        (vec![MettaValue::SExpr(vec![])], env_after_expected)
     } else {
-        let msg_str = match &args[2] {
-            MettaValue::String(s) | MettaValue::Atom(s) => s.clone(),
-            other => format!("{:?}", other),
-        };
-
         let err = MettaValue::Error(
-            msg_str,
+            format!(
+                "{}\nExpected: {:?}\nActual: {:?}",
+                message, expected_results, actual_results
+            ),
             Arc::new(MettaValue::SExpr(vec![
                 MettaValue::Atom("assertEqualMsg".to_string()),
                 args[0].clone(),
@@ -137,7 +240,6 @@ pub(super) fn eval_assert_alpha_equal_msg(items: Vec<MettaValue>, env: Environme
     require_args_with_usage!(
         "assertAlphaEqualMsg",
         items,
-        3,
         env,
         "(assertAlphaEqualMsg actual expected message)"
     );
@@ -146,7 +248,7 @@ pub(super) fn eval_assert_alpha_equal_msg(items: Vec<MettaValue>, env: Environme
     let (expected_results, env_after_expected) = eval(args[1].clone(), env_after_actual);
 
     if results_are_alpha_equal(&actual_results, &expected_results) {
-        (vec![MettaValue::Nil], env_after_expected)
+        (vec![MettaValue::SExpr(vec![])], env_after_expected)
     } else {
         let msg_str = match &args[2] {
             MettaValue::String(s) | MettaValue::Atom(s) => s.clone(),
@@ -176,7 +278,6 @@ pub(super) fn eval_assert_equal_to_result(items: Vec<MettaValue>, env: Environme
     require_args_with_usage!(
         "assertEqualToResult",
         items,
-        2,
         env,
         "(assertEqualToResult actual expected-results)"
     );
@@ -185,7 +286,7 @@ pub(super) fn eval_assert_equal_to_result(items: Vec<MettaValue>, env: Environme
     let expected_as_results = vec![args[1].clone()];
 
     if results_are_equal(&actual_results, &expected_as_results) {
-        (vec![MettaValue::Nil], env_after_actual)
+        (vec![MettaValue::SExpr(vec![])], env_after_actual)
     } else {
         let err = MettaValue::Error(
             format!(
@@ -216,7 +317,6 @@ pub(super) fn eval_assert_alpha_equal_to_result(
     require_args_with_usage!(
         "assertAlphaEqualToResult",
         items,
-        2,
         env,
         "(assertAlphaEqualToResult actual expected-results)"
     );
@@ -225,7 +325,7 @@ pub(super) fn eval_assert_alpha_equal_to_result(
     let expected_as_results = vec![args[1].clone()];
 
     if results_are_alpha_equal(&actual_results, &expected_as_results) {
-        (vec![MettaValue::Nil], env_after_actual)
+        (vec![MettaValue::SExpr(vec![])], env_after_actual)
     } else {
         let err = MettaValue::Error(
             format!(
@@ -255,7 +355,6 @@ pub(super) fn eval_assert_equal_to_result_msg(
     require_args_with_usage!(
         "assertEqualToResultMsg",
         items,
-        3,
         env,
         "(assertEqualToResultMsg actual expected-results message)"
     );
@@ -264,7 +363,7 @@ pub(super) fn eval_assert_equal_to_result_msg(
     let expected_as_results = vec![args[1].clone()];
 
     if results_are_equal(&actual_results, &expected_as_results) {
-        (vec![MettaValue::Nil], env_after_actual)
+        (vec![MettaValue::SExpr(vec![])], env_after_actual)
     } else {
         let msg_str = match &args[2] {
             MettaValue::String(s) | MettaValue::Atom(s) => s.clone(),
@@ -296,7 +395,6 @@ pub(super) fn eval_assert_alpha_equal_to_result_msg(
     require_args_with_usage!(
         "assertAlphaEqualToResultMsg",
         items,
-        3,
         env,
         "(assertAlphaEqualToResultMsg actual expected-results message)"
     );
@@ -305,7 +403,7 @@ pub(super) fn eval_assert_alpha_equal_to_result_msg(
     let expected_as_results = vec![args[1].clone()];
 
     if results_are_alpha_equal(&actual_results, &expected_as_results) {
-        (vec![MettaValue::Nil], env_after_actual)
+        (vec![MettaValue::SExpr(vec![])], env_after_actual)
     } else {
         let msg_str = match &args[2] {
             MettaValue::String(s) | MettaValue::Atom(s) => s.clone(),
@@ -422,6 +520,7 @@ fn alpha_equivalent_with_mappings(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::backend::run::eval_last;
 
     #[test]
     fn test_assert_equal_success_with_literals() {
@@ -436,7 +535,7 @@ mod tests {
 
         let (results, _) = eval(value, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -472,7 +571,7 @@ mod tests {
 
         let (results, _) = eval(value, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -489,7 +588,7 @@ mod tests {
 
         let (results, _) = eval(value, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -509,12 +608,51 @@ mod tests {
 
         match &results[0] {
             MettaValue::Error(msg, _) => {
-                assert_eq!(msg, "Custom error message");
+                assert_eq!(
+                    msg,
+                    "Custom error message\nExpected: [Long(10)]\nActual: [Long(5)]"
+                );
             }
             _ => panic!("Expected error result"),
         }
     }
 
+    #[test]
+    fn test_assert_equal_msg_message_must_be_string() {
+        let env = Environment::new();
+        let assert_with = |message: MettaValue| {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("assertEqualMsg".to_string()),
+                MettaValue::Long(5),
+                MettaValue::Long(10),
+                message,
+            ])
+        };
+
+        // A message computed by an expression is used once evaluated
+        let (results, env) = eval(
+            assert_with(MettaValue::SExpr(vec![
+                MettaValue::Atom("format".to_string()),
+                MettaValue::String("case {}".to_string()),
+                MettaValue::Long(7),
+            ])),
+            env,
+        );
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg.starts_with("case 7\n")),
+            "{:?}",
+            results
+        );
+
+        // Anything else is rejected
+        let (results, _) = eval(assert_with(MettaValue::Long(3)), env);
+        assert!(
+            matches!(&results[0], MettaValue::Error(msg, _) if msg.contains("message must be a string")),
+            "{:?}",
+            results
+        );
+    }
+
     #[test]
     fn test_assert_equal_with_complex_expressions() {
         let env = Environment::new();
@@ -536,7 +674,7 @@ mod tests {
 
         let (results, _) = eval(value, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -557,7 +695,7 @@ mod tests {
 
         let (results, _) = eval(value, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -621,7 +759,7 @@ mod tests {
 
         let (results, _) = eval(value, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -795,7 +933,7 @@ mod tests {
 
         let (results, _) = eval_assert_alpha_equal(items, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -840,7 +978,7 @@ mod tests {
 
         let (results, _) = eval_assert_alpha_equal_msg(items, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -889,7 +1027,7 @@ mod tests {
 
         let (results, _) = eval_assert_alpha_equal_to_result(items, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -936,7 +1074,7 @@ mod tests {
 
         let (results, _) = eval_assert_alpha_equal_to_result_msg(items, env);
         assert_eq!(results.len(), 1);
-        assert_eq!(results[0], MettaValue::Nil);
+        assert_eq!(results[0], MettaValue::SExpr(vec![]));
     }
 
     #[test]
@@ -966,4 +1104,49 @@ mod tests {
             _ => panic!("Expected error result, got: {:?}", results[0]),
         }
     }
+
+    fn assert_failed(results: &[MettaValue], op: &str) {
+        match results {
+            [MettaValue::Error(msg, details)] => {
+                assert!(msg.starts_with("Assertion failed"), "{}", msg);
+                match details.as_ref() {
+                    MettaValue::SExpr(items) => {
+                        assert_eq!(items[0], MettaValue::Atom(op.to_string()))
+                    }
+                    other => panic!("Expected {} details, got {:?}", op, other),
+                }
+            }
+            other => panic!("Expected an assertion error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_assert() {
+        assert_eq!(
+            eval_last("!(assert (< 1 2))"),
+            vec![MettaValue::SExpr(vec![])]
+        );
+        assert_failed(&eval_last("!(assert (> 1 2))"), "assert");
+        // Not a boolean at all
+        assert_failed(&eval_last("!(assert 5)"), "assert");
+        // Every nondeterministic result must hold
+        assert_failed(&eval_last("!(assert (superpose (True False)))"), "assert");
+    }
+
+    #[test]
+    fn test_assert_includes() {
+        let src = "(= (color) red)\n(= (color) green)\n(= (color) blue)\n";
+        assert_eq!(
+            eval_last(&format!("{}!(assertIncludes (color) green)", src)),
+            vec![MettaValue::SExpr(vec![])]
+        );
+        assert_eq!(
+            eval_last("!(assertIncludes (superpose (1 2 3)) (+ 1 1))"),
+            vec![MettaValue::SExpr(vec![])]
+        );
+        assert_failed(
+            &eval_last(&format!("{}!(assertIncludes (color) purple)", src)),
+            "assertIncludes",
+        );
+    }
 }
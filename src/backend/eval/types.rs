@@ -3,6 +3,9 @@ use crate::backend::models::{EvalResult, MettaValue};
 use std::sync::Arc;
 use tracing::trace;
 
+/// Type of atoms with no type assertion and no built-in type, as in hyperon
+const UNDEFINED_TYPE: &str = "%Undefined%";
+
 /// Built-in type names with correct capitalization for "Did you mean?" suggestions
 const TYPE_NAME_MAPPINGS: &[(&str, &str)] = &[
     ("bool", "Bool"),
@@ -46,7 +49,7 @@ pub(crate) fn suggest_type_name(name: &str) -> Option<String> {
 /// Adds a type assertion to the environment
 pub(super) fn eval_type_assertion(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_type_assertion", ?items);
-    require_args_with_usage!(":", items, 2, env, "(: expr type)");
+    require_args_with_usage!(":", items, env, "(: expr type)");
 
     let expr = &items[1];
     let typ = items[2].clone();
@@ -76,11 +79,25 @@ pub(super) fn eval_type_assertion(items: Vec<MettaValue>, env: Environment) -> E
 
 /// get-type: return the type of an expression
 /// (get-type expr) -> Type
+///
+/// Symbols with neither a `(: sym type)` assertion nor a built-in type, and calls
+/// whose type cannot be inferred, have type `%Undefined%`. A symbol with several
+/// assertions has all of them as results.
 pub(super) fn eval_get_type(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_get_type", ?items);
-    require_args_with_usage!("get-type", items, 1, env, "(get-type expr)");
+    require_args_with_usage!("get-type", items, env, "(get-type expr)");
 
     let expr = &items[1];
+    if let MettaValue::Atom(name) = expr {
+        let is_variable = name.starts_with('$') || name.starts_with('\'');
+        if !is_variable {
+            let types = env.get_types(name);
+            if types.len() > 1 {
+                return (types, env);
+            }
+        }
+    }
+
     let typ = infer_type(expr, &env);
     (vec![typ], env)
 }
@@ -89,7 +106,7 @@ pub(super) fn eval_get_type(items: Vec<MettaValue>, env: Environment) -> EvalRes
 /// (check-type expr expected-type) -> Bool
 pub(super) fn eval_check_type(items: Vec<MettaValue>, env: Environment) -> EvalResult {
     trace!(target: "mettatron::eval::eval_check_type", ?items);
-    require_args_with_usage!("check-type", items, 2, env, "(check-type expr type)");
+    require_args_with_usage!("check-type", items, env, "(check-type expr type)");
 
     let expr = &items[1];
     let expected = &items[2];
@@ -119,8 +136,8 @@ fn infer_type(expr: &MettaValue, env: &Environment) -> MettaValue {
 
         // For atoms, look up in environment
         MettaValue::Atom(name) => {
-            // Check if it's a variable (starts with $, &, or ')
-            if name.starts_with('$') || name.starts_with('&') || name.starts_with('\'') {
+            // Check if it's a variable (starts with $ or ')
+            if name.starts_with('$') || name.starts_with('\'') {
                 // Type variable - return as-is wrapped in Type
                 return MettaValue::Type(Arc::new(MettaValue::Atom(name.clone())));
             }
@@ -135,7 +152,7 @@ fn infer_type(expr: &MettaValue, env: &Environment) -> MettaValue {
                         // Note: We could return a more detailed error here,
                         // but keeping Undefined for backward compatibility
                     }
-                    MettaValue::Atom("Undefined".to_string())
+                    MettaValue::Atom(UNDEFINED_TYPE.to_string())
                 }
             }
         }
@@ -163,10 +180,16 @@ fn infer_type(expr: &MettaValue, env: &Environment) -> MettaValue {
                     _ => {
                         // Look up function type in environment
                         if let Some(func_type) = env.get_type(op) {
-                            // If it's an arrow type, extract return type
+                            // If it's an arrow type, check the arguments against the
+                            // parameter types and extract the return type
                             if let MettaValue::SExpr(ref type_items) = func_type {
                                 if let Some(MettaValue::Atom(arrow)) = type_items.first() {
                                     if arrow == "->" && type_items.len() > 1 {
+                                        let params = &type_items[1..type_items.len() - 1];
+                                        if !call_args_match(&items[1..], params, env) {
+                                            // Ill-typed call: it has no type
+                                            return MettaValue::Atom(UNDEFINED_TYPE.to_string());
+                                        }
                                         // Return type is last element
                                         return type_items.last().cloned().unwrap();
                                     }
@@ -179,7 +202,7 @@ fn infer_type(expr: &MettaValue, env: &Environment) -> MettaValue {
             }
 
             // Can't infer type
-            MettaValue::Atom("Undefined".to_string())
+            MettaValue::Atom(UNDEFINED_TYPE.to_string())
         }
 
         // Conjunctions have Conjunction type
@@ -196,6 +219,25 @@ fn infer_type(expr: &MettaValue, env: &Environment) -> MettaValue {
     }
 }
 
+/// Check the arguments of a call against the parameter types of its signature
+/// The arity must match; arguments whose type is unknown (untyped symbols, variables)
+/// are accepted for any parameter type
+fn call_args_match(args: &[MettaValue], params: &[MettaValue], env: &Environment) -> bool {
+    if args.len() != params.len() {
+        return false;
+    }
+    args.iter().zip(params).all(|(arg, param)| {
+        let actual = infer_type(arg, env);
+        match &actual {
+            MettaValue::Atom(name) if name == UNDEFINED_TYPE => true,
+            MettaValue::Type(var) if matches!(var.as_ref(), MettaValue::Atom(v) if v.starts_with('$')) => {
+                true
+            }
+            _ => types_match(&actual, param),
+        }
+    })
+}
+
 /// Check if two types match
 /// Handles type variables and structural equality
 fn types_match(actual: &MettaValue, expected: &MettaValue) -> bool {
@@ -254,6 +296,7 @@ fn types_match(actual: &MettaValue, expected: &MettaValue) -> bool {
 mod tests {
     use super::*;
     use crate::backend::models::Rule;
+    use crate::backend::run::eval_last;
     use crate::eval;
 
     #[test]
@@ -274,6 +317,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_get_type_untyped_symbol_is_undefined() {
+        let env = Environment::new();
+        let undefined = MettaValue::Atom("%Undefined%".to_string());
+
+        // (get-type mystery) - no assertion, no built-in type
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("get-type".to_string()),
+            MettaValue::Atom("mystery".to_string()),
+        ]);
+        let (results, env) = eval(value, env);
+        assert_eq!(results, vec![undefined.clone()]);
+
+        // (get-type (mystery 1)) - a call to an untyped head
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("get-type".to_string()),
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("mystery".to_string()),
+                MettaValue::Long(1),
+            ]),
+        ]);
+        let (results, _) = eval(value, env);
+        assert_eq!(results, vec![undefined]);
+    }
+
+    #[test]
+    fn test_type_assertions_accumulate_for_get_type() {
+        let (forms, env) = crate::backend::run::eval_forms(
+            r#"
+            (: Socrates Human)
+            !(get-type Socrates)
+            (: Socrates Philosopher)
+            !(get-type Socrates)
+        "#,
+            Environment::new(),
+        );
+        let outputs: Vec<_> = forms.into_iter().filter(|r| !r.is_empty()).collect();
+
+        let human = MettaValue::Atom("Human".to_string());
+        let philosopher = MettaValue::Atom("Philosopher".to_string());
+        assert_eq!(outputs[0], vec![human.clone()]);
+        assert_eq!(outputs[1].len(), 2);
+        assert!(outputs[1].contains(&human));
+        assert!(outputs[1].contains(&philosopher));
+        assert_eq!(env.get_types("Socrates").len(), 2);
+    }
+
     #[test]
     fn test_get_type_missing_argument() {
         let env = Environment::new();
@@ -502,17 +592,17 @@ mod tests {
         env = new_env;
 
         // Add rule: (= (double $x) (mul $x 2))
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("double".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("*".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Long(2),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Check type of double
@@ -591,4 +681,55 @@ mod tests {
             other => panic!("Expected Error, got {:?}", other),
         }
     }
+
+    #[test]
+    fn test_check_type_call_matches_signature() {
+        let results = eval_last(
+            r#"
+            (: inc (-> Number Number))
+            (= (inc $x) (+ $x 1))
+            !(check-type (inc 3) Number)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Bool(true)]);
+
+        // Nested calls are checked through their inferred return types
+        let results = eval_last(
+            r#"
+            (: inc (-> Number Number))
+            !(check-type (inc (inc 3)) Number)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Bool(true)]);
+    }
+
+    #[test]
+    fn test_check_type_call_violates_signature() {
+        // Wrong argument type
+        let results = eval_last(
+            r#"
+            (: inc (-> Number Number))
+            !(check-type (inc "three") Number)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Bool(false)]);
+
+        // Wrong arity
+        let results = eval_last(
+            r#"
+            (: inc (-> Number Number))
+            !(check-type (inc 1 2) Number)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Bool(false)]);
+
+        // Well-typed call, but the declared result type differs
+        let results = eval_last(
+            r#"
+            (: inc (-> Number Number))
+            !(check-type (inc 3) String)
+            "#,
+        );
+        assert_eq!(results, vec![MettaValue::Bool(false)]);
+    }
 }
@@ -0,0 +1,147 @@
+// Execution metrics for profiling evaluation
+//
+// Collection is off by default; when disabled each hook costs a single relaxed atomic
+// load. The collector is shared by all clones of an Environment, so counts from nested
+// and forked evaluations accumulate in one place.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+
+/// Snapshot of execution metrics collected during evaluation
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EvalMetrics {
+    /// Evaluation steps executed by the trampoline (one per evaluated value)
+    pub steps: u64,
+    /// Rule dispatches that matched more than one rule and so fanned out
+    pub choice_points: u64,
+    /// Rule lookups performed for expressions that were not special forms or builtins
+    pub rule_dispatches: u64,
+    /// Deepest evaluation depth reached
+    pub max_depth: usize,
+    /// `collapse` forms answered from the collapse cache without evaluating again
+    pub collapse_cache_hits: u64,
+}
+
+impl fmt::Display for EvalMetrics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Execution metrics:")?;
+        writeln!(f, "  steps:           {}", self.steps)?;
+        writeln!(f, "  choice points:   {}", self.choice_points)?;
+        writeln!(f, "  rule dispatches: {}", self.rule_dispatches)?;
+        writeln!(f, "  max depth:       {}", self.max_depth)?;
+        write!(f, "  collapse hits:   {}", self.collapse_cache_hits)
+    }
+}
+
+/// Thread-safe metrics collector updated by the evaluator
+#[derive(Debug, Default)]
+pub struct MetricsCollector {
+    enabled: AtomicBool,
+    steps: AtomicU64,
+    choice_points: AtomicU64,
+    rule_dispatches: AtomicU64,
+    max_depth: AtomicUsize,
+    collapse_cache_hits: AtomicU64,
+}
+
+impl MetricsCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set_enabled(&self, enabled: bool) {
+        self.enabled.store(enabled, Ordering::Relaxed);
+    }
+
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Relaxed)
+    }
+
+    /// Record one evaluation step at the given depth
+    #[inline]
+    pub fn record_step(&self, depth: usize) {
+        if self.is_enabled() {
+            self.steps.fetch_add(1, Ordering::Relaxed);
+            self.max_depth.fetch_max(depth, Ordering::Relaxed);
+        }
+    }
+
+    /// Record a rule lookup that found `matches` matching rules
+    #[inline]
+    pub fn record_rule_dispatch(&self, matches: usize) {
+        if self.is_enabled() {
+            self.rule_dispatches.fetch_add(1, Ordering::Relaxed);
+            if matches > 1 {
+                self.choice_points.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    /// Record a `collapse` answered from the collapse cache
+    #[inline]
+    pub fn record_collapse_cache_hit(&self) {
+        if self.is_enabled() {
+            self.collapse_cache_hits.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    pub fn snapshot(&self) -> EvalMetrics {
+        EvalMetrics {
+            steps: self.steps.load(Ordering::Relaxed),
+            choice_points: self.choice_points.load(Ordering::Relaxed),
+            rule_dispatches: self.rule_dispatches.load(Ordering::Relaxed),
+            max_depth: self.max_depth.load(Ordering::Relaxed),
+            collapse_cache_hits: self.collapse_cache_hits.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Zero all counters (the enabled flag is left unchanged)
+    pub fn reset(&self) {
+        self.steps.store(0, Ordering::Relaxed);
+        self.choice_points.store(0, Ordering::Relaxed);
+        self.rule_dispatches.store(0, Ordering::Relaxed);
+        self.max_depth.store(0, Ordering::Relaxed);
+        self.collapse_cache_hits.store(0, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_collector_records_nothing() {
+        let metrics = MetricsCollector::new();
+        metrics.record_step(3);
+        metrics.record_rule_dispatch(2);
+        metrics.record_collapse_cache_hit();
+        assert_eq!(metrics.snapshot(), EvalMetrics::default());
+    }
+
+    #[test]
+    fn test_enabled_collector_counts() {
+        let metrics = MetricsCollector::new();
+        metrics.set_enabled(true);
+        metrics.record_step(1);
+        metrics.record_step(4);
+        metrics.record_step(2);
+        metrics.record_rule_dispatch(1);
+        metrics.record_rule_dispatch(3);
+        metrics.record_collapse_cache_hit();
+        assert_eq!(
+            metrics.snapshot(),
+            EvalMetrics {
+                steps: 3,
+                choice_points: 1,
+                rule_dispatches: 2,
+                max_depth: 4,
+                collapse_cache_hits: 1,
+            }
+        );
+
+        metrics.reset();
+        assert_eq!(metrics.snapshot(), EvalMetrics::default());
+        assert!(metrics.is_enabled());
+    }
+}
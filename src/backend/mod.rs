@@ -9,11 +9,21 @@ pub mod compile;
 pub mod environment;
 pub mod eval;
 pub mod fuzzy_match;
+pub mod metrics;
 pub mod models;
 pub mod mork_convert;
+pub mod random;
+pub mod run;
+pub mod stdlib;
 
-pub use compile::compile;
-pub use environment::Environment;
-pub use eval::{eval, pattern_match};
+pub use compile::{compile, compile_checked, compile_incremental, CompileError};
+pub use environment::{
+    EnvSnapshot, EnvStats, Environment, InternStats, RuleTraceSink, TypeMismatch,
+};
+pub use eval::{
+    eval, flatten_conjunction_results, flatten_nested_conjunction_results, pattern_match,
+};
 pub use fuzzy_match::FuzzyMatcher;
+pub use metrics::EvalMetrics;
 pub use models::*;
+pub use run::{eval_source, eval_source_in, RunConfig};
@@ -119,6 +119,18 @@ impl SmartBindings {
     pub fn is_empty(&self) -> bool {
         matches!(self, SmartBindings::Empty)
     }
+
+    /// Represent the bindings as a MeTTa value: an S-expression of `($var value)` pairs
+    /// in binding order, e.g. `(($x 1) ($y foo))`. Empty bindings become `()`.
+    pub fn to_metta_value(&self) -> MettaValue {
+        MettaValue::SExpr(
+            self.iter()
+                .map(|(name, value)| {
+                    MettaValue::SExpr(vec![MettaValue::Atom(name.clone()), value.clone()])
+                })
+                .collect(),
+        )
+    }
 }
 
 impl Default for SmartBindings {
@@ -256,4 +268,29 @@ mod tests {
         assert_eq!(collected[0].0, "$x");
         assert_eq!(*collected[0].1, MettaValue::Long(42));
     }
+
+    #[test]
+    fn test_to_metta_value() {
+        assert_eq!(
+            SmartBindings::new().to_metta_value(),
+            MettaValue::SExpr(vec![])
+        );
+
+        let mut bindings = SmartBindings::new();
+        bindings.insert("$x".to_string(), MettaValue::Long(1));
+        bindings.insert("$y".to_string(), MettaValue::Atom("foo".to_string()));
+        assert_eq!(
+            bindings.to_metta_value(),
+            MettaValue::SExpr(vec![
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("$x".to_string()),
+                    MettaValue::Long(1)
+                ]),
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("$y".to_string()),
+                    MettaValue::Atom("foo".to_string())
+                ]),
+            ])
+        );
+    }
 }
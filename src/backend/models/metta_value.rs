@@ -1,5 +1,8 @@
 use crate::ir::MettaExpr;
 
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 /// Represents a MeTTa value as an s-expression
@@ -30,7 +33,52 @@ pub enum MettaValue {
     Conjunction(Vec<MettaValue>),
 }
 
+/// Category of an error value, for matching on failures programmatically
+///
+/// Errors keep their `(Error message details)` shape; the kind is derived from the
+/// details atom attached where the error is created (`ArityError`, `TypeError`,
+/// `DivisionByZero`, `ArithmeticError`, `ValueError`, `UnknownSymbol`), never from
+/// the message text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Wrong number of arguments to a builtin or special form
+    ArityMismatch,
+    /// Argument of the wrong type
+    TypeError,
+    /// Integer division or modulo by zero
+    DivByZero,
+    /// Other arithmetic failures such as overflow or a math domain error
+    Arithmetic,
+    /// Argument of the right type but an invalid value
+    InvalidValue,
+    /// Call to a symbol that has no matching rule (reported with a suggestion)
+    UnknownSymbol,
+    /// Any other error, including user errors raised with `(error ...)`
+    Runtime,
+}
+
 impl MettaValue {
+    /// Classify an error value; returns None for non-error values
+    pub fn error_kind(&self) -> Option<ErrorKind> {
+        let MettaValue::Error(_, details) = self else {
+            return None;
+        };
+
+        let kind = match details.as_ref() {
+            MettaValue::Atom(tag) => match tag.as_str() {
+                "ArityError" => ErrorKind::ArityMismatch,
+                "TypeError" => ErrorKind::TypeError,
+                "DivisionByZero" => ErrorKind::DivByZero,
+                "ArithmeticError" => ErrorKind::Arithmetic,
+                "ValueError" => ErrorKind::InvalidValue,
+                "UnknownSymbol" => ErrorKind::UnknownSymbol,
+                _ => ErrorKind::Runtime,
+            },
+            _ => ErrorKind::Runtime,
+        };
+        Some(kind)
+    }
+
     /// Create a quoted expression: (quote inner)
     ///
     /// Returns a quote special form that prevents evaluation of the inner expression.
@@ -46,6 +94,14 @@ impl MettaValue {
         MettaValue::SExpr(vec![MettaValue::Atom("quote".to_string()), inner])
     }
 
+    /// Check if an atom name is an anonymous wildcard: `_` or `$_`
+    ///
+    /// Wildcards match anything and never bind, so two wildcards in one pattern are
+    /// independent, whereas a repeated named variable like `$x` must match equal values.
+    pub fn is_wildcard_name(name: &str) -> bool {
+        name == "_" || name == "$_"
+    }
+
     /// Check if this value is a ground type (non-reducible literal)
     /// Ground types: Bool, Long, Float, String, Nil
     /// Returns true if the value doesn't require further evaluation
@@ -97,20 +153,21 @@ impl MettaValue {
     pub fn structurally_equivalent(&self, other: &MettaValue) -> bool {
         match (self, other) {
             // Variables match any other variable (names don't matter)
-            // EXCEPT: standalone "&" is a literal operator (used in match), not a variable
             (MettaValue::Atom(a), MettaValue::Atom(b))
-                if (a.starts_with('$') || a.starts_with('&') || a.starts_with('\''))
-                    && (b.starts_with('$') || b.starts_with('&') || b.starts_with('\''))
-                    && a != "&"
-                    && b != "&" =>
+                if (a.starts_with('$') || a.starts_with('\''))
+                    && (b.starts_with('$') || b.starts_with('\'')) =>
             {
                 true
             }
 
             // Wildcards match wildcards
-            (MettaValue::Atom(a), MettaValue::Atom(b)) if a == "_" && b == "_" => true,
+            (MettaValue::Atom(a), MettaValue::Atom(b))
+                if Self::is_wildcard_name(a) && Self::is_wildcard_name(b) =>
+            {
+                true
+            }
 
-            // Non-variable atoms must match exactly (including standalone "&")
+            // Non-variable atoms must match exactly
             (MettaValue::Atom(a), MettaValue::Atom(b)) => a == b,
 
             // Other ground types must match exactly
@@ -159,25 +216,17 @@ impl MettaValue {
     pub fn get_head_symbol(&self) -> Option<&str> {
         match self {
             // For s-expressions like (double $x), extract "double"
-            // EXCEPT: standalone "&" is allowed as a head symbol (used in match)
             MettaValue::SExpr(items) if !items.is_empty() => match &items[0] {
                 MettaValue::Atom(head)
-                    if !head.starts_with('$')
-                        && (!head.starts_with('&') || head == "&")
-                        && !head.starts_with('\'')
-                        && head != "_" =>
+                    if !head.starts_with('$') && !head.starts_with('\'') && head != "_" =>
                 {
                     Some(head.as_str())
                 }
                 _ => None,
             },
             // For bare atoms like foo, use the atom itself
-            // EXCEPT: standalone "&" is allowed as a head symbol (used in match)
             MettaValue::Atom(head)
-                if !head.starts_with('$')
-                    && (!head.starts_with('&') || head == "&")
-                    && !head.starts_with('\'')
-                    && head != "_" =>
+                if !head.starts_with('$') && !head.starts_with('\'') && head != "_" =>
             {
                 Some(head.as_str())
             }
@@ -190,14 +239,7 @@ impl MettaValue {
     pub fn pattern_specificity(&self) -> usize {
         match self {
             // Variables are least specific
-            // EXCEPT: standalone "&" is a literal operator (used in match), not a variable
-            MettaValue::Atom(s)
-                if (s.starts_with('$')
-                    || s.starts_with('&')
-                    || s.starts_with('\'')
-                    || s == "_")
-                    && s != "&" =>
-            {
+            MettaValue::Atom(s) if s.starts_with('$') || s.starts_with('\'') || s == "_" => {
                 1000 // Variables are least specific
             }
             MettaValue::Atom(_)
@@ -206,7 +248,7 @@ impl MettaValue {
             | MettaValue::Float(_)
             | MettaValue::String(_)
             | MettaValue::Nil => {
-                0 // Literals are most specific (including standalone "&")
+                0 // Literals are most specific
             }
             MettaValue::SExpr(items) => {
                 // Sum specificity of all items
@@ -239,11 +281,10 @@ impl MettaValue {
         match self {
             MettaValue::Atom(s) => {
                 // Variables need to start with $ in MORK format
-                // EXCEPT: standalone "&" is a literal operator (used in match), not a variable
-                if (s.starts_with('$') || s.starts_with('&') || s.starts_with('\'')) && s != "&" {
-                    format!("${}", &s[1..]) // Keep $ prefix, remove original prefix
-                } else if s == "_" {
+                if Self::is_wildcard_name(s) {
                     "$".to_string() // Wildcard becomes $
+                } else if s.starts_with('$') || s.starts_with('\'') {
+                    format!("${}", &s[1..]) // Keep $ prefix, remove original prefix
                 } else {
                     s.clone()
                 }
@@ -354,12 +395,17 @@ pub fn escape_json(s: &str) -> String {
 }
 
 // Implement Eq for MettaValue (required for HashMap keys)
-// Note: Float uses bit-level comparison for hashing purposes
+//
+// Float handling: equality follows IEEE 754 (the derived PartialEq), so
+// `0.0 == -0.0` and NaN is never equal to anything, including itself. Hashing
+// uses the bit pattern with zero normalized, which keeps `a == b => hash(a) == hash(b)`.
+// A key containing NaN is therefore never found again in a map - memoization
+// simply misses for such terms instead of returning a wrong entry.
 impl Eq for MettaValue {}
 
 // Implement Hash for MettaValue to enable use as HashMap key
-impl std::hash::Hash for MettaValue {
-    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+impl Hash for MettaValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
         match self {
             MettaValue::Atom(s) => {
                 0u8.hash(state);
@@ -375,8 +421,10 @@ impl std::hash::Hash for MettaValue {
             }
             MettaValue::Float(f) => {
                 3u8.hash(state);
-                // Hash float as its bit representation for deterministic hashing
-                f.to_bits().hash(state);
+                // Hash float as its bit representation for deterministic hashing.
+                // -0.0 and 0.0 compare equal, so they must hash equally too.
+                let normalized = if *f == 0.0 { 0.0f64 } else { *f };
+                normalized.to_bits().hash(state);
             }
             MettaValue::String(s) => {
                 4u8.hash(state);
@@ -406,6 +454,140 @@ impl std::hash::Hash for MettaValue {
     }
 }
 
+impl MettaValue {
+    /// Compute a structural hash of this value
+    ///
+    /// Equal values always produce equal hashes (see the `Hash` impl for float handling).
+    /// The hash walks the whole term, so callers that look up the same large term
+    /// repeatedly should wrap it in a [`HashedValue`] to pay this cost once.
+    pub fn structural_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// A `MettaValue` paired with its precomputed structural hash
+///
+/// Intended as a memoization key: hashing only feeds the cached `u64` to the hasher,
+/// and equality compares the cached hashes before falling back to a full structural
+/// comparison. The `collapse` cache keys its entries on these.
+#[derive(Debug, Clone)]
+pub struct HashedValue {
+    hash: u64,
+    value: MettaValue,
+}
+
+impl HashedValue {
+    /// Wrap a value, computing its structural hash once
+    pub fn new(value: MettaValue) -> Self {
+        let hash = value.structural_hash();
+        HashedValue { hash, value }
+    }
+
+    /// The cached structural hash
+    pub fn structural_hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Borrow the wrapped value
+    pub fn value(&self) -> &MettaValue {
+        &self.value
+    }
+
+    /// Unwrap into the underlying value
+    pub fn into_value(self) -> MettaValue {
+        self.value
+    }
+}
+
+impl PartialEq for HashedValue {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && self.value == other.value
+    }
+}
+
+impl Eq for HashedValue {}
+
+impl Hash for HashedValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
+impl From<MettaValue> for HashedValue {
+    fn from(value: MettaValue) -> Self {
+        HashedValue::new(value)
+    }
+}
+
+/// Canonical textual form of a value, compatible with the MeTTa parser
+///
+/// Strings are quoted and escaped, booleans print as `True`/`False`, floats always
+/// carry a decimal point so they re-parse as floats, and errors print as
+/// `(Error "msg" details)` with the message quoted, which the reader turns back into
+/// the same error. The empty expression, the unit value returned by side-effecting
+/// forms such as `nop` and `assertEqual`, prints as `()`, and so does `Nil`. Types print as
+/// `Type(...)`, which has no surface syntax. `Empty` (the outcome of `(empty)`) is the
+/// absence of results rather than a value, so it has no printed form.
+impl fmt::Display for MettaValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MettaValue::Atom(s) => write!(f, "{}", s),
+            MettaValue::Bool(true) => write!(f, "True"),
+            MettaValue::Bool(false) => write!(f, "False"),
+            MettaValue::Long(n) => write!(f, "{}", n),
+            MettaValue::Float(x) => {
+                let text = x.to_string();
+                if x.is_finite() && !text.contains(['.', 'e', 'E']) {
+                    write!(f, "{}.0", text)
+                } else {
+                    write!(f, "{}", text)
+                }
+            }
+            MettaValue::String(s) => write!(f, "\"{}\"", escape_metta_string(s)),
+            MettaValue::Nil => write!(f, "()"),
+            MettaValue::Error(msg, details) => {
+                write!(f, "(Error \"{}\" {})", escape_metta_string(msg), details)
+            }
+            MettaValue::Type(t) => write!(f, "Type({})", t),
+            MettaValue::SExpr(items) => {
+                write!(f, "(")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, " ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, ")")
+            }
+            MettaValue::Conjunction(goals) => {
+                write!(f, "(,")?;
+                for goal in goals {
+                    write!(f, " {}", goal)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+/// Escape a string so that the parser reads it back unchanged
+fn escape_metta_string(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
 impl TryFrom<&MettaExpr> for MettaValue {
     type Error = String;
 
@@ -434,6 +616,16 @@ impl TryFrom<&MettaExpr> for MettaValue {
                         .first()
                         .is_some_and(|first| matches!(first, MettaExpr::Atom(s, _) if s == ","));
 
+                    if let [MettaExpr::Atom(head, _), MettaExpr::String(msg, _), details] =
+                        items.as_slice()
+                    {
+                        // (Error "msg" details) is how errors print; read it back as one
+                        if head == "Error" {
+                            let details = MettaValue::try_from(details)?;
+                            return Ok(MettaValue::Error(msg.clone(), Arc::new(details)));
+                        }
+                    }
+
                     if is_conjunction {
                         // Convert to Conjunction variant (skip the comma operator)
                         let goals: Result<Vec<_>, _> =
@@ -723,10 +915,6 @@ mod tests {
     #[test]
     fn test_structurally_equivalent_variables_mixed_prefixes() {
         // Variables with different prefixes still match
-        let v1 = MettaValue::Atom("$x".to_string());
-        let v2 = MettaValue::Atom("&y".to_string());
-        assert!(v1.structurally_equivalent(&v2));
-
         let v3 = MettaValue::Atom("'a".to_string());
         let v4 = MettaValue::Atom("$b".to_string());
         assert!(v3.structurally_equivalent(&v4));
@@ -741,6 +929,11 @@ mod tests {
 
         // Standalone "&" matches itself
         assert!(op.structurally_equivalent(&op));
+
+        // Nor is a token reference such as &self
+        let space = MettaValue::Atom("&self".to_string());
+        assert!(!space.structurally_equivalent(&var));
+        assert!(space.structurally_equivalent(&space));
     }
 
     #[test]
@@ -877,7 +1070,6 @@ mod tests {
     fn test_get_head_symbol_variable_atom() {
         // Variables cannot be head symbols
         assert_eq!(MettaValue::Atom("$x".to_string()).get_head_symbol(), None);
-        assert_eq!(MettaValue::Atom("&y".to_string()).get_head_symbol(), None);
         assert_eq!(MettaValue::Atom("'z".to_string()).get_head_symbol(), None);
     }
 
@@ -1073,9 +1265,9 @@ mod tests {
     }
 
     #[test]
-    fn test_to_mork_string_variable_ampersand() {
-        // & prefix becomes $ in MORK format
-        assert_eq!(MettaValue::Atom("&y".to_string()).to_mork_string(), "$y");
+    fn test_to_mork_string_token_reference() {
+        // &name is a token reference, not a variable, and is kept as written
+        assert_eq!(MettaValue::Atom("&y".to_string()).to_mork_string(), "&y");
     }
 
     #[test]
@@ -1157,12 +1349,12 @@ mod tests {
     }
 
     #[test]
-    fn test_to_mork_string_sexpr_with_ampersand_variable() {
+    fn test_to_mork_string_sexpr_with_token_reference() {
         let value = MettaValue::SExpr(vec![
             MettaValue::Atom("f".to_string()),
             MettaValue::Atom("&y".to_string()),
         ]);
-        assert_eq!(value.to_mork_string(), "(f $y)");
+        assert_eq!(value.to_mork_string(), "(f &y)");
     }
 
     #[test]
@@ -1240,4 +1432,182 @@ mod tests {
         assert!(json.contains(r#"\""#));
         assert!(json.contains(r#"\\"#));
     }
+
+    #[test]
+    fn test_structural_hash_equal_expressions() {
+        let make = || {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("foo".to_string()),
+                MettaValue::Float(1.5),
+                MettaValue::SExpr(vec![
+                    MettaValue::Long(1),
+                    MettaValue::String("s".to_string()),
+                ]),
+            ])
+        };
+        assert_eq!(make(), make());
+        assert_eq!(make().structural_hash(), make().structural_hash());
+
+        let other = MettaValue::SExpr(vec![MettaValue::Atom("foo".to_string())]);
+        assert_ne!(make().structural_hash(), other.structural_hash());
+    }
+
+    #[test]
+    fn test_structural_hash_float_handling() {
+        // 0.0 == -0.0, so they must hash equally
+        let pos = MettaValue::Float(0.0);
+        let neg = MettaValue::Float(-0.0);
+        assert_eq!(pos, neg);
+        assert_eq!(pos.structural_hash(), neg.structural_hash());
+
+        // NaN is never equal, even to itself
+        let nan = MettaValue::Float(f64::NAN);
+        assert_ne!(nan, nan.clone());
+        assert_ne!(HashedValue::new(nan.clone()), HashedValue::new(nan));
+    }
+
+    #[test]
+    fn test_hashed_value_memoization_hit() {
+        use std::collections::HashMap;
+
+        let mut memo: HashMap<HashedValue, MettaValue> = HashMap::new();
+        let mut computations = 0;
+        let mut call = |arg: MettaValue| {
+            let key = HashedValue::new(arg);
+            memo.entry(key)
+                .or_insert_with_key(|k| {
+                    computations += 1;
+                    MettaValue::SExpr(vec![
+                        MettaValue::Atom("done".to_string()),
+                        k.value().clone(),
+                    ])
+                })
+                .clone()
+        };
+
+        let arg = || {
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("fib".to_string()),
+                MettaValue::Long(30),
+            ])
+        };
+        let first = call(arg());
+        let second = call(arg());
+        assert_eq!(first, second);
+        assert_eq!(computations, 1);
+    }
+
+    #[test]
+    fn test_display_each_variant() {
+        assert_eq!(MettaValue::Atom("foo".to_string()).to_string(), "foo");
+        assert_eq!(MettaValue::Bool(true).to_string(), "True");
+        assert_eq!(MettaValue::Bool(false).to_string(), "False");
+        assert_eq!(MettaValue::Long(-42).to_string(), "-42");
+        assert_eq!(MettaValue::Float(2.5).to_string(), "2.5");
+        assert_eq!(MettaValue::Float(3.0).to_string(), "3.0");
+        assert_eq!(MettaValue::String("hi".to_string()).to_string(), "\"hi\"");
+        assert_eq!(
+            MettaValue::String("a\"b\\c\n".to_string()).to_string(),
+            r#""a\"b\\c\n""#
+        );
+        assert_eq!(MettaValue::Nil.to_string(), "()");
+        assert_eq!(
+            MettaValue::Error("boom".to_string(), Arc::new(MettaValue::Long(0))).to_string(),
+            "(Error \"boom\" 0)"
+        );
+        assert_eq!(
+            MettaValue::Type(Arc::new(MettaValue::Atom("Number".to_string()))).to_string(),
+            "Type(Number)"
+        );
+        assert_eq!(MettaValue::SExpr(vec![]).to_string(), "()");
+        assert_eq!(
+            MettaValue::SExpr(vec![
+                MettaValue::Atom("+".to_string()),
+                MettaValue::Long(1),
+                MettaValue::SExpr(vec![MettaValue::Atom("f".to_string())]),
+            ])
+            .to_string(),
+            "(+ 1 (f))"
+        );
+        assert_eq!(MettaValue::Conjunction(vec![]).to_string(), "(,)");
+        assert_eq!(
+            MettaValue::Conjunction(vec![
+                MettaValue::Atom("a".to_string()),
+                MettaValue::Atom("b".to_string()),
+            ])
+            .to_string(),
+            "(, a b)"
+        );
+    }
+
+    #[test]
+    fn test_display_matches_previous_cli_output() {
+        // Cases the CLI formatted before Display existed; only Bool, whole-number
+        // floats and error messages changed (to True/False, 3.0 and a quoted message)
+        // so the output re-parses correctly
+        let cases = vec![
+            (MettaValue::Long(6), "6"),
+            (MettaValue::Float(1.5), "1.5"),
+            (MettaValue::String("yes".to_string()), "\"yes\""),
+            (MettaValue::Atom("success".to_string()), "success"),
+            (
+                MettaValue::Error(
+                    "Division by zero".to_string(),
+                    Arc::new(MettaValue::Atom("ArithmeticError".to_string())),
+                ),
+                "(Error \"Division by zero\" ArithmeticError)",
+            ),
+            (
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("Cons".to_string()),
+                    MettaValue::Long(1),
+                    MettaValue::Atom("Nil".to_string()),
+                ]),
+                "(Cons 1 Nil)",
+            ),
+        ];
+        for (value, expected) in cases {
+            assert_eq!(value.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_display_round_trips_through_parser() {
+        let value = MettaValue::SExpr(vec![
+            MettaValue::Atom("f".to_string()),
+            MettaValue::Bool(true),
+            MettaValue::Float(2.0),
+            MettaValue::String("say \"hi\"\n".to_string()),
+            MettaValue::Long(-3),
+        ]);
+        let state = crate::backend::compile::compile(&value.to_string()).unwrap();
+        assert_eq!(state.source, vec![value]);
+    }
+
+    #[test]
+    fn test_error_display_round_trips_through_parser() {
+        let error = MettaValue::Error(
+            "bad \"input\"".to_string(),
+            Arc::new(MettaValue::SExpr(vec![
+                MettaValue::Atom("parse".to_string()),
+                MettaValue::SExpr(vec![
+                    MettaValue::String("x y".to_string()),
+                    MettaValue::Long(1),
+                ]),
+                MettaValue::Atom("TypeError".to_string()),
+            ])),
+        );
+        assert_eq!(
+            error.to_string(),
+            r#"(Error "bad \"input\"" (parse ("x y" 1) TypeError))"#
+        );
+
+        let state = crate::backend::compile::compile(&error.to_string()).unwrap();
+        assert_eq!(state.source, vec![error.clone()]);
+
+        // Errors nested in other values round-trip too
+        let wrapped = MettaValue::SExpr(vec![MettaValue::Atom("result".to_string()), error]);
+        let state = crate::backend::compile::compile(&wrapped.to_string()).unwrap();
+        assert_eq!(state.source, vec![wrapped]);
+    }
 }
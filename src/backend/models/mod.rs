@@ -4,16 +4,70 @@ pub mod metta_value;
 
 pub use bindings::SmartBindings as Bindings;
 pub use metta_state::MettaState;
-pub use metta_value::MettaValue;
+pub use metta_value::{ErrorKind, HashedValue, MettaValue};
 
 use crate::backend::environment::Environment;
 
 /// Result of evaluation: (result, new_environment)
 pub type EvalResult = (Vec<MettaValue>, Environment);
 
-/// Represents a pattern matching rule: (= lhs rhs)
+/// Represents a pattern matching rule: (= lhs rhs), or (= lhs rhs where guard)
 #[derive(Debug, Clone)]
 pub struct Rule {
     pub lhs: MettaValue,
     pub rhs: MettaValue,
+    /// Condition the rule needs to fire, evaluated with the bindings of the match;
+    /// the rule only applies if it evaluates to True
+    pub guard: Option<MettaValue>,
+}
+
+impl Rule {
+    /// An unguarded rule: (= lhs rhs)
+    pub fn new(lhs: MettaValue, rhs: MettaValue) -> Self {
+        Rule {
+            lhs,
+            rhs,
+            guard: None,
+        }
+    }
+
+    /// A rule that only fires when `guard` evaluates to True: (= lhs rhs where guard)
+    pub fn guarded(lhs: MettaValue, rhs: MettaValue, guard: MettaValue) -> Self {
+        Rule {
+            lhs,
+            rhs,
+            guard: Some(guard),
+        }
+    }
+
+    /// The rule as the expression it is stored as: `(= lhs rhs)` or
+    /// `(= lhs rhs where guard)`
+    pub fn to_sexpr(&self) -> MettaValue {
+        let mut items = vec![
+            MettaValue::Atom("=".to_string()),
+            self.lhs.clone(),
+            self.rhs.clone(),
+        ];
+        if let Some(guard) = &self.guard {
+            items.push(MettaValue::Atom("where".to_string()));
+            items.push(guard.clone());
+        }
+        MettaValue::SExpr(items)
+    }
+
+    /// Read a rule back from the expression `to_sexpr` produces
+    pub fn from_sexpr(value: &MettaValue) -> Option<Rule> {
+        match value {
+            MettaValue::SExpr(items) if matches!(&items[..], [MettaValue::Atom(op), ..] if op == "=") => {
+                match &items[1..] {
+                    [lhs, rhs] => Some(Rule::new(lhs.clone(), rhs.clone())),
+                    [lhs, rhs, MettaValue::Atom(kw), guard] if kw == "where" => {
+                        Some(Rule::guarded(lhs.clone(), rhs.clone(), guard.clone()))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
 }
@@ -87,10 +87,11 @@ fn write_metta_value(
     match value {
         MettaValue::Atom(name) => {
             // Check if it's a variable
-            // EXCEPT: standalone "&" is a literal operator (used in match), not a variable
-            if (name.starts_with('$') || name.starts_with('&') || name.starts_with('\''))
-                && name != "&"
-            {
+            if MettaValue::is_wildcard_name(name) {
+                // Wildcard - a fresh anonymous variable at every occurrence
+                ez.write_new_var();
+                ez.loc += 1;
+            } else if name.starts_with('$') || name.starts_with('\'') {
                 // Variable - use De Bruijn encoding
                 let var_id = &name[1..]; // Remove prefix
                 match ctx.get_or_create_var(var_id)? {
@@ -105,12 +106,8 @@ fn write_metta_value(
                         ez.loc += 1;
                     }
                 }
-            } else if name == "_" {
-                // Wildcard - treat as anonymous variable
-                ez.write_new_var();
-                ez.loc += 1;
             } else {
-                // Regular atom - write as symbol (including standalone "&")
+                // Regular atom - write as symbol
                 write_symbol(name.as_bytes(), space, ez)?;
             }
         }
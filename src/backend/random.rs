@@ -0,0 +1,95 @@
+// Random number generation for the `random-int` and `random-float` builtins
+//
+// A small SplitMix64 generator is used so that runs started with `--seed N` produce
+// the same sequence on every platform without pulling in an external RNG crate.
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Seedable pseudo-random number generator (SplitMix64)
+///
+/// Not suitable for cryptographic use.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Create a generator producing a deterministic sequence for `seed`
+    pub fn from_seed(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    /// Create a generator seeded from process entropy
+    pub fn from_entropy() -> Self {
+        // RandomState is keyed from OS randomness; mix in the clock for good measure
+        let mut hasher = RandomState::new().build_hasher();
+        if let Ok(elapsed) = SystemTime::now().duration_since(UNIX_EPOCH) {
+            hasher.write_u128(elapsed.as_nanos());
+        }
+        Rng::from_seed(hasher.finish())
+    }
+
+    /// Next 64 random bits
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform float in `[0, 1)`
+    pub fn next_f64(&mut self) -> f64 {
+        // Use the top 53 bits, the width of an f64 mantissa
+        (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    /// Uniform integer in the half-open range `[lo, hi)`; requires `lo < hi`
+    pub fn range_i64(&mut self, lo: i64, hi: i64) -> i64 {
+        debug_assert!(lo < hi);
+        let span = hi.wrapping_sub(lo) as u64;
+        // Rejection sampling avoids modulo bias
+        let zone = u64::MAX - (u64::MAX - span + 1) % span;
+        loop {
+            let v = self.next_u64();
+            if v <= zone {
+                return lo.wrapping_add((v % span) as i64);
+            }
+        }
+    }
+}
+
+impl Default for Rng {
+    fn default() -> Self {
+        Rng::from_entropy()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = Rng::from_seed(42);
+        let mut b = Rng::from_seed(42);
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+    }
+
+    #[test]
+    fn test_range_and_float_bounds() {
+        let mut rng = Rng::from_seed(7);
+        for _ in 0..1000 {
+            let n = rng.range_i64(-3, 4);
+            assert!((-3..4).contains(&n));
+            let f = rng.next_f64();
+            assert!((0.0..1.0).contains(&f));
+        }
+        // Full i64 range must not overflow
+        rng.range_i64(i64::MIN, i64::MAX);
+    }
+}
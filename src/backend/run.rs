@@ -0,0 +1,292 @@
+// Source-to-results pipeline: compile MeTTa text and evaluate every form in order
+//
+// This is the loop the CLI, tests and benchmarks otherwise each write by hand:
+// compile, then thread the environment through `eval` for each top-level form so
+// that rules defined by earlier forms are visible to later ones.
+
+use crate::backend::compile::compile_incremental;
+use crate::backend::environment::{
+    Environment, RuleTraceSink, DEFAULT_MAX_CALL_DEPTH, DEFAULT_MAX_EVAL_DEPTH,
+};
+use crate::backend::eval::{eval, flatten_conjunction_results, flatten_nested_conjunction_results};
+use crate::backend::models::MettaValue;
+use crate::tree_sitter_parser::SyntaxError;
+
+/// Evaluation settings applied to the environment before any form runs
+#[derive(Clone)]
+pub struct RunConfig {
+    /// Seed for `random-int`/`random-float`; None draws from entropy
+    pub seed: Option<u64>,
+    /// Collect execution metrics (read them back with `Environment::metrics`)
+    pub collect_metrics: bool,
+    /// Limit on nested evaluation, see `Environment::set_max_call_depth`
+    pub max_call_depth: usize,
+    /// Limit on nested `(eval ...)` forms, see `Environment::set_max_eval_depth`
+    pub max_eval_depth: usize,
+    /// Limit on the results `collapse` gathers, see
+    /// `Environment::set_max_collapse_results`
+    pub max_collapse_results: Option<usize>,
+    /// Where each rule application is logged, see `Environment::set_rule_trace`
+    pub rule_trace: Option<RuleTraceSink>,
+    /// Where identical rule definitions are reported, see
+    /// `Environment::set_duplicate_rule_warnings`
+    pub duplicate_rule_warnings: Option<RuleTraceSink>,
+    /// Also expand conjunctions nested inside S-expressions into separate results,
+    /// see `flatten_nested_conjunction_results`; top-level conjunctions are always
+    /// expanded, as by the CLI and REPL
+    pub flatten_results: bool,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        RunConfig {
+            seed: None,
+            collect_metrics: false,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_eval_depth: DEFAULT_MAX_EVAL_DEPTH,
+            max_collapse_results: None,
+            rule_trace: None,
+            duplicate_rule_warnings: None,
+            flatten_results: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for RunConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RunConfig")
+            .field("seed", &self.seed)
+            .field("collect_metrics", &self.collect_metrics)
+            .field("max_call_depth", &self.max_call_depth)
+            .field("max_eval_depth", &self.max_eval_depth)
+            .field("max_collapse_results", &self.max_collapse_results)
+            .field("rule_trace", &self.rule_trace.is_some())
+            .field(
+                "duplicate_rule_warnings",
+                &self.duplicate_rule_warnings.is_some(),
+            )
+            .field("flatten_results", &self.flatten_results)
+            .finish()
+    }
+}
+
+/// Compile `input` and evaluate each of its forms in order
+///
+/// Returns the results of every form, in source order, together with the final
+/// environment. A conjunction result is expanded into its goals, as the CLI and
+/// REPL print it. Only parsing can fail; evaluation errors are returned as
+/// `MettaValue::Error` results like everywhere else.
+///
+/// # Example
+///
+/// ```ignore
+/// let (results, _env) = eval_source("(= (double $x) (* $x 2))\n!(double 21)", RunConfig::default())?;
+/// assert_eq!(results, vec![MettaValue::Long(42)]);
+/// ```
+pub fn eval_source(
+    input: &str,
+    config: RunConfig,
+) -> Result<(Vec<MettaValue>, Environment), SyntaxError> {
+    eval_source_in(input, Environment::new(), config)
+}
+
+/// Like `eval_source`, but evaluate against `env`, so the forms see the rules and
+/// facts it already holds; `config` replaces the settings of `env`
+pub fn eval_source_in(
+    input: &str,
+    env: Environment,
+    config: RunConfig,
+) -> Result<(Vec<MettaValue>, Environment), SyntaxError> {
+    let state = compile_incremental(input, env)?;
+    let mut env = state.environment;
+    if let Some(seed) = config.seed {
+        env.seed_rng(seed);
+    }
+    env.set_collect_metrics(config.collect_metrics);
+    env.set_max_call_depth(config.max_call_depth);
+    env.set_max_eval_depth(config.max_eval_depth);
+    env.set_max_collapse_results(config.max_collapse_results);
+    env.set_rule_trace(config.rule_trace);
+    env.set_duplicate_rule_warnings(config.duplicate_rule_warnings);
+
+    let mut all_results = Vec::new();
+    for expr in state.source {
+        let (results, new_env) = eval(expr, env);
+        env = new_env;
+        if config.flatten_results {
+            all_results.extend(flatten_nested_conjunction_results(results));
+        } else {
+            all_results.extend(flatten_conjunction_results(results));
+        }
+    }
+
+    Ok((all_results, env))
+}
+
+/// Evaluate each form of `input` in order against `env`, keeping every form's
+/// results apart; the shared loop for tests that inspect results form by form
+#[cfg(test)]
+pub(crate) fn eval_forms(input: &str, env: Environment) -> (Vec<Vec<MettaValue>>, Environment) {
+    let state = compile_incremental(input, env).unwrap();
+    let mut env = state.environment;
+    let mut forms = Vec::new();
+    for expr in state.source {
+        let (results, new_env) = eval(expr, env);
+        env = new_env;
+        forms.push(results);
+    }
+    (forms, env)
+}
+
+/// Evaluate each form of `input` in a fresh environment and return the results of
+/// the last form only; the shared helper for tests where earlier forms set things up
+#[cfg(test)]
+pub(crate) fn eval_last(input: &str) -> Vec<MettaValue> {
+    eval_last_in(input, Environment::new()).0
+}
+
+/// Like `eval_last`, but evaluate against `env` and return the final environment too
+#[cfg(test)]
+pub(crate) fn eval_last_in(input: &str, env: Environment) -> (Vec<MettaValue>, Environment) {
+    let (mut forms, env) = eval_forms(input, env);
+    (forms.pop().unwrap_or_default(), env)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    #[test]
+    fn test_eval_source_matches_manual_loop() {
+        let src = r#"
+            (= (double $x) (* $x 2))
+            (= (quad $x) (double (double $x)))
+            (parent tom bob)
+            !(quad 5)
+            !(match &self (parent tom $c) $c)
+            !(double (quad 1))
+        "#;
+
+        let (forms, _) = eval_forms(src, Environment::new());
+
+        let (results, env) = eval_source(src, RunConfig::default()).unwrap();
+        assert_eq!(results, forms.concat());
+        assert_eq!(
+            results,
+            vec![
+                // A bare fact is added to the space and returned unreduced
+                MettaValue::SExpr(vec![
+                    MettaValue::Atom("parent".to_string()),
+                    MettaValue::Atom("tom".to_string()),
+                    MettaValue::Atom("bob".to_string()),
+                ]),
+                MettaValue::Long(20),
+                MettaValue::Atom("bob".to_string()),
+                MettaValue::Long(8)
+            ]
+        );
+        // Rules defined by the source stay in the returned environment
+        assert_eq!(env.stats().rules, 2);
+    }
+
+    #[test]
+    fn test_eval_source_applies_config() {
+        let config = RunConfig {
+            seed: Some(7),
+            collect_metrics: true,
+            ..RunConfig::default()
+        };
+        let src = "!(random-int 0 1000000)";
+        let (first, env) = eval_source(src, config.clone()).unwrap();
+        let (second, _) = eval_source(src, config).unwrap();
+        assert_eq!(first, second);
+        assert!(env.metrics().steps > 0);
+    }
+
+    #[test]
+    fn test_eval_source_uses_config_sinks() {
+        let lines = Arc::new(Mutex::new(Vec::new()));
+        let sink = |prefix: &'static str| -> RuleTraceSink {
+            let lines = lines.clone();
+            Arc::new(move |line: &str| lines.lock().unwrap().push(format!("{}{}", prefix, line)))
+        };
+        let config = RunConfig {
+            rule_trace: Some(sink("trace: ")),
+            duplicate_rule_warnings: Some(sink("dup: ")),
+            ..RunConfig::default()
+        };
+        let src = "(= (one) 1)\n(= (one) 1)\n!(one)";
+        eval_source(src, config).unwrap();
+
+        let lines = lines.lock().unwrap();
+        assert!(
+            lines.iter().any(|l| l.starts_with("trace: ")),
+            "{:?}",
+            lines
+        );
+        assert!(lines.iter().any(|l| l.starts_with("dup: ")), "{:?}", lines);
+    }
+
+    #[test]
+    fn test_eval_source_flatten_results() {
+        let src = r#"
+            (= (pair) (p x y))
+            !(match & self (= (pair) (p $a $b)) (, $a (, $b $a)))
+            !(match & self (= (pair) (p $a $b)) (swap (, $a (, $b $a))))
+        "#;
+        let atom = |s: &str| MettaValue::Atom(s.to_string());
+        let swap = |s: &str| MettaValue::SExpr(vec![atom("swap"), atom(s)]);
+
+        // By default only the top-level conjunction of conjunctions is expanded
+        let (results, _) = eval_source(src, RunConfig::default()).unwrap();
+        assert_eq!(
+            results,
+            vec![
+                atom("x"),
+                atom("y"),
+                atom("x"),
+                MettaValue::SExpr(vec![
+                    atom("swap"),
+                    MettaValue::Conjunction(vec![
+                        atom("x"),
+                        MettaValue::Conjunction(vec![atom("y"), atom("x")]),
+                    ]),
+                ]),
+            ]
+        );
+
+        let config = RunConfig {
+            flatten_results: true,
+            ..RunConfig::default()
+        };
+        let (results, _) = eval_source(src, config).unwrap();
+        assert_eq!(
+            results,
+            vec![
+                atom("x"),
+                atom("y"),
+                atom("x"),
+                swap("x"),
+                swap("y"),
+                swap("x"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_eval_source_in_keeps_environment() {
+        let (_, env) = eval_source("(= (double $x) (* $x 2))", RunConfig::default()).unwrap();
+        let (results, env) = eval_source_in("!(double 21)", env, RunConfig::default()).unwrap();
+        assert_eq!(results, vec![MettaValue::Long(42)]);
+        assert_eq!(env.stats().rules, 1);
+
+        let (last, _) = eval_last_in("(= (one) 1)\n!(one)\n!(double (one))", env);
+        assert_eq!(last, vec![MettaValue::Long(2)]);
+    }
+
+    #[test]
+    fn test_eval_source_syntax_error() {
+        assert!(eval_source("(unclosed", RunConfig::default()).is_err());
+    }
+}
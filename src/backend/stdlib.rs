@@ -0,0 +1,84 @@
+// Standard library: MeTTa definitions that programs can opt into
+//
+// The definitions live in stdlib.metta, embedded in the binary and parsed once per
+// process. `Environment::with_stdlib` evaluates them into a fresh environment;
+// `Environment::new` stays empty.
+
+use std::sync::OnceLock;
+
+use crate::backend::compile::compile;
+use crate::backend::environment::Environment;
+use crate::backend::eval::eval;
+use crate::backend::models::MettaValue;
+
+/// Source of the standard library
+pub const STDLIB_SOURCE: &str = include_str!("stdlib.metta");
+
+/// Parsed standard library forms, shared by every `with_stdlib` call
+fn stdlib_forms() -> &'static [MettaValue] {
+    static FORMS: OnceLock<Vec<MettaValue>> = OnceLock::new();
+    FORMS.get_or_init(|| {
+        compile(STDLIB_SOURCE)
+            .expect("embedded stdlib.metta must parse")
+            .source
+    })
+}
+
+impl Environment {
+    /// Create an environment preloaded with the standard library rules
+    /// (`len`, `map`, `filter`, `foldl`, `sum`, `reverse`, ...)
+    pub fn with_stdlib() -> Self {
+        let mut env = Environment::new();
+        for form in stdlib_forms() {
+            let (_, new_env) = eval(form.clone(), env);
+            env = new_env;
+        }
+        env
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backend::run::eval_last_in;
+
+    fn run_with_stdlib(src: &str) -> Vec<MettaValue> {
+        eval_last_in(src, Environment::with_stdlib()).0
+    }
+
+    fn longs(values: &[i64]) -> MettaValue {
+        MettaValue::SExpr(values.iter().map(|n| MettaValue::Long(*n)).collect())
+    }
+
+    #[test]
+    fn test_stdlib_helpers_need_no_definition() {
+        assert_eq!(run_with_stdlib("!(len (a b c))"), vec![MettaValue::Long(3)]);
+        assert_eq!(
+            run_with_stdlib("!(map inc (1 2 3))"),
+            vec![longs(&[2, 3, 4])]
+        );
+        assert_eq!(
+            run_with_stdlib("!(sum (1 2 3 4))"),
+            vec![MettaValue::Long(10)]
+        );
+        assert_eq!(
+            run_with_stdlib("!(foldl - 10 (1 2 3))"),
+            vec![MettaValue::Long(4)]
+        );
+        assert_eq!(
+            run_with_stdlib("!(reverse (1 2 3))"),
+            vec![longs(&[3, 2, 1])]
+        );
+        // User rules work alongside the stdlib
+        assert_eq!(
+            run_with_stdlib("(= (is-small $x) (< $x 3))\n!(filter is-small (1 5 2))"),
+            vec![longs(&[1, 2])]
+        );
+    }
+
+    #[test]
+    fn test_new_environment_has_no_stdlib() {
+        assert_eq!(Environment::new().rule_count(), 0);
+        assert!(Environment::with_stdlib().rule_count() > 0);
+    }
+}
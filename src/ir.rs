@@ -5,6 +5,8 @@
 
 use std::fmt;
 
+use crate::backend::models::metta_value::escape_json;
+
 /// Position in source code (line and column, 0-indexed)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Position {
@@ -81,6 +83,21 @@ impl fmt::Display for Span {
     }
 }
 
+impl Span {
+    /// JSON object with 0-indexed start/end positions and byte offsets
+    pub fn to_json_string(&self) -> String {
+        format!(
+            r#"{{"start":{{"row":{},"column":{}}},"end":{{"row":{},"column":{}}},"start_byte":{},"end_byte":{}}}"#,
+            self.start.row,
+            self.start.column,
+            self.end.row,
+            self.end.column,
+            self.start_byte,
+            self.end_byte
+        )
+    }
+}
+
 /// MeTTa IR - Enhanced intermediate representation for MeTTa expressions
 ///
 /// Represents the abstract syntax of MeTTa code with semantic distinctions
@@ -160,11 +177,78 @@ impl MettaExpr {
     pub fn quoted(expr: MettaExpr) -> Self {
         MettaExpr::Quoted(Box::new(expr), None)
     }
+
+    /// Node kind used in the JSON form, named after the Tree-Sitter node types
+    ///
+    /// Atoms are split into `variable`, `wildcard` and `symbol` by their text.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            MettaExpr::Atom(s, _) if s.starts_with('$') => "variable",
+            MettaExpr::Atom(s, _) if s == "_" => "wildcard",
+            MettaExpr::Atom(_, _) => "symbol",
+            MettaExpr::String(_, _) => "string_literal",
+            MettaExpr::Integer(_, _) => "integer_literal",
+            MettaExpr::Float(_, _) => "float_literal",
+            MettaExpr::List(_, _) => "list",
+            MettaExpr::Quoted(_, _) => "quoted",
+        }
+    }
+
+    /// Convert the tree to JSON for tooling that does not link the crate
+    ///
+    /// Every node has `kind` and `span` (`null` when unknown). Leaves carry a `value`,
+    /// lists and quoted expressions carry their `children`.
+    pub fn to_json_string(&self) -> String {
+        let kind = self.kind();
+        let span = self
+            .span()
+            .map_or_else(|| "null".to_string(), |span| span.to_json_string());
+        let value = match self {
+            MettaExpr::Atom(s, _) | MettaExpr::String(s, _) => format!(r#""{}""#, escape_json(s)),
+            MettaExpr::Integer(i, _) => i.to_string(),
+            MettaExpr::Float(f, _) if f.is_finite() => f.to_string(),
+            MettaExpr::Float(_, _) => "null".to_string(),
+            MettaExpr::List(items, _) => {
+                let children: Vec<String> =
+                    items.iter().map(|item| item.to_json_string()).collect();
+                return format!(
+                    r#"{{"kind":"{}","span":{},"children":[{}]}}"#,
+                    kind,
+                    span,
+                    children.join(",")
+                );
+            }
+            MettaExpr::Quoted(expr, _) => {
+                return format!(
+                    r#"{{"kind":"{}","span":{},"children":[{}]}}"#,
+                    kind,
+                    span,
+                    expr.to_json_string()
+                );
+            }
+        };
+        format!(r#"{{"kind":"{}","span":{},"value":{}}}"#, kind, span, value)
+    }
+}
+
+/// Reader prefixes the parser expands into a two-element list: `'x` -> `(' x)`
+const READER_PREFIXES: [&str; 3] = ["'", "!", "?"];
+
+fn is_reader_prefixed(items: &[MettaExpr]) -> bool {
+    match items {
+        [MettaExpr::Atom(head, _), _] => READER_PREFIXES.contains(&head.as_str()),
+        _ => false,
+    }
 }
 
 impl fmt::Display for MettaExpr {
+    /// Prints MeTTa source text; lists produced by a reader prefix (`'x`, `!x`, `?x`)
+    /// are printed back in their shorthand form so the output re-parses to the same AST
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            MettaExpr::List(items, _) if is_reader_prefixed(items) => {
+                write!(f, "{}{}", items[0], items[1])
+            }
             MettaExpr::Atom(s, _) => write!(f, "{}", s),
             MettaExpr::String(s, _) => write!(f, "\"{}\"", s),
             MettaExpr::Integer(i, _) => write!(f, "{}", i),
@@ -0,0 +1,656 @@
+//! Standalone MeTTa tokenizer for editor integrations
+//!
+//! Classifies source text into the token categories of the tree-sitter grammar
+//! (`tree-sitter-metta/grammar.js`) without building a parse tree. Each token kind
+//! names the grammar node it corresponds to, so highlighting rules written against
+//! `highlights.scm` apply unchanged. Like the grammar's lexer, the longest token
+//! wins and ties go to the higher-precedence category (`True` is a boolean, not an
+//! identifier; `-5` is an integer, `-x` an identifier).
+//!
+//! Token spans carry byte offsets as well as positions. After an edit, `retokenize`
+//! uses them to re-lex only the region the edit can have changed.
+
+use crate::ir::{Position, Span};
+use tree_sitter_metta::node_types;
+
+/// Token category, one per lexical rule of the grammar
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TokenKind {
+    OpenParen,
+    CloseParen,
+    OpenBrace,
+    CloseBrace,
+    Variable,
+    Wildcard,
+    Identifier,
+    BooleanLiteral,
+    SpecialTypeSymbol,
+    StringLiteral,
+    UriLiteral,
+    CharLiteral,
+    FloatLiteral,
+    IntegerLiteral,
+    ArrowOperator,
+    ComparisonOperator,
+    AssignmentOperator,
+    TypeAnnotationOperator,
+    RuleDefinitionOperator,
+    PunctuationOperator,
+    ArithmeticOperator,
+    LogicOperator,
+    ExclaimPrefix,
+    QuestionPrefix,
+    QuotePrefix,
+    LineComment,
+    /// Text no rule accepts, such as an unclosed string or a stray character
+    Error,
+}
+
+impl TokenKind {
+    /// Grammar node type for this token (see `tree_sitter_metta::node_types`);
+    /// delimiters are anonymous nodes named by their text
+    pub fn node_type(self) -> &'static str {
+        match self {
+            TokenKind::OpenParen => "(",
+            TokenKind::CloseParen => ")",
+            TokenKind::OpenBrace => "{",
+            TokenKind::CloseBrace => "}",
+            TokenKind::Variable => node_types::VARIABLE,
+            TokenKind::Wildcard => node_types::WILDCARD,
+            TokenKind::Identifier => node_types::IDENTIFIER,
+            TokenKind::BooleanLiteral => node_types::BOOLEAN_LITERAL,
+            TokenKind::SpecialTypeSymbol => node_types::SPECIAL_TYPE_SYMBOL,
+            TokenKind::StringLiteral => node_types::STRING_LITERAL,
+            TokenKind::UriLiteral => node_types::URI_LITERAL,
+            TokenKind::CharLiteral => node_types::CHAR_LITERAL,
+            TokenKind::FloatLiteral => node_types::FLOAT_LITERAL,
+            TokenKind::IntegerLiteral => node_types::INTEGER_LITERAL,
+            TokenKind::ArrowOperator => node_types::ARROW_OPERATOR,
+            TokenKind::ComparisonOperator => node_types::COMPARISON_OPERATOR,
+            TokenKind::AssignmentOperator => node_types::ASSIGNMENT_OPERATOR,
+            TokenKind::TypeAnnotationOperator => node_types::TYPE_ANNOTATION_OPERATOR,
+            TokenKind::RuleDefinitionOperator => node_types::RULE_DEFINITION_OPERATOR,
+            TokenKind::PunctuationOperator => node_types::PUNCTUATION_OPERATOR,
+            TokenKind::ArithmeticOperator => node_types::ARITHMETIC_OPERATOR,
+            TokenKind::LogicOperator => node_types::LOGIC_OPERATOR,
+            TokenKind::ExclaimPrefix => node_types::EXCLAIM_PREFIX,
+            TokenKind::QuestionPrefix => node_types::QUESTION_PREFIX,
+            TokenKind::QuotePrefix => node_types::QUOTE_PREFIX,
+            TokenKind::LineComment => node_types::LINE_COMMENT,
+            TokenKind::Error => "ERROR",
+        }
+    }
+
+    /// Whether a token of this kind ends an operand, after which `-` means subtraction
+    fn is_operand(self) -> bool {
+        matches!(
+            self,
+            TokenKind::CloseParen
+                | TokenKind::CloseBrace
+                | TokenKind::IntegerLiteral
+                | TokenKind::FloatLiteral
+                | TokenKind::StringLiteral
+                | TokenKind::CharLiteral
+                | TokenKind::UriLiteral
+                | TokenKind::BooleanLiteral
+                | TokenKind::SpecialTypeSymbol
+                | TokenKind::Wildcard
+        )
+    }
+}
+
+/// Split `input` into classified tokens with their spans, skipping whitespace
+///
+/// Never fails: text that cannot start a token becomes a one-character
+/// `TokenKind::Error`, and an unterminated string runs to the end of the input.
+pub fn tokenize_with_kinds(input: &str) -> Vec<(TokenKind, Span)> {
+    let mut tokens = Vec::new();
+    lex(input, &mut tokens, 0, 0, 0, |_| false);
+    tokens
+}
+
+/// A text edit in byte offsets: `old[start..old_end]` was replaced, giving
+/// `new[start..new_end]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextEdit {
+    pub start: usize,
+    pub old_end: usize,
+    pub new_end: usize,
+}
+
+/// Tokenize `new_input` after `edit`, given the tokens of the text before the edit
+///
+/// Gives the same tokens as `tokenize_with_kinds(new_input)`, but only re-lexes from
+/// the last token before the edit that is followed by whitespace up to the first token
+/// after the edit that comes out unchanged. The tokens after that one are the old
+/// tokens moved by the size of the edit.
+pub fn retokenize(
+    tokens: &[(TokenKind, Span)],
+    new_input: &str,
+    edit: TextEdit,
+) -> Vec<(TokenKind, Span)> {
+    let bytes = new_input.as_bytes();
+
+    // A token followed by whitespace ahead of the edit was lexed from unchanged text,
+    // and so was every token before it
+    let keep = tokens
+        .iter()
+        .rposition(|(_, span)| {
+            span.end_byte < edit.start
+                && bytes
+                    .get(span.end_byte)
+                    .is_some_and(u8::is_ascii_whitespace)
+        })
+        .map_or(0, |i| i + 1);
+    let mut new_tokens = tokens[..keep].to_vec();
+    let (pos, row, line_start) = match new_tokens.last() {
+        Some((_, span)) => (span.end_byte, span.end.row, span.end_byte - span.end.column),
+        None => (0, 0, 0),
+    };
+
+    // Old tokens after the edit, with their start moved to where it is in the new text
+    let delta = edit.new_end as isize - edit.old_end as isize;
+    let mut after = tokens
+        .iter()
+        .enumerate()
+        .skip(keep)
+        .filter(|(_, (_, span))| span.start_byte >= edit.old_end)
+        .peekable();
+    let mut resync = None;
+    lex(
+        new_input,
+        &mut new_tokens,
+        pos,
+        row,
+        line_start,
+        |(kind, span)| {
+            while let Some((i, (old_kind, old))) = after.peek() {
+                let old_start = old.start_byte as isize + delta;
+                if old_start > span.start_byte as isize {
+                    return false;
+                }
+                if old_start == span.start_byte as isize
+                    && old_kind == kind
+                    && old.end_byte - old.start_byte == span.end_byte - span.start_byte
+                {
+                    resync = Some(*i);
+                    return true;
+                }
+                after.next();
+            }
+            false
+        },
+    );
+
+    // The rest of the text is unchanged and lexes as before, moved by the edit
+    if let Some(i) = resync {
+        let old = tokens[i].1;
+        let new = new_tokens[new_tokens.len() - 1].1;
+        let row_delta = new.start.row as isize - old.start.row as isize;
+        let column_delta = new.start.column as isize - old.start.column as isize;
+        let shift = |p: Position| Position {
+            row: (p.row as isize + row_delta) as usize,
+            column: if p.row == old.start.row {
+                (p.column as isize + column_delta) as usize
+            } else {
+                p.column
+            },
+        };
+        new_tokens.extend(tokens[i + 1..].iter().map(|(kind, span)| {
+            (
+                *kind,
+                Span::new(
+                    shift(span.start),
+                    shift(span.end),
+                    (span.start_byte as isize + delta) as usize,
+                    (span.end_byte as isize + delta) as usize,
+                ),
+            )
+        }));
+    }
+    new_tokens
+}
+
+/// Lex `input` from byte `pos` (on line `row`, which starts at byte `line_start`),
+/// appending to `tokens`
+///
+/// The last token already in `tokens` is the one before `pos`. Stops early, returning
+/// true, as soon as `stop` accepts a token just appended.
+fn lex(
+    input: &str,
+    tokens: &mut Vec<(TokenKind, Span)>,
+    mut pos: usize,
+    mut row: usize,
+    mut line_start: usize,
+    mut stop: impl FnMut(&(TokenKind, Span)) -> bool,
+) -> bool {
+    let bytes = input.as_bytes();
+
+    while pos < bytes.len() {
+        let b = bytes[pos];
+        if b.is_ascii_whitespace() {
+            if b == b'\n' {
+                row += 1;
+                line_start = pos + 1;
+            }
+            pos += 1;
+            continue;
+        }
+
+        let (mut kind, mut len) = scan_token(&bytes[pos..]).unwrap_or_else(|| {
+            let width = input[pos..].chars().next().map_or(1, char::len_utf8);
+            (TokenKind::Error, width)
+        });
+
+        // A '-' directly after an operand is subtraction, not a sign: `2-1` is 2, -, 1
+        if b == b'-' && matches!(kind, TokenKind::IntegerLiteral | TokenKind::FloatLiteral) {
+            let after_operand = tokens
+                .last()
+                .is_some_and(|(prev, span): &(TokenKind, Span)| {
+                    span.end_byte == pos && prev.is_operand()
+                });
+            if after_operand {
+                (kind, len) = (TokenKind::ArithmeticOperator, 1);
+            }
+        }
+
+        let end = pos + len;
+
+        // Only strings can span lines
+        let mut end_row = row;
+        let mut end_line_start = line_start;
+        for (i, _) in input[pos..end].match_indices('\n') {
+            end_row += 1;
+            end_line_start = pos + i + 1;
+        }
+
+        tokens.push((
+            kind,
+            Span::new(
+                Position::new(row, pos - line_start),
+                Position::new(end_row, end - end_line_start),
+                pos,
+                end,
+            ),
+        ));
+        if stop(&tokens[tokens.len() - 1]) {
+            return true;
+        }
+        row = end_row;
+        line_start = end_line_start;
+        pos = end;
+    }
+
+    false
+}
+
+/// Classify the token at the start of `s` (which is not whitespace)
+fn scan_token(s: &[u8]) -> Option<(TokenKind, usize)> {
+    let fixed = match s[0] {
+        b'(' => Some(TokenKind::OpenParen),
+        b')' => Some(TokenKind::CloseParen),
+        b'{' => Some(TokenKind::OpenBrace),
+        b'}' => Some(TokenKind::CloseBrace),
+        _ => None,
+    };
+    if let Some(kind) = fixed {
+        return Some((kind, 1));
+    }
+
+    match s[0] {
+        // Comments take precedence over the ';' punctuation operator
+        b';' => return Some((TokenKind::LineComment, run(s, 1, |b| b != b'\n'))),
+        b'"' => return Some((TokenKind::StringLiteral, string_len(s))),
+        b'`' => {
+            let len = run(s, 1, |b| b != b'`' && b != b'\n');
+            return (s.get(len) == Some(&b'`')).then_some((TokenKind::UriLiteral, len + 1));
+        }
+        b'#' if s.get(1) == Some(&b'\\') => {
+            let len = run(s, 2, |b| !b.is_ascii_whitespace() && b != b'(' && b != b')');
+            return Some((TokenKind::CharLiteral, len));
+        }
+        b'$' => {
+            let len = run(s, 1, |b| {
+                b.is_ascii_alphanumeric() || b"_'-+*/&".contains(&b)
+            });
+            return Some((TokenKind::Variable, len));
+        }
+        _ => {}
+    }
+
+    // Everything else: longest match, ties broken by precedence
+    let candidates = [
+        (float_len(s), 4, TokenKind::FloatLiteral),
+        (integer_len(s), 3, TokenKind::IntegerLiteral),
+        (boolean_len(s), 3, TokenKind::BooleanLiteral),
+        (special_type_len(s), 3, TokenKind::SpecialTypeSymbol),
+        (identifier_len(s), 2, TokenKind::Identifier),
+        (
+            longest_of(s, &["->", "<-", "<=", "<<-"]),
+            2,
+            TokenKind::ArrowOperator,
+        ),
+        (
+            longest_of(s, &["==", ">", "<"]),
+            1,
+            TokenKind::ComparisonOperator,
+        ),
+        (
+            longest_of(s, &["+", "-", "*", "/"]),
+            1,
+            TokenKind::ArithmeticOperator,
+        ),
+        (longest_of(s, &["="]), 0, TokenKind::AssignmentOperator),
+        (longest_of(s, &[":"]), 0, TokenKind::TypeAnnotationOperator),
+        (longest_of(s, &[":="]), 0, TokenKind::RuleDefinitionOperator),
+        (
+            longest_of(s, &["|", ",", "@", "&", "...", "."]),
+            0,
+            TokenKind::PunctuationOperator,
+        ),
+        (longest_of(s, &["!?", "?!"]), 0, TokenKind::LogicOperator),
+        (longest_of(s, &["_"]), 0, TokenKind::Wildcard),
+        (longest_of(s, &["!"]), 0, TokenKind::ExclaimPrefix),
+        (longest_of(s, &["?"]), 0, TokenKind::QuestionPrefix),
+        (longest_of(s, &["'"]), 0, TokenKind::QuotePrefix),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|&(len, _, _)| len > 0)
+        .max_by_key(|&(len, prec, _)| (len, prec))
+        .map(|(len, _, kind)| (kind, len))
+}
+
+/// Index of the first byte at or after `from` that does not satisfy `pred`
+fn run(s: &[u8], from: usize, pred: impl Fn(u8) -> bool) -> usize {
+    from + s[from.min(s.len())..]
+        .iter()
+        .take_while(|&&b| pred(b))
+        .count()
+}
+
+/// Length of a string literal, through the closing quote or to the end of input
+fn string_len(s: &[u8]) -> usize {
+    let mut i = 1;
+    while i < s.len() {
+        match s[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    s.len()
+}
+
+fn longest_of(s: &[u8], options: &[&str]) -> usize {
+    options
+        .iter()
+        .filter(|o| s.starts_with(o.as_bytes()))
+        .map(|o| o.len())
+        .max()
+        .unwrap_or(0)
+}
+
+fn digits(s: &[u8], from: usize) -> usize {
+    run(s, from, |b| b.is_ascii_digit())
+}
+
+fn integer_len(s: &[u8]) -> usize {
+    let start = usize::from(s[0] == b'-');
+    let end = digits(s, start);
+    if end > start {
+        end
+    } else {
+        0
+    }
+}
+
+fn float_len(s: &[u8]) -> usize {
+    let int_end = integer_len(s);
+    if int_end == 0 || s.get(int_end) != Some(&b'.') {
+        return 0;
+    }
+    let frac_end = digits(s, int_end + 1);
+    if frac_end == int_end + 1 {
+        return 0;
+    }
+    if matches!(s.get(frac_end), Some(b'e' | b'E')) {
+        let sign = usize::from(matches!(s.get(frac_end + 1), Some(b'+' | b'-')));
+        let exp_end = digits(s, frac_end + 1 + sign);
+        if exp_end > frac_end + 1 + sign {
+            return exp_end;
+        }
+    }
+    frac_end
+}
+
+fn boolean_len(s: &[u8]) -> usize {
+    longest_of(s, &["True", "False"])
+}
+
+fn special_type_len(s: &[u8]) -> usize {
+    if s[0] != b'%' || !s.get(1).is_some_and(u8::is_ascii_alphabetic) {
+        return 0;
+    }
+    let end = run(s, 2, |b| {
+        b.is_ascii_alphanumeric() || b == b'_' || b == b'-'
+    });
+    if s.get(end) == Some(&b'%') {
+        end + 1
+    } else {
+        0
+    }
+}
+
+fn identifier_len(s: &[u8]) -> usize {
+    let is_tail = |b: u8| b.is_ascii_alphanumeric() || b"_'-+*/".contains(&b);
+    if s[0].is_ascii_alphabetic() {
+        let end = run(s, 1, is_tail);
+        end + usize::from(s.get(end) == Some(&b'!'))
+    } else if b"+-*/".contains(&s[0]) {
+        let end = run(s, 1, is_tail);
+        if end > 1 {
+            end
+        } else {
+            0
+        }
+    } else if s[0] == b'&' && s.get(1).is_some_and(u8::is_ascii_alphabetic) {
+        // Space and token references such as &self; a bare & stays an operator
+        run(s, 2, is_tail)
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(input: &str) -> Vec<TokenKind> {
+        tokenize_with_kinds(input)
+            .into_iter()
+            .map(|(kind, _)| kind)
+            .collect()
+    }
+
+    #[test]
+    fn test_tokenize_rule_with_comment() {
+        use TokenKind::*;
+
+        let input = "(= (double $x) (* $x 2)) ; doubles";
+        assert_eq!(
+            kinds(input),
+            vec![
+                OpenParen,
+                AssignmentOperator,
+                OpenParen,
+                Identifier,
+                Variable,
+                CloseParen,
+                OpenParen,
+                ArithmeticOperator,
+                Variable,
+                IntegerLiteral,
+                CloseParen,
+                CloseParen,
+                LineComment,
+            ]
+        );
+
+        // Spans cover the token text
+        let tokens = tokenize_with_kinds(input);
+        let text = |i: usize| &input[tokens[i].1.start_byte..tokens[i].1.end_byte];
+        assert_eq!(text(3), "double");
+        assert_eq!(text(4), "$x");
+        assert_eq!(text(12), "; doubles");
+        assert_eq!(tokens[12].1.start, Position::new(0, 25));
+    }
+
+    #[test]
+    fn test_tokenize_literals_prefixes_and_precedence() {
+        use TokenKind::*;
+
+        assert_eq!(
+            kinds("!(if True -5 -x) ?(: f (-> Number %Undefined%)) '_"),
+            vec![
+                ExclaimPrefix,
+                OpenParen,
+                Identifier,
+                BooleanLiteral,
+                IntegerLiteral,
+                Identifier,
+                CloseParen,
+                QuestionPrefix,
+                OpenParen,
+                TypeAnnotationOperator,
+                Identifier,
+                OpenParen,
+                ArrowOperator,
+                Identifier,
+                SpecialTypeSymbol,
+                CloseParen,
+                CloseParen,
+                QuotePrefix,
+                Wildcard,
+            ]
+        );
+        assert_eq!(
+            kinds("\"a \\\" b\" 1.5e-3 #\\a `x/y` change-state! Truth == :="),
+            vec![
+                StringLiteral,
+                FloatLiteral,
+                CharLiteral,
+                UriLiteral,
+                Identifier,
+                Identifier,
+                ComparisonOperator,
+                RuleDefinitionOperator,
+            ]
+        );
+        // &name is one identifier; a bare & is punctuation
+        assert_eq!(
+            kinds("&self & self"),
+            vec![Identifier, PunctuationOperator, Identifier]
+        );
+        assert_eq!(kinds("\"open ~"), vec![StringLiteral]);
+        assert_eq!(kinds("~"), vec![Error]);
+        assert_eq!(TokenKind::Variable.node_type(), "variable");
+    }
+
+    #[test]
+    fn test_tokenize_negative_numbers_and_minus() {
+        use TokenKind::*;
+
+        assert_eq!(kinds("-5"), vec![IntegerLiteral]);
+        assert_eq!(
+            kinds("(- 1 2)"),
+            vec![
+                OpenParen,
+                ArithmeticOperator,
+                IntegerLiteral,
+                IntegerLiteral,
+                CloseParen
+            ]
+        );
+        assert_eq!(
+            kinds("(+ x -3)"),
+            vec![
+                OpenParen,
+                ArithmeticOperator,
+                Identifier,
+                IntegerLiteral,
+                CloseParen
+            ]
+        );
+        assert_eq!(
+            kinds("(- 5)"),
+            vec![OpenParen, ArithmeticOperator, IntegerLiteral, CloseParen]
+        );
+
+        // Directly after an operand the '-' is an operator
+        assert_eq!(
+            kinds("2-1 (f)-1.5 2 -1"),
+            vec![
+                IntegerLiteral,
+                ArithmeticOperator,
+                IntegerLiteral,
+                OpenParen,
+                Identifier,
+                CloseParen,
+                ArithmeticOperator,
+                FloatLiteral,
+                IntegerLiteral,
+                IntegerLiteral,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_retokenize_matches_full_relex() {
+        let old = "(= (double $x)\n   (* $x 2)) ; doubles\n!(double -21)\n(\"a b\" 1.5e3 c)\n";
+        let old_tokens = tokenize_with_kinds(old);
+
+        let edit = |start: usize, old_end: usize, replacement: &str| {
+            let new = format!("{}{}{}", &old[..start], replacement, &old[old_end..]);
+            let edit = TextEdit {
+                start,
+                old_end,
+                new_end: start + replacement.len(),
+            };
+            assert_eq!(
+                retokenize(&old_tokens, &new, edit),
+                tokenize_with_kinds(&new),
+                "edit {:?} in {:?}",
+                edit,
+                new
+            );
+        };
+
+        let double = old.find("double").unwrap();
+        edit(double + 6, double + 6, "d"); // extend an identifier
+        edit(double, double + 6, "triple"); // replace one
+        edit(double, double, "\n\n"); // add lines
+        let body = old.find("(* $x 2)").unwrap();
+        edit(body, body + 8, "$x"); // delete
+        edit(body + 3, body + 3, "1"); // `$x1`
+        let minus = old.find("-21").unwrap();
+        edit(minus - 1, minus, ""); // `double-21` is one identifier
+        edit(minus - 7, minus, "(f)"); // `(f)-21`: '-' becomes an operator
+        let string = old.find("\"a b\"").unwrap();
+        edit(string, string + 1, ""); // unbalanced quote changes everything after
+        edit(string + 2, string + 2, "\"\n\""); // split the string
+        edit(0, 0, "; comment\n");
+        edit(old.len(), old.len(), "(tail)");
+        edit(0, old.len(), "");
+    }
+
+    #[test]
+    fn test_tokenize_multiline_positions() {
+        let tokens = tokenize_with_kinds("(a\n  \"x\ny\" b)");
+        let string = tokens[2].1;
+        assert_eq!(string.start, Position::new(1, 2));
+        assert_eq!(string.end, Position::new(2, 2));
+        assert_eq!(tokens[3].1.start, Position::new(2, 3));
+    }
+}
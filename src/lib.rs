@@ -1,6 +1,7 @@
 pub mod backend;
 pub mod config;
 pub mod ir;
+pub mod lexer;
 pub mod pathmap_par_integration;
 pub mod repl;
 pub mod rholang_integration;
@@ -75,7 +76,8 @@ pub use backend::{
     models::{MettaState, MettaValue, Rule},
 };
 pub use ir::{MettaExpr, Position, SExpr, Span};
-pub use rholang_integration::run_state;
+pub use lexer::{retokenize, tokenize_with_kinds, TextEdit, TokenKind};
+pub use rholang_integration::{metta_state_from_json, run_state, run_state_json, StateJsonError};
 pub use tree_sitter_parser::TreeSitterMettaParser;
 
 // Export run_state_async when async feature is enabled (which is by default)
@@ -95,6 +97,7 @@ pub use repl::{MettaHelper, PatternHistory, QueryHighlighter, ReplStateMachine,
 #[cfg(test)]
 mod tests {
     use super::*;
+    use backend::run::eval_forms;
     use backend::*;
 
     #[test]
@@ -139,27 +142,13 @@ mod tests {
 
     #[test]
     fn test_multiple_evaluations() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (double $x) (* $x 2))
             !(double 5)
             !(double 10)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut all_results = Vec::new();
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-
-            if !expr_results.is_empty() {
-                all_results.extend(expr_results);
-            }
-        }
+        let (all_results, _) = eval_source(input, RunConfig::default()).unwrap();
 
         assert_eq!(all_results.len(), 2);
         assert_eq!(all_results[0], MettaValue::Long(10));
@@ -223,59 +212,32 @@ mod tests {
 
     #[test]
     fn test_if_lazy_evaluation_true_branch() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (boom) (error "should not evaluate" 0))
-            (if true success (boom))
+            (if True success (boom))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Atom("success".to_string())));
     }
 
     #[test]
     fn test_if_prevents_infinite_loop() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (loop) (loop))
-            (if true success (loop))
+            (if True success (loop))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Atom("success".to_string())));
     }
 
     #[test]
     fn test_factorial_with_if() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (factorial $x)
             (if (> $x 0)
@@ -284,26 +246,14 @@ mod tests {
             !(factorial 5)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(120)));
     }
 
     #[test]
     fn test_factorial_base_case() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (factorial $x)
             (if (> $x 0)
@@ -312,17 +262,8 @@ mod tests {
             !(factorial 0)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(1)));
     }
@@ -360,26 +301,14 @@ mod tests {
 
     #[test]
     fn test_if_with_function_calls_in_branches() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (double $x) (* $x 2))
             (= (triple $x) (* $x 3))
             !(if (> 10 5) (double 7) (triple 7))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(14)));
     }
@@ -423,25 +352,13 @@ mod tests {
 
     #[test]
     fn test_error_in_function_call() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (safe-op $x) (if (< $x 0) (error "negative value" $x) (* $x 2)))
             !(safe-op -5)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         if let Some(MettaValue::Error(msg, details)) = result {
             assert_eq!(msg, "negative value");
@@ -453,9 +370,6 @@ mod tests {
 
     #[test]
     fn test_error_in_recursive_function() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (div-by-zero $n)
                 (if (== $n 0)
@@ -464,17 +378,8 @@ mod tests {
             !(div-by-zero 3)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         if let Some(MettaValue::Error(msg, _)) = result {
             assert_eq!(msg, "division by zero");
@@ -572,9 +477,6 @@ mod tests {
 
     #[test]
     fn test_error_recovery_pattern() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (safe-div $x $y)
                 (if (== $y 0)
@@ -586,17 +488,7 @@ mod tests {
             !(try-div 10 2)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut results = Vec::new();
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                results.extend(expr_results);
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
 
         assert_eq!(results[0], MettaValue::Long(-1));
         assert_eq!(results[1], MettaValue::Long(5));
@@ -604,26 +496,20 @@ mod tests {
 
     #[test]
     fn test_multiple_errors_in_sequence() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (error "first" 1)
             (error "second" 2)
             (error "third" 3)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut errors = Vec::new();
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(MettaValue::Error(msg, _)) = expr_results.first() {
-                errors.push(msg.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let errors: Vec<String> = results
+            .iter()
+            .filter_map(|result| match result {
+                MettaValue::Error(msg, _) => Some(msg.clone()),
+                _ => None,
+            })
+            .collect();
 
         assert_eq!(errors.len(), 3);
         assert_eq!(errors[0], "first");
@@ -633,25 +519,13 @@ mod tests {
 
     #[test]
     fn test_error_stops_evaluation_in_expression() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (side-effect) (error "should not see this" 0))
             (+ (error "first-error" 1) (side-effect))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         if let Some(MettaValue::Error(msg, _)) = result {
             assert_eq!(msg, "first-error");
@@ -680,9 +554,6 @@ mod tests {
 
     #[test]
     fn test_catch_in_recursive_context() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (safe-fact $n)
                 (if (< $n 0)
@@ -694,17 +565,7 @@ mod tests {
             !(safe-fact -3)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut results = Vec::new();
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                results.extend(expr_results);
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
 
         assert_eq!(results[0], MettaValue::Long(120));
         assert_eq!(results[1], MettaValue::Long(0));
@@ -718,88 +579,48 @@ mod tests {
 
     #[test]
     fn test_simple_recursion() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (countdown 0) done)
             (= (countdown $n) (countdown (- $n 1)))
             !(countdown 3)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut last_result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-
-            env = new_env;
-            if let Some(result) = expr_results.last() {
-                last_result = Some(result.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let last_result = results.last().cloned();
 
         assert_eq!(last_result, Some(MettaValue::Atom("done".to_string())));
     }
 
     #[test]
     fn test_recursive_list_length_safe() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (len nil) 0)
             (= (len (cons $x $xs)) (+ 1 (len $xs)))
             !(len (cons a (cons b (cons c nil))))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(3)));
     }
 
     #[test]
     fn test_recursive_list_sum() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (sum nil) 0)
             (= (sum (cons $x $xs)) (+ $x (sum $xs)))
             !(sum (cons 10 (cons 20 (cons 30 nil))))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(60)));
     }
 
     #[test]
     fn test_recursive_fibonacci() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (fib 0) 0)
             (= (fib 1) 1)
@@ -807,68 +628,35 @@ mod tests {
             !(fib 6)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(8)));
     }
 
     #[test]
     fn test_higher_order_apply_twice() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (apply-twice $f $x) ($f ($f $x)))
             (= (square $x) (* $x $x))
             !(apply-twice square 2)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut last_result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(result) = expr_results.last() {
-                last_result = Some(result.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let last_result = results.last().cloned();
 
         assert_eq!(last_result, Some(MettaValue::Long(16)));
     }
 
     #[test]
     fn test_apply_twice_with_constructor() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (apply-twice $f $x) ($f ($f $x)))
             !(apply-twice 1 2)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         if let Some(MettaValue::SExpr(outer)) = result {
             assert_eq!(outer[0], MettaValue::Long(1));
@@ -883,35 +671,20 @@ mod tests {
 
     #[test]
     fn test_apply_three_times() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (apply-three $f $x) ($f ($f ($f $x))))
             (= (inc $x) (+ $x 1))
             !(apply-three inc 10)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(13)));
     }
 
     #[test]
     fn test_compose_functions() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (compose $f $g $x) ($f ($g $x)))
             (= (double $x) (* $x 2))
@@ -919,26 +692,14 @@ mod tests {
             !(compose double inc 5)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         assert_eq!(result, Some(MettaValue::Long(12)));
     }
 
     #[test]
     fn test_map_with_square() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (mymap $f nil) nil)
             (= (mymap $f (cons $x $xs)) (cons ($f $x) (mymap $f $xs)))
@@ -946,17 +707,8 @@ mod tests {
             !(mymap square (cons 1 (cons 2 (cons 3 nil))))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         if let Some(MettaValue::SExpr(items)) = result {
             assert_eq!(items[0], MettaValue::Atom("cons".to_string()));
@@ -978,9 +730,6 @@ mod tests {
 
     #[test]
     fn test_filter_positive_numbers() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (filter $pred nil) nil)
             (= (filter $pred (cons $x $xs))
@@ -991,17 +740,8 @@ mod tests {
             !(filter positive (cons 5 (cons -3 (cons 7 nil))))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         // Should keep only 5 and 7: (cons 5 (cons 7 nil))
         if let Some(MettaValue::SExpr(items)) = result {
@@ -1019,9 +759,6 @@ mod tests {
 
     #[test]
     fn test_fold_left() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (foldl $f $acc nil) $acc)
             (= (foldl $f $acc (cons $x $xs))
@@ -1029,17 +766,8 @@ mod tests {
             !(foldl + 0 (cons 1 (cons 2 (cons 3 nil))))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         // foldl(+, 0, [1,2,3]) = ((0+1)+2)+3 = 6
         assert_eq!(result, Some(MettaValue::Long(6)));
@@ -1047,26 +775,14 @@ mod tests {
 
     #[test]
     fn test_append_lists() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (append nil $ys) $ys)
             (= (append (cons $x $xs) $ys) (cons $x (append $xs $ys)))
             !(append (cons 1 (cons 2 nil)) (cons 3 (cons 4 nil)))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(r) = expr_results.last() {
-                result = Some(r.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let result = results.last().cloned();
 
         if let Some(MettaValue::SExpr(items)) = result {
             assert_eq!(items[0], MettaValue::Atom("cons".to_string()));
@@ -1078,26 +794,14 @@ mod tests {
 
     #[test]
     fn test_simple_list_length() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (len nil) 0)
             (= (len (cons $x $xs)) (+ 1 (len $xs)))
             !(len (cons a (cons b (cons c nil))))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut last_result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if let Some(result) = expr_results.last() {
-                last_result = Some(result.clone());
-            }
-        }
+        let (results, _) = eval_source(input, RunConfig::default()).unwrap();
+        let last_result = results.last().cloned();
 
         assert_eq!(last_result, Some(MettaValue::Long(3)));
     }
@@ -1129,26 +833,14 @@ mod tests {
 
     #[test]
     fn test_basic_nondeterminism() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (coin) heads)
             (= (coin) tails)
             !(coin)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 2);
@@ -1161,26 +853,14 @@ mod tests {
 
     #[test]
     fn test_binary_bit_nondeterminism() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (bin) 0)
             (= (bin) 1)
             !(bin)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 2);
@@ -1193,9 +873,6 @@ mod tests {
 
     #[test]
     fn test_working_nondeterminism() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (= (pair) (cons 0 0))
             (= (pair) (cons 0 1))
@@ -1204,17 +881,8 @@ mod tests {
             !(pair)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 4);
@@ -1225,9 +893,6 @@ mod tests {
 
     #[test]
     fn test_nondeterministic_nested_application() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         // Test that g is applied to ALL expansions of f
         // (f) -> [1, 2, 3]
         // (g $x) -> (* $x $x)
@@ -1240,17 +905,8 @@ mod tests {
             !(g (f))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 3);
@@ -1264,9 +920,6 @@ mod tests {
 
     #[test]
     fn test_nondeterministic_cartesian_product() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         // Test Cartesian product: when BOTH operands are nondeterministic
         // (a) -> [1, 2]
         // (b) -> [10, 20]
@@ -1279,17 +932,8 @@ mod tests {
             !(+ (a) (b))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 4);
@@ -1304,9 +948,6 @@ mod tests {
 
     #[test]
     fn test_nondeterministic_triple_product() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         // Test triple Cartesian product
         // (x) -> [1, 2]
         // (y) -> [10, 20]
@@ -1322,17 +963,8 @@ mod tests {
             !(cons (x) (cons (y) (z)))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 8);
@@ -1343,9 +975,6 @@ mod tests {
 
     #[test]
     fn test_nondeterministic_deeply_nested() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         // Test deeply nested nondeterministic application
         // (f) -> [1, 2]
         // (g $x) -> (* $x 10)
@@ -1359,17 +988,8 @@ mod tests {
             !(h (g (f)))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 2);
@@ -1382,9 +1002,6 @@ mod tests {
 
     #[test]
     fn test_nondeterministic_with_pattern_matching() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         // Test nondeterminism combined with pattern matching
         // (color) -> [red, green, blue]
         // (intensity $c) matches all colors and returns different values
@@ -1398,17 +1015,8 @@ mod tests {
             !(intensity (color))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 3);
@@ -1422,26 +1030,14 @@ mod tests {
 
     #[test]
     fn test_match_basic_pattern() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (leaf1 leaf2)
             (leaf0 leaf1)
             !(match &self ($x leaf2) $x)
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 1);
@@ -1454,9 +1050,6 @@ mod tests {
 
     #[test]
     fn test_match_multiple_bindings() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (Sam is a frog)
             (Tom is a cat)
@@ -1464,17 +1057,8 @@ mod tests {
             !(match &self ($who is a $what) ($who the $what))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 3);
@@ -1506,25 +1090,13 @@ mod tests {
 
     #[test]
     fn test_match_nested_structure() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             ((nested value) result)
             !(match &self (($x $y) result) (found $x and $y))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 1);
@@ -1573,26 +1145,14 @@ mod tests {
 
     #[test]
     fn test_match_with_numbers() {
-        use crate::backend::compile::compile;
-        use crate::backend::eval::eval;
-
         let input = r#"
             (number 42)
             (number 100)
             !(match &self (number $n) (value $n))
         "#;
 
-        let state = compile(input).unwrap();
-        let mut env = state.environment;
-        let mut result = None;
-
-        for expr in state.source {
-            let (expr_results, new_env) = eval(expr, env);
-            env = new_env;
-            if !expr_results.is_empty() {
-                result = Some(expr_results);
-            }
-        }
+        let (forms, _) = eval_forms(input, Environment::new());
+        let result = forms.into_iter().rev().find(|results| !results.is_empty());
 
         if let Some(results) = result {
             assert_eq!(results.len(), 2);
@@ -7,8 +7,10 @@ use rustyline::Editor;
 use std::env;
 use std::fs;
 use std::io::{self, Read, Write};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 const VERSION: &str = env!("CARGO_PKG_VERSION");
 
@@ -23,7 +25,14 @@ fn print_usage() {
     eprintln!("    -v, --version        Print version information");
     eprintln!("    -o, --output <FILE>  Write output to FILE (default: stdout)");
     eprintln!("    --sexpr              Print S-expressions instead of evaluating");
+    eprintln!("    --ast-json           Print the parsed S-expression tree as JSON");
     eprintln!("    --repl               Start interactive REPL");
+    eprintln!("    --load <FILE>        Preload definitions from FILE");
+    eprintln!("    --save-env <FILE>    Write rules, facts and types to FILE after evaluation");
+    eprintln!("    --seed <N>           Seed random-int/random-float for reproducible runs");
+    eprintln!("    --metrics            Print execution metrics to stderr after evaluation");
+    eprintln!("    --trace-rules        Log each rule application to stderr");
+    eprintln!("    --stdlib             Preload the standard library (len, map, filter, ...)");
     eprintln!("    --eval               Evaluate and print results (default)");
     eprintln!();
     eprintln!("ARGUMENTS:");
@@ -32,7 +41,11 @@ fn print_usage() {
     eprintln!("EXAMPLES:");
     eprintln!("    mettatron input.metta");
     eprintln!("    mettatron --repl");
+    eprintln!("    mettatron --repl --load defs.metta");
+    eprintln!("    mettatron --seed 42 simulation.metta");
+    eprintln!("    mettatron --save-env session.metta input.metta");
     eprintln!("    mettatron --sexpr input.metta");
+    eprintln!("    mettatron --ast-json input.metta");
     eprintln!("    cat input.metta | mettatron -");
 }
 
@@ -44,7 +57,14 @@ struct Options {
     input: Option<String>,
     output: Option<String>,
     show_sexpr: bool,
+    ast_json: bool,
     repl_mode: bool,
+    load: Option<String>,
+    save_env: Option<String>,
+    seed: Option<u64>,
+    metrics: bool,
+    trace_rules: bool,
+    stdlib: bool,
 }
 
 fn parse_args() -> Result<Options, String> {
@@ -53,7 +73,14 @@ fn parse_args() -> Result<Options, String> {
     let mut input = None;
     let mut output = None;
     let mut show_sexpr = false;
+    let mut ast_json = false;
     let mut repl_mode = false;
+    let mut load = None;
+    let mut save_env = None;
+    let mut seed = None;
+    let mut metrics = false;
+    let mut trace_rules = false;
+    let mut stdlib = false;
     let mut i = 1;
 
     while i < args.len() {
@@ -76,9 +103,47 @@ fn parse_args() -> Result<Options, String> {
             "--sexpr" => {
                 show_sexpr = true;
             }
+            "--ast-json" => {
+                ast_json = true;
+            }
             "--repl" => {
                 repl_mode = true;
             }
+            "--load" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("Missing file after --load".to_string());
+                }
+                load = Some(args[i].clone());
+            }
+            "--save-env" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("Missing file after --save-env".to_string());
+                }
+                save_env = Some(args[i].clone());
+            }
+            "--seed" => {
+                i += 1;
+                if i >= args.len() {
+                    return Err("Missing number after --seed".to_string());
+                }
+                seed = Some(args[i].parse::<u64>().map_err(|_| {
+                    format!(
+                        "Invalid seed '{}': expected a non-negative integer",
+                        args[i]
+                    )
+                })?);
+            }
+            "--metrics" => {
+                metrics = true;
+            }
+            "--trace-rules" => {
+                trace_rules = true;
+            }
+            "--stdlib" => {
+                stdlib = true;
+            }
             "--eval" => {
                 // Default mode, no-op
             }
@@ -99,7 +164,14 @@ fn parse_args() -> Result<Options, String> {
         input,
         output,
         show_sexpr,
+        ast_json,
         repl_mode,
+        load,
+        save_env,
+        seed,
+        metrics,
+        trace_rules,
+        stdlib,
     })
 }
 
@@ -137,35 +209,14 @@ fn write_output(output: Option<&str>, content: &str) -> Result<(), String> {
     }
 }
 
-fn format_result(value: &MettaValue) -> String {
-    match value {
-        MettaValue::Atom(s) => s.clone(),
-        MettaValue::Bool(b) => b.to_string(),
-        MettaValue::Long(n) => n.to_string(),
-        MettaValue::Float(f) => f.to_string(),
-        MettaValue::String(s) => format!("\"{}\"", s),
-        MettaValue::Nil => "Nil".to_string(),
-        MettaValue::Error(msg, details) => {
-            // Format as (Error "msg" details) to match MeTTa spec
-            format!("(Error {} {})", msg, format_result(details))
-        }
-        MettaValue::Type(t) => format!("Type({})", format_result(t)),
-        MettaValue::SExpr(items) => {
-            let formatted: Vec<String> = items.iter().map(format_result).collect();
-            format!("({})", formatted.join(" "))
-        }
-        MettaValue::Conjunction(goals) => {
-            let formatted: Vec<String> = goals.iter().map(format_result).collect();
-            format!("(, {})", formatted.join(" "))
-        }
-    }
-}
-
+/// Format the results of one expression as a list
+/// Unit prints as `[()]`, while an expression with no results, such as `(empty)`,
+/// prints as the empty list `[]`.
 fn format_results(results: &[MettaValue]) -> String {
     if results.is_empty() {
         return "[]".to_string();
     }
-    let formatted: Vec<String> = results.iter().map(format_result).collect();
+    let formatted: Vec<String> = results.iter().map(|r| r.to_string()).collect();
     format!("[{}]", formatted.join(", "))
 }
 
@@ -182,9 +233,49 @@ fn eval_metta(input: &str, options: &Options) -> Result<String, String> {
         return Ok(output);
     }
 
+    if options.ast_json {
+        // Parse with Tree-Sitter and show the trees as a JSON array
+        let mut parser = mettatron::TreeSitterMettaParser::new()
+            .map_err(|e| format!("Failed to initialize parser: {}", e))?;
+        let sexprs = parser.parse(input).map_err(|e| e.to_string())?;
+        let nodes: Vec<String> = sexprs.iter().map(|sexpr| sexpr.to_json_string()).collect();
+        return Ok(format!("[{}]\n", nodes.join(",")));
+    }
+
     // Compile to MettaValue
-    let state = compile(input).map_err(|e| e.to_string())?;
+    let mut env = if options.stdlib {
+        Environment::with_stdlib()
+    } else {
+        Environment::new()
+    };
+    if let Some(path) = options.load.as_deref() {
+        let (loaded_env, errors) = preload_file(path, env);
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+        env = loaded_env;
+    }
+    let state = compile_checked(input, env).map_err(|e| match (&e, options.input.as_deref()) {
+        // Arity errors carry a line:col location; name the file in front of it unless
+        // a `;#line` directive already did
+        (CompileError::InvalidArity { file: None, .. }, Some(path)) if path != "-" => {
+            format!("{}:{}", path, e)
+        }
+        _ => e.to_string(),
+    })?;
     let mut env = state.environment;
+    if let Some(seed) = options.seed {
+        env.seed_rng(seed);
+    }
+    env.set_collect_metrics(options.metrics);
+    if options.trace_rules {
+        env.set_rule_trace(Some(stderr_rule_trace()));
+    }
+
+    // Resolve relative import! paths against the input file's directory
+    if let Some(path) = options.input.as_deref().filter(|p| *p != "-") {
+        env.set_module_path(Path::new(path).parent().map(|p| p.to_path_buf()));
+    }
 
     // Evaluate each expression
     let mut output = String::new();
@@ -194,6 +285,7 @@ fn eval_metta(input: &str, options: &Options) -> Result<String, String> {
 
         let (results, new_env) = eval(sexpr, env);
         env = new_env;
+        let results = flatten_conjunction_results(results);
 
         // Print results with list notation (only for S-expressions)
         if should_output && !results.is_empty() {
@@ -201,9 +293,23 @@ fn eval_metta(input: &str, options: &Options) -> Result<String, String> {
         }
     }
 
+    if options.metrics {
+        eprintln!("{}", env.metrics());
+    }
+
+    if let Some(path) = options.save_env.as_deref() {
+        fs::write(path, env.to_metta_source())
+            .map_err(|e| format!("Failed to write environment to '{}': {}", path, e))?;
+    }
+
     Ok(output)
 }
 
+/// Rule trace sink printing each rule application to stderr
+fn stderr_rule_trace() -> RuleTraceSink {
+    Arc::new(|line: &str| eprintln!("[rule] {}", line))
+}
+
 /// Check if stdout is a TTY (for conditional color output)
 fn is_stdout_tty() -> bool {
     use std::io::IsTerminal;
@@ -233,22 +339,147 @@ fn highlight_output(text: &str, highlighter: Option<&QueryHighlighter>) -> Strin
     }
 }
 
-fn run_repl() {
+/// Most history entries loaded into a new REPL session
+const MAX_HISTORY_ENTRIES: usize = 1000;
+
+/// File the REPL keeps its input history in: `~/.mettatron_history`
+fn history_path() -> Option<PathBuf> {
+    env::var_os("HOME").map(|home| PathBuf::from(home).join(".mettatron_history"))
+}
+
+/// Read the most recent history entries, oldest first
+/// A missing or unreadable file is an empty history.
+fn read_history(path: &Path) -> Vec<String> {
+    let Ok(text) = fs::read_to_string(path) else {
+        return Vec::new();
+    };
+    let entries: Vec<String> = text
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    let skip = entries.len().saturating_sub(MAX_HISTORY_ENTRIES);
+    entries.into_iter().skip(skip).collect()
+}
+
+/// Append one entry to the history file, creating it if needed
+/// A multi-line entry is stored on a single line.
+fn append_history(path: &Path, entry: &str) -> io::Result<()> {
+    let line = entry.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{}", line)
+}
+
+/// Evaluate a file of definitions into `env` before the REPL starts
+/// Returns the seeded environment and any errors encountered; errors never abort the preload
+fn preload_file(path: &str, env: Environment) -> (Environment, Vec<String>) {
+    let mut errors = Vec::new();
+
+    let source = match read_input(path) {
+        Ok(source) => source,
+        Err(e) => {
+            errors.push(e);
+            return (env, errors);
+        }
+    };
+
+    let state = match compile_incremental(&source, env.clone()) {
+        Ok(state) => state,
+        Err(e) => {
+            errors.push(format!("{}: {}", path, e));
+            return (env, errors);
+        }
+    };
+
+    let mut env = state.environment;
+    let previous_path = env.module_path();
+    env.set_module_path(Path::new(path).parent().map(|p| p.to_path_buf()));
+
+    for sexpr in state.source {
+        let (results, new_env) = eval(sexpr, env);
+        env = new_env;
+        errors.extend(
+            results
+                .iter()
+                .filter(|r| matches!(r, MettaValue::Error(_, _)))
+                .map(|r| format!("{}: {}", path, r)),
+        );
+    }
+
+    env.set_module_path(previous_path);
+    (env, errors)
+}
+
+fn run_repl(options: &Options) {
     println!("MeTTaTron REPL v{}", VERSION);
     println!("Enter MeTTa expressions. Type 'exit' or 'quit' to exit.");
+    println!("Type ':doc <symbol>' to show documentation attached with @doc.");
+    println!("Type ':stats' to show the size of the environment.");
+    println!("Type ':intern' to show symbol interning diagnostics.");
+    println!("Type ':trace-rules on|off' to log each rule application.");
+    println!("Type ':reset' to clear every rule, fact and state.");
     println!("Multi-line input: Press ENTER on incomplete expressions to continue.\n");
 
     // Create rustyline editor with MettaHelper
     let mut editor: Editor<MettaHelper, DefaultHistory> = Editor::new().unwrap();
-    let helper = MettaHelper::new().expect("Failed to create MettaHelper");
+    let mut helper = MettaHelper::new().expect("Failed to create MettaHelper");
+
+    // Recall input from earlier sessions
+    let history_file = history_path();
+    if let Some(path) = history_file.as_deref() {
+        for entry in read_history(path) {
+            editor.add_history_entry(entry.as_str()).ok();
+            helper.add_to_history(entry);
+        }
+    }
+    let mut history_write_failed = false;
     editor.set_helper(Some(helper));
 
     // Create output highlighter
     let output_highlighter = QueryHighlighter::new().ok();
 
-    let mut env = Environment::new();
+    let mut env = if options.stdlib {
+        Environment::with_stdlib()
+    } else {
+        Environment::new()
+    };
+    if let Some(seed) = options.seed {
+        env.seed_rng(seed);
+    }
+    if options.trace_rules {
+        env.set_rule_trace(Some(stderr_rule_trace()));
+    }
+
+    // Ctrl-C while an expression is evaluating interrupts that evaluation instead of
+    // killing the REPL (at the prompt, rustyline reports it as ReadlineError::Interrupted)
+    let interrupt = Arc::new(AtomicBool::new(false));
+    env.set_interrupt_flag(interrupt.clone());
+    {
+        let interrupt = interrupt.clone();
+        if let Err(e) = ctrlc::set_handler(move || interrupt.store(true, Ordering::Relaxed)) {
+            eprintln!("Warning: could not install Ctrl-C handler: {}", e);
+        }
+    }
+
     let mut line_num = 1;
 
+    if let Some(path) = options.load.as_deref() {
+        let (loaded_env, errors) = preload_file(path, env);
+        env = loaded_env;
+        for error in &errors {
+            eprintln!("Error: {}", error);
+        }
+        if errors.is_empty() {
+            println!("Loaded {}", path);
+        }
+        if let Some(helper) = editor.helper_mut() {
+            helper.update_from_environment(&env);
+        }
+    }
+
     loop {
         let prompt = create_prompt(line_num);
         let readline = editor.readline(&prompt);
@@ -266,24 +497,95 @@ fn run_repl() {
                     continue;
                 }
 
-                // Add to history
+                // Add to history, and to the history file for later sessions
                 editor.add_history_entry(input).ok();
+                if let Some(path) = history_file.as_deref() {
+                    if let Err(e) = append_history(path, input) {
+                        if !history_write_failed {
+                            eprintln!(
+                                "Warning: could not save history to {}: {}",
+                                path.display(),
+                                e
+                            );
+                            history_write_failed = true;
+                        }
+                    }
+                }
+
+                // :doc <symbol> - show documentation attached via @doc
+                if let Some(symbol) = input.strip_prefix(":doc") {
+                    let symbol = symbol.trim();
+                    if symbol.is_empty() {
+                        eprintln!("Usage: :doc <symbol>");
+                    } else {
+                        match env.get_doc(symbol) {
+                            Some(doc) => println!("{}: {}", symbol, doc),
+                            None => println!("No documentation for '{}'", symbol),
+                        }
+                    }
+                    line_num += 1;
+                    continue;
+                }
+
+                // :stats - show the size of the environment
+                if input == ":stats" {
+                    for (name, count) in env.stats().entries() {
+                        println!("{:<20} {}", name, count);
+                    }
+                    line_num += 1;
+                    continue;
+                }
+
+                // :intern - show symbol interning diagnostics
+                if input == ":intern" {
+                    for (name, count) in env.intern_stats().entries() {
+                        println!("{:<20} {}", name, count);
+                    }
+                    line_num += 1;
+                    continue;
+                }
+
+                // :reset - forget every rule, fact and state defined so far
+                if input == ":reset" {
+                    env.clear();
+                    println!("Environment cleared");
+                    line_num += 1;
+                    continue;
+                }
+
+                // :trace-rules on|off - log each rule application
+                if let Some(mode) = input.strip_prefix(":trace-rules") {
+                    match mode.trim() {
+                        "on" => env.set_rule_trace(Some(stderr_rule_trace())),
+                        "off" => env.set_rule_trace(None),
+                        _ => eprintln!("Usage: :trace-rules on|off"),
+                    }
+                    line_num += 1;
+                    continue;
+                }
 
                 // Add to helper's history for inline hints
                 if let Some(helper) = editor.helper_mut() {
                     helper.add_to_history(input.to_string());
                 }
 
-                match compile(input) {
+                match compile_incremental(input, env.clone()) {
                     Ok(state) => {
-                        env = env.union(&state.environment);
+                        env = state.environment;
 
+                        interrupt.store(false, Ordering::Relaxed);
                         for sexpr in state.source {
                             // Only output results for S-expressions, not atoms or ground types
                             let should_output = matches!(sexpr, MettaValue::SExpr(_));
 
                             let (results, updated_env) = eval(sexpr.clone(), env.clone());
                             env = updated_env;
+                            if interrupt.load(Ordering::Relaxed) {
+                                // Skip the rest of the input after an interrupted evaluation
+                                println!("Interrupted");
+                                break;
+                            }
+                            let results = flatten_conjunction_results(results);
 
                             // Print results with syntax highlighting (only for S-expressions)
                             if should_output && !results.is_empty() {
@@ -335,7 +637,7 @@ fn main() {
 
     // REPL mode
     if options.repl_mode {
-        run_repl();
+        run_repl(&options);
         return;
     }
 
@@ -369,3 +671,177 @@ fn main() {
         process::exit(1);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_temp(name: &str, content: &str) -> String {
+        let path = env::temp_dir().join(format!("mettatron-{}-{}", std::process::id(), name));
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().into_owned()
+    }
+
+    #[test]
+    fn test_preload_rule_is_usable() {
+        let path = write_temp("preload.metta", "(= (square $x) (* $x $x))\n");
+        let (env, errors) = preload_file(&path, Environment::new());
+        assert!(errors.is_empty(), "{:?}", errors);
+
+        let (results, _) = eval_source_in("!(square 7)", env, RunConfig::default()).unwrap();
+        assert_eq!(results, vec![MettaValue::Long(49)]);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_preload_errors_are_reported_but_keep_definitions() {
+        let path = write_temp(
+            "preload-errors.metta",
+            "(= (one) 1)\n!(error boom 0)\n(= (two) 2)\n",
+        );
+        let (env, errors) = preload_file(&path, Environment::new());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("boom"));
+
+        let (results, _) = eval_source_in("!(two)", env, RunConfig::default()).unwrap();
+        assert_eq!(results, vec![MettaValue::Long(2)]);
+        fs::remove_file(&path).ok();
+    }
+
+    #[test]
+    fn test_save_env_round_trips_through_load() {
+        let input = write_temp(
+            "save-env-input.metta",
+            "(: fact Type)\n(parent tom bob)\n(parent bob ann)\n\
+             (= (fact $n) (if (== $n 0) 1 (* $n (fact (- $n 1)))))\n(= (fact) 1)\n\
+             (= (parent-of $c) (match &self (parent $p $c) $p))\n\
+             !(fact 5)\n",
+        );
+        let saved = env::temp_dir().join(format!("mettatron-{}-saved.metta", std::process::id()));
+        let saved = saved.to_string_lossy().into_owned();
+        let source = fs::read_to_string(&input).unwrap();
+
+        let options = Options {
+            input: Some(input.clone()),
+            output: None,
+            show_sexpr: false,
+            ast_json: false,
+            repl_mode: false,
+            load: None,
+            save_env: Some(saved.clone()),
+            seed: None,
+            metrics: false,
+            trace_rules: false,
+            stdlib: false,
+        };
+        assert_eq!(eval_metta(&source, &options).unwrap(), "[120]\n");
+
+        // Types, then facts, then rules sorted by head and arity
+        let text = fs::read_to_string(&saved).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "(: fact Type)");
+        assert!(lines[1..3].iter().all(|line| line.starts_with("(parent ")));
+        assert!(lines[3].starts_with("(= (fact) "));
+        assert!(lines[4].starts_with("(= (fact $n)"));
+        assert!(lines[5].starts_with("(= (parent-of "));
+
+        let (env, errors) = preload_file(&saved, Environment::new());
+        assert!(errors.is_empty(), "{:?}", errors);
+        let query = "!(fact 6) !(parent-of ann) !(get-type fact)";
+        let (results, _) = eval_source_in(query, env, RunConfig::default()).unwrap();
+        assert_eq!(
+            results,
+            vec![
+                MettaValue::Long(720),
+                MettaValue::Atom("bob".to_string()),
+                MettaValue::Atom("Type".to_string()),
+            ]
+        );
+        fs::remove_file(&input).ok();
+        fs::remove_file(&saved).ok();
+    }
+
+    #[test]
+    fn test_format_unit_and_empty_results() {
+        let run = |input: &str| eval_source(input, RunConfig::default()).unwrap().0;
+        let unit = run("!(nop)");
+        assert_eq!(format_results(&unit), "[()]");
+        let asserted = run("!(assertEqual 1 1)");
+        assert_eq!(format_results(&asserted), "[()]");
+        // Both are the same unit value, not just the same text
+        assert_eq!(asserted, unit);
+        let compared = run("!(== (nop) (assertEqual 1 1))");
+        assert_eq!(format_results(&compared), "[True]");
+        let empty = run("!(empty)");
+        assert_eq!(format_results(&empty), "[]");
+    }
+
+    #[test]
+    fn test_history_round_trip() {
+        let path = env::temp_dir().join(format!("mettatron-{}-history", std::process::id()));
+        fs::remove_file(&path).ok();
+
+        // A missing file is an empty history
+        assert!(read_history(&path).is_empty());
+
+        append_history(&path, "(= (double $x) (* $x 2))").unwrap();
+        append_history(&path, "!(double\n   21)").unwrap();
+        assert_eq!(
+            read_history(&path),
+            vec!["(= (double $x) (* $x 2))", "!(double 21)"]
+        );
+
+        // Only the most recent entries are loaded
+        for i in 0..MAX_HISTORY_ENTRIES {
+            append_history(&path, &format!("!(+ {} 1)", i)).unwrap();
+        }
+        let entries = read_history(&path);
+        assert_eq!(entries.len(), MAX_HISTORY_ENTRIES);
+        assert_eq!(entries[0], "!(+ 0 1)");
+        fs::remove_file(&path).ok();
+
+        // An unwritable location reports an error instead of panicking
+        assert!(append_history(Path::new("/nonexistent/dir/history"), "x").is_err());
+    }
+
+    #[test]
+    fn test_ast_json_output() {
+        let options = Options {
+            input: None,
+            output: None,
+            show_sexpr: false,
+            ast_json: true,
+            repl_mode: false,
+            load: None,
+            save_env: None,
+            seed: None,
+            metrics: false,
+            trace_rules: false,
+            stdlib: false,
+        };
+        let span = |start: usize, end: usize| {
+            format!(
+                r#"{{"start":{{"row":0,"column":{0}}},"end":{{"row":0,"column":{1}}},"start_byte":{0},"end_byte":{1}}}"#,
+                start, end
+            )
+        };
+        let expected = format!(
+            r#"[{{"kind":"list","span":{},"children":[{{"kind":"symbol","span":{},"value":"greet"}},{{"kind":"variable","span":{},"value":"$name"}},{{"kind":"string_literal","span":{},"value":"hi \"you\""}}]}}]"#,
+            span(0, 26),
+            span(1, 6),
+            span(7, 12),
+            span(13, 25)
+        );
+        assert_eq!(
+            eval_metta(r#"(greet $name "hi \"you\"")"#, &options).unwrap(),
+            format!("{}\n", expected)
+        );
+    }
+
+    #[test]
+    fn test_preload_missing_file() {
+        let (_, errors) = preload_file("/nonexistent/defs.metta", Environment::new());
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("not found"));
+    }
+}
@@ -23,12 +23,58 @@ fn create_int_par(n: i64) -> Par {
     }])
 }
 
+// URI tags marking tuples that encode a MettaValue other than an S-expression
+// A URI cannot be produced by any other MettaValue, so tagged tuples never collide
+// with S-expressions such as `(error msg details)`
+const FLOAT_TAG: &str = "metta:float";
+const ERROR_TAG: &str = "metta:error";
+const TYPE_TAG: &str = "metta:type";
+const CONJUNCTION_TAG: &str = "metta:conjunction";
+
+/// Helper function to create a tuple Par
+fn create_tuple_par(ps: Vec<Par>) -> Par {
+    Par::default().with_exprs(vec![Expr {
+        expr_instance: Some(ExprInstance::ETupleBody(ETuple {
+            ps,
+            locally_free: Vec::new(),
+            connective_used: false,
+        })),
+    }])
+}
+
+/// Helper function to create a tuple Par whose first element is the URI `tag`
+fn create_tagged_par(tag: &str, fields: impl IntoIterator<Item = Par>) -> Par {
+    let mut ps = vec![Par::default().with_exprs(vec![Expr {
+        expr_instance: Some(ExprInstance::GUri(tag.to_string())),
+    }])];
+    ps.extend(fields);
+    create_tuple_par(ps)
+}
+
+/// Undo the escaping `metta_value_to_par` applies to the contents of a string
+fn unescape_string_par(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => out.extend(chars.next()),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
 // Magic numbers for MeTTa Environment byte arrays
 // These identify byte arrays as MeTTa-specific data for the pretty-printer
 const METTA_MULTIPLICITIES_MAGIC: &[u8] = b"MTTM"; // MeTTa Multiplicities
 const METTA_SPACE_MAGIC: &[u8] = b"MTTS"; // MeTTa Space
 
 /// Convert a MettaValue to a Rholang Par object
+///
+/// Every variant round-trips through `par_to_metta_value`. Atoms, integers and
+/// booleans map to the corresponding Rholang ground terms, strings to quoted strings,
+/// Nil to the empty Par and S-expressions to tuples. Floats, errors, types and
+/// conjunctions become tuples tagged with a `metta:` URI.
 pub fn metta_value_to_par(value: &MettaValue) -> Par {
     trace!(target: "mettatron::rholang_integration::metta_value_to_par", ?value, "MeTTa value");
 
@@ -41,7 +87,8 @@ pub fn metta_value_to_par(value: &MettaValue) -> Par {
             expr_instance: Some(ExprInstance::GBool(*b)),
         }]),
         MettaValue::Long(n) => create_int_par(*n),
-        MettaValue::Float(f) => create_string_par(f.to_string()),
+        // Display of f64 is the shortest text that parses back to the same value
+        MettaValue::Float(f) => create_tagged_par(FLOAT_TAG, [create_string_par(f.to_string())]),
         MettaValue::String(s) => {
             // Strings are quoted with escaped quotes to distinguish from atoms
             create_string_par(format!(
@@ -55,55 +102,22 @@ pub fn metta_value_to_par(value: &MettaValue) -> Par {
         }
         MettaValue::SExpr(items) => {
             // Convert S-expressions to Rholang tuples (more semantically appropriate than lists)
-            let item_pars: Vec<Par> = items.iter().map(metta_value_to_par).collect();
-
-            Par::default().with_exprs(vec![Expr {
-                expr_instance: Some(ExprInstance::ETupleBody(ETuple {
-                    ps: item_pars,
-                    locally_free: Vec::new(),
-                    connective_used: false,
-                })),
-            }])
+            create_tuple_par(items.iter().map(metta_value_to_par).collect())
         }
         MettaValue::Error(msg, details) => {
-            // Represent errors as tuples: ("error", msg, details)
-            let tag_par = create_string_par("error".to_string());
-            let msg_par = create_string_par(msg.clone());
-            let details_par = metta_value_to_par(details);
-
-            Par::default().with_exprs(vec![Expr {
-                expr_instance: Some(ExprInstance::ETupleBody(ETuple {
-                    ps: vec![tag_par, msg_par, details_par],
-                    locally_free: Vec::new(),
-                    connective_used: false,
-                })),
-            }])
+            // Represent errors as tagged tuples: (`metta:error`, msg, details)
+            create_tagged_par(
+                ERROR_TAG,
+                [create_string_par(msg.clone()), metta_value_to_par(details)],
+            )
         }
         MettaValue::Type(t) => {
-            // Represent types as tagged tuples: ("type", <inner_value>)
-            let tag_par = create_string_par("type".to_string());
-            let value_par = metta_value_to_par(t);
-
-            Par::default().with_exprs(vec![Expr {
-                expr_instance: Some(ExprInstance::ETupleBody(ETuple {
-                    ps: vec![tag_par, value_par],
-                    locally_free: Vec::new(),
-                    connective_used: false,
-                })),
-            }])
+            // Represent types as tagged tuples: (`metta:type`, <inner_value>)
+            create_tagged_par(TYPE_TAG, [metta_value_to_par(t)])
         }
         MettaValue::Conjunction(goals) => {
-            // Represent conjunctions as tagged tuples: ("conjunction", goal1, goal2, ...)
-            let mut ps = vec![create_string_par("conjunction".to_string())];
-            ps.extend(goals.iter().map(metta_value_to_par));
-
-            Par::default().with_exprs(vec![Expr {
-                expr_instance: Some(ExprInstance::ETupleBody(ETuple {
-                    ps,
-                    locally_free: Vec::new(),
-                    connective_used: false,
-                })),
-            }])
+            // Represent conjunctions as tagged tuples: (`metta:conjunction`, goal1, goal2, ...)
+            create_tagged_par(CONJUNCTION_TAG, goals.iter().map(metta_value_to_par))
         }
     };
 
@@ -379,10 +393,7 @@ pub fn par_to_metta_value(par: &Par) -> Result<MettaValue, String> {
                 // Check if it's a quoted string (starts and ends with ")
                 if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
                     // It's a string - unescape and remove quotes
-                    let unescaped = s[1..s.len() - 1]
-                        .replace("\\\"", "\"")
-                        .replace("\\\\", "\\");
-                    Ok(MettaValue::String(unescaped))
+                    Ok(MettaValue::String(unescape_string_par(&s[1..s.len() - 1])))
                 } else {
                     // It's an atom (plain string)
                     Ok(MettaValue::Atom(s.clone()))
@@ -397,61 +408,22 @@ pub fn par_to_metta_value(par: &Par) -> Result<MettaValue, String> {
                 Ok(MettaValue::SExpr(items?))
             }
             Some(ExprInstance::ETupleBody(tuple)) => {
-                // Check if it's a tagged structure (error, type)
-                // Tagged structures have string tag as first element
-                if tuple.ps.len() >= 2 {
-                    if let Some(ExprInstance::GString(tag)) = tuple.ps[0]
-                        .exprs
-                        .first()
-                        .and_then(|e| e.expr_instance.as_ref())
-                    {
-                        // Check if the tag looks like a quoted string (for distinguishing from atoms)
-                        if tag.starts_with('"') {
-                            // It's a tagged structure, not a plain S-expr
-                            match tag.as_str() {
-                                "error" => {
-                                    // Error tuple: (tag, msg, details)
-                                    if tuple.ps.len() >= 3 {
-                                        let msg = par_to_metta_value(&tuple.ps[1])?;
-                                        let details = par_to_metta_value(&tuple.ps[2])?;
-                                        if let MettaValue::String(msg_str) = msg {
-                                            Ok(MettaValue::Error(msg_str, Arc::new(details)))
-                                        } else {
-                                            Err("Error message must be a string".to_string())
-                                        }
-                                    } else {
-                                        Err("Error tuple must have 3 elements".to_string())
-                                    }
-                                }
-                                "type" => {
-                                    // Type tuple: (tag, inner_value)
-                                    let inner = par_to_metta_value(&tuple.ps[1])?;
-                                    Ok(MettaValue::Type(Arc::new(inner)))
-                                }
-                                _ => {
-                                    // Unknown tag, treat as regular S-expr
-                                    let items: Result<Vec<MettaValue>, String> =
-                                        tuple.ps.iter().map(par_to_metta_value).collect();
-                                    Ok(MettaValue::SExpr(items?))
-                                }
-                            }
-                        } else {
-                            // First element is an atom, not a tag - it's a regular S-expr
-                            let items: Result<Vec<MettaValue>, String> =
-                                tuple.ps.iter().map(par_to_metta_value).collect();
-                            Ok(MettaValue::SExpr(items?))
-                        }
-                    } else {
-                        // First element is not a string - it's a regular S-expr
+                // A tuple tagged with a URI encodes a value other than an S-expression
+                let tag = tuple
+                    .ps
+                    .first()
+                    .and_then(|p| p.exprs.first())
+                    .and_then(|e| match &e.expr_instance {
+                        Some(ExprInstance::GUri(tag)) => Some(tag.as_str()),
+                        _ => None,
+                    });
+                match tag {
+                    Some(tag) => par_tagged_to_metta_value(tag, &tuple.ps[1..]),
+                    None => {
                         let items: Result<Vec<MettaValue>, String> =
                             tuple.ps.iter().map(par_to_metta_value).collect();
                         Ok(MettaValue::SExpr(items?))
                     }
-                } else {
-                    // Small tuple, treat as S-expr
-                    let items: Result<Vec<MettaValue>, String> =
-                        tuple.ps.iter().map(par_to_metta_value).collect();
-                    Ok(MettaValue::SExpr(items?))
                 }
             }
             _ => Err("Unsupported Par expression type for MettaValue conversion".to_string()),
@@ -464,6 +436,38 @@ pub fn par_to_metta_value(par: &Par) -> Result<MettaValue, String> {
     }
 }
 
+/// Convert the fields of a tuple tagged with `tag` back to the MettaValue it encodes
+fn par_tagged_to_metta_value(tag: &str, fields: &[Par]) -> Result<MettaValue, String> {
+    let plain_string = |par: &Par| match par.exprs.first().and_then(|e| e.expr_instance.as_ref()) {
+        Some(ExprInstance::GString(s)) => Ok(s.clone()),
+        _ => Err(format!("{} tuple must hold a string", tag)),
+    };
+    match (tag, fields) {
+        (FLOAT_TAG, [value]) => {
+            let text = plain_string(value)?;
+            text.parse()
+                .map(MettaValue::Float)
+                .map_err(|_| format!("Invalid float in {} tuple: {}", tag, text))
+        }
+        (ERROR_TAG, [msg, details]) => Ok(MettaValue::Error(
+            plain_string(msg)?,
+            Arc::new(par_to_metta_value(details)?),
+        )),
+        (TYPE_TAG, [inner]) => Ok(MettaValue::Type(Arc::new(par_to_metta_value(inner)?))),
+        (CONJUNCTION_TAG, goals) => {
+            let goals: Result<Vec<MettaValue>, String> =
+                goals.iter().map(par_to_metta_value).collect();
+            Ok(MettaValue::Conjunction(goals?))
+        }
+        (FLOAT_TAG | ERROR_TAG | TYPE_TAG, _) => Err(format!(
+            "{} tuple has the wrong number of fields: {}",
+            tag,
+            fields.len()
+        )),
+        _ => Err(format!("Unknown MeTTa tag in Par tuple: {}", tag)),
+    }
+}
+
 /// Convert a Rholang Par back to Environment
 /// Deserializes the Space's PathMap and multiplicities from byte arrays
 /// Expects an ETuple with named fields:
@@ -835,17 +839,17 @@ mod tests {
     fn test_environment_serialization_roundtrip() {
         // Create an environment with a rule
         let mut env = Environment::new();
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("double".to_string()),
                 MettaValue::Atom("$x".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("mul".to_string()),
                 MettaValue::Atom("$x".to_string()),
                 MettaValue::Long(2),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Verify original environment
@@ -1055,6 +1059,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_metta_value_par_round_trip_all_variants() {
+        let atom = |s: &str| MettaValue::Atom(s.to_string());
+        let values = vec![
+            atom("test"),
+            atom("$x"),
+            MettaValue::Bool(true),
+            MettaValue::Bool(false),
+            MettaValue::Long(i64::MIN),
+            MettaValue::Long(0),
+            MettaValue::Float(1.5),
+            MettaValue::Float(0.1),
+            MettaValue::Float(-2.0),
+            MettaValue::Float(1e300),
+            MettaValue::Float(f64::INFINITY),
+            MettaValue::String(String::new()),
+            MettaValue::String(r#"quote " and backslash \ and \" both"#.to_string()),
+            MettaValue::String("test".to_string()),
+            MettaValue::Nil,
+            MettaValue::SExpr(vec![]),
+            // S-expressions spelled like the tagged encodings stay S-expressions
+            MettaValue::SExpr(vec![atom("error"), atom("msg"), atom("details")]),
+            MettaValue::SExpr(vec![atom("type"), atom("Number")]),
+            MettaValue::SExpr(vec![atom("conjunction")]),
+            MettaValue::SExpr(vec![
+                atom("add"),
+                MettaValue::Float(2.5),
+                MettaValue::SExpr(vec![MettaValue::String("a".to_string()), MettaValue::Nil]),
+            ]),
+            MettaValue::Error(
+                "boom".to_string(),
+                Arc::new(MettaValue::SExpr(vec![atom("f"), MettaValue::Long(1)])),
+            ),
+            MettaValue::Error("\"quoted\"".to_string(), Arc::new(MettaValue::Nil)),
+            MettaValue::Type(Arc::new(atom("Number"))),
+            MettaValue::Conjunction(vec![]),
+            MettaValue::Conjunction(vec![
+                MettaValue::SExpr(vec![atom("p"), atom("$x")]),
+                MettaValue::Float(3.25),
+            ]),
+        ];
+
+        for value in values {
+            let par = metta_value_to_par(&value);
+            assert_eq!(par_to_metta_value(&par), Ok(value.clone()), "{:?}", value);
+        }
+
+        // NaN is never equal to itself, so compare its kind
+        let par = metta_value_to_par(&MettaValue::Float(f64::NAN));
+        assert!(matches!(par_to_metta_value(&par), Ok(MettaValue::Float(f)) if f.is_nan()));
+    }
+
     #[test]
     fn test_metta_state_to_pathmap_par() {
         let state = MettaState::new_compiled(vec![MettaValue::Long(42)]);
@@ -1267,13 +1323,13 @@ mod tests {
         ]));
 
         // Add rule that uses match (the pattern that triggered the bug)
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("is_connected".to_string()),
                 MettaValue::Atom("$from".to_string()),
                 MettaValue::Atom("$to".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("match".to_string()),
                 MettaValue::Atom("&".to_string()),
                 MettaValue::Atom("self".to_string()),
@@ -1284,7 +1340,7 @@ mod tests {
                 ]),
                 MettaValue::Bool(true),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         // Serialize to Par (this is what happens when sending to Rholang)
@@ -1363,13 +1419,13 @@ mod tests {
         ]));
 
         // Add a rule that uses match (pattern from robot_planning.rho)
-        let rule = Rule {
-            lhs: MettaValue::SExpr(vec![
+        let rule = Rule::new(
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("is_connected".to_string()), // 'o' = 111, 'n' = 110 (RESERVED!)
                 MettaValue::Atom("$from".to_string()),
                 MettaValue::Atom("$to".to_string()),
             ]),
-            rhs: MettaValue::SExpr(vec![
+            MettaValue::SExpr(vec![
                 MettaValue::Atom("match".to_string()),
                 MettaValue::Atom("&".to_string()),
                 MettaValue::Atom("self".to_string()),
@@ -1380,7 +1436,7 @@ mod tests {
                 ]),
                 MettaValue::Bool(true),
             ]),
-        };
+        );
         env.add_rule(rule);
 
         let initial_count = env.iter_rules().count();
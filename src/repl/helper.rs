@@ -105,10 +105,7 @@ impl MettaHelper {
                 MettaValue::SExpr(items) if !items.is_empty() => {
                     // Pattern like (fibonacci $n) -> extract "fibonacci"
                     if let MettaValue::Atom(name) = &items[0] {
-                        if !name.starts_with('$')
-                            && !name.starts_with('&')
-                            && !name.starts_with('\'')
-                        {
+                        if !name.starts_with('$') && !name.starts_with('\'') {
                             // It's a function name, not a variable
                             if !self.defined_functions.contains(name) {
                                 self.defined_functions.push(name.clone());
@@ -120,7 +117,7 @@ impl MettaHelper {
                     // Simple constant like (= my-const 42) -> extract "my-const"
                     // Variable names like $global-var get normalized to $a, $b, etc.
                     // so we can't reliably complete them. Only constants/functions work.
-                    if !name.starts_with('$') && !name.starts_with('&') && !name.starts_with('\'') {
+                    if !name.starts_with('$') && !name.starts_with('\'') {
                         // It's a constant/function
                         if !self.defined_functions.contains(name) {
                             self.defined_functions.push(name.clone());
@@ -5,7 +5,8 @@ use crate::backend::compile::compile;
 ///
 /// This module provides:
 /// 1. **JSON export** for debugging and inspection (`metta_state_to_json`)
-/// 2. **State evaluation** for REPL-style interaction (`run_state`, `run_state_async`)
+/// 2. **State evaluation** for REPL-style interaction (`run_state`, `run_state_async`),
+///    including states loaded from that JSON (`metta_state_from_json`, `run_state_json`)
 /// 3. **Error handling** for safe compilation (`compile_safe`)
 ///
 /// **Note**: For Rholang integration, use the PathMap Par functions in
@@ -13,7 +14,7 @@ use crate::backend::compile::compile;
 use crate::backend::fuzzy_match::FuzzyMatcher;
 use crate::backend::models::{MettaState, MettaValue};
 use crate::tree_sitter_parser::{SyntaxError, SyntaxErrorKind};
-use std::sync::OnceLock;
+use std::sync::{Arc, OnceLock};
 
 use tracing::{debug, error, info, instrument, trace, warn};
 
@@ -140,6 +141,16 @@ fn improve_error_message(error: &SyntaxError) -> String {
                 seq, suggestion
             ))
         }
+        SyntaxErrorKind::InvalidUri(_) => {
+            Some("URI literals look like `scheme:rest` or a relative reference like `path/to/x`".into())
+        }
+        SyntaxErrorKind::InvalidChar(_) => Some(
+            "character literals hold one character: #\\a, or an escape #\\n, #\\t, #\\\\, #\\'"
+                .into(),
+        ),
+        SyntaxErrorKind::NestingTooDeep(_) => {
+            Some("split deeply nested expressions into smaller definitions".into())
+        }
         SyntaxErrorKind::UnexpectedToken => {
             // Try to suggest similar keywords
             if !error.text.is_empty() {
@@ -210,7 +221,7 @@ pub fn run_state(
     accumulated_state: MettaState,
     compiled_state: MettaState,
 ) -> Result<MettaState, String> {
-    use crate::backend::eval::eval;
+    use crate::backend::eval::{eval, flatten_conjunction_results};
 
     info!("Run state");
     debug!(
@@ -233,8 +244,9 @@ pub fn run_state(
 
         // Only extend outputs for evaluation expressions (!)
         // Other S-expressions are added to the atom space but produce no outputs
+        // Top-level conjunction results expand into separate outputs
         if is_eval_expr {
-            outputs.extend(results);
+            outputs.extend(flatten_conjunction_results(results));
         }
     }
 
@@ -247,6 +259,451 @@ pub fn run_state(
     Ok(MettaState::new_accumulated(env, outputs))
 }
 
+/// Error loading a `MettaState` from JSON, see `metta_state_from_json`
+#[derive(Debug, Clone, PartialEq)]
+pub enum StateJsonError {
+    /// The input is not well-formed JSON
+    Syntax { offset: usize, message: String },
+    /// Arrays and objects are nested more than `limit` levels deep
+    NestingTooDeep { offset: usize, limit: usize },
+    /// The JSON is well-formed but does not have the shape of a `MettaState`
+    InvalidField {
+        /// Path of the offending field, e.g. `source[0].items[1].value`
+        field: String,
+        message: String,
+    },
+    /// The loaded state failed to evaluate
+    Eval(String),
+}
+
+impl std::fmt::Display for StateJsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StateJsonError::Syntax { offset, message } => {
+                write!(f, "invalid JSON at byte {}: {}", offset, message)
+            }
+            StateJsonError::NestingTooDeep { offset, limit } => write!(
+                f,
+                "invalid JSON at byte {}: nested more than {} levels deep",
+                offset, limit
+            ),
+            StateJsonError::InvalidField { field, message } if field.is_empty() => {
+                write!(f, "invalid state JSON: {}", message)
+            }
+            StateJsonError::InvalidField { field, message } => {
+                write!(f, "invalid state JSON at '{}': {}", field, message)
+            }
+            StateJsonError::Eval(message) => write!(f, "evaluation failed: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for StateJsonError {}
+
+/// Load a state in the shape written by `MettaState::to_json_string`
+///
+/// `source` and `output` must be arrays of values as written by
+/// `MettaValue::to_json_string`, and `environment` must be an object. The environment
+/// is only a summary in that format, so the loaded state starts with an empty one.
+/// Input that does not have this shape is reported with the path of the offending
+/// field; nothing is evaluated.
+pub fn metta_state_from_json(json: &str) -> Result<MettaState, StateJsonError> {
+    trace!(target: "mettatron::rholang_integration::metta_state_from_json", json);
+    let root = JsonReader::parse(json)?;
+    let fields = expect_object(&root, "")?;
+
+    let source = decode_values(json_field(fields, "source", "")?, "source")?;
+    expect_object(json_field(fields, "environment", "")?, "environment")?;
+    let output = decode_values(json_field(fields, "output", "")?, "output")?;
+
+    let mut state = MettaState::new_compiled(source);
+    state.output = output;
+    Ok(state)
+}
+
+/// Run a compiled state given as JSON against `accumulated_state`
+///
+/// Like `run_state`, with the compiled state loaded by `metta_state_from_json`, so
+/// malformed JSON is returned as a `StateJsonError` instead of being evaluated.
+pub fn run_state_json(
+    accumulated_state: MettaState,
+    compiled_json: &str,
+) -> Result<MettaState, StateJsonError> {
+    let compiled_state = metta_state_from_json(compiled_json)?;
+    run_state(accumulated_state, compiled_state).map_err(StateJsonError::Eval)
+}
+
+/// Parsed JSON document, kept only as long as it takes to decode a state
+enum Json {
+    Null,
+    Bool(bool),
+    /// Number as written, so integers are not rounded through f64
+    Number(String),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    fn kind(&self) -> &'static str {
+        match self {
+            Json::Null => "null",
+            Json::Bool(_) => "a boolean",
+            Json::Number(_) => "a number",
+            Json::String(_) => "a string",
+            Json::Array(_) => "an array",
+            Json::Object(_) => "an object",
+        }
+    }
+}
+
+/// Deepest nesting of arrays and objects `JsonReader` accepts; an expression level
+/// takes two JSON levels (the value object and its `items` array), so this leaves
+/// room for any expression the parser accepts
+const MAX_JSON_DEPTH: usize = 2 * crate::tree_sitter_parser::DEFAULT_MAX_NESTING_DEPTH + 16;
+
+/// Recursive-descent reader for the JSON written by `to_json_string`
+struct JsonReader<'a> {
+    src: &'a str,
+    pos: usize,
+    /// Arrays and objects currently open, limited to `MAX_JSON_DEPTH`
+    depth: usize,
+}
+
+impl<'a> JsonReader<'a> {
+    fn parse(src: &'a str) -> Result<Json, StateJsonError> {
+        let mut reader = JsonReader {
+            src,
+            pos: 0,
+            depth: 0,
+        };
+        let value = reader.value()?;
+        reader.skip_whitespace();
+        if reader.pos < src.len() {
+            return Err(reader.error("unexpected characters after the document"));
+        }
+        Ok(value)
+    }
+
+    fn error(&self, message: impl Into<String>) -> StateJsonError {
+        StateJsonError::Syntax {
+            offset: self.pos,
+            message: message.into(),
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.src[self.pos..].chars().next()
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = &self.src[self.pos..];
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), StateJsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(found) if found == c => {
+                self.pos += 1;
+                Ok(())
+            }
+            Some(found) => Err(self.error(format!("expected '{}', found '{}'", c, found))),
+            None => Err(self.error(format!("expected '{}', found end of input", c))),
+        }
+    }
+
+    fn value(&mut self) -> Result<Json, StateJsonError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.nested(Self::object),
+            Some('[') => self.nested(Self::array),
+            Some('"') => self.string().map(Json::String),
+            Some('t') => self.keyword("true", Json::Bool(true)),
+            Some('f') => self.keyword("false", Json::Bool(false)),
+            Some('n') => self.keyword("null", Json::Null),
+            Some(c) if c == '-' || c.is_ascii_digit() => Ok(self.number()),
+            Some(c) => Err(self.error(format!("unexpected character '{}'", c))),
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    /// Read an array or object one level deeper, failing instead of recursing past
+    /// `MAX_JSON_DEPTH`
+    fn nested(
+        &mut self,
+        read: fn(&mut Self) -> Result<Json, StateJsonError>,
+    ) -> Result<Json, StateJsonError> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(StateJsonError::NestingTooDeep {
+                offset: self.pos,
+                limit: MAX_JSON_DEPTH,
+            });
+        }
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn keyword(&mut self, word: &str, value: Json) -> Result<Json, StateJsonError> {
+        if self.src[self.pos..].starts_with(word) {
+            self.pos += word.len();
+            Ok(value)
+        } else {
+            Err(self.error(format!("expected '{}'", word)))
+        }
+    }
+
+    fn number(&mut self) -> Json {
+        let rest = &self.src[self.pos..];
+        let len = rest
+            .find(|c: char| !(c.is_ascii_digit() || matches!(c, '-' | '+' | '.' | 'e' | 'E')))
+            .unwrap_or(rest.len());
+        self.pos += len;
+        Json::Number(rest[..len].to_string())
+    }
+
+    fn string(&mut self) -> Result<String, StateJsonError> {
+        self.expect('"')?;
+        let mut out = String::new();
+        loop {
+            let Some(c) = self.peek() else {
+                return Err(self.error("unterminated string"));
+            };
+            self.pos += c.len_utf8();
+            match c {
+                '"' => return Ok(out),
+                '\\' => {
+                    let Some(escape) = self.peek() else {
+                        return Err(self.error("unterminated string"));
+                    };
+                    self.pos += escape.len_utf8();
+                    match escape {
+                        '"' | '\\' | '/' => out.push(escape),
+                        'n' => out.push('\n'),
+                        'r' => out.push('\r'),
+                        't' => out.push('\t'),
+                        'b' => out.push('\u{8}'),
+                        'f' => out.push('\u{c}'),
+                        'u' => out.push(self.unicode_escape()?),
+                        other => return Err(self.error(format!("invalid escape '\\{}'", other))),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+    }
+
+    /// The character of a `\u` escape whose `\u` has been read, combining a UTF-16
+    /// surrogate pair written as two escapes
+    fn unicode_escape(&mut self) -> Result<char, StateJsonError> {
+        let high = self.hex4()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high).ok_or_else(|| self.error("invalid \\u escape"));
+        }
+        if !self.src[self.pos..].starts_with("\\u") {
+            return Err(self.error("unpaired surrogate in \\u escape"));
+        }
+        self.pos += 2;
+        let low = self.hex4()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return Err(self.error("unpaired surrogate in \\u escape"));
+        }
+        let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+        char::from_u32(code).ok_or_else(|| self.error("invalid \\u escape"))
+    }
+
+    /// Four hex digits of a `\u` escape
+    fn hex4(&mut self) -> Result<u32, StateJsonError> {
+        let hex = self.src.get(self.pos..self.pos + 4).unwrap_or("");
+        if !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(self.error("invalid \\u escape"));
+        }
+        let value = u32::from_str_radix(hex, 16).map_err(|_| self.error("invalid \\u escape"))?;
+        self.pos += 4;
+        Ok(value)
+    }
+
+    fn array(&mut self) -> Result<Json, StateJsonError> {
+        self.expect('[')?;
+        let mut items = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.pos += 1;
+            return Ok(Json::Array(items));
+        }
+        loop {
+            items.push(self.value()?);
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some(']') => {
+                    self.pos += 1;
+                    return Ok(Json::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']' in array")),
+            }
+        }
+    }
+
+    fn object(&mut self) -> Result<Json, StateJsonError> {
+        self.expect('{')?;
+        let mut fields = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.pos += 1;
+            return Ok(Json::Object(fields));
+        }
+        loop {
+            self.skip_whitespace();
+            let name = self.string()?;
+            self.expect(':')?;
+            fields.push((name, self.value()?));
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.pos += 1,
+                Some('}') => {
+                    self.pos += 1;
+                    return Ok(Json::Object(fields));
+                }
+                _ => return Err(self.error("expected ',' or '}' in object")),
+            }
+        }
+    }
+}
+
+fn field_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}.{}", path, name)
+    }
+}
+
+fn invalid_field(field: impl Into<String>, message: impl Into<String>) -> StateJsonError {
+    StateJsonError::InvalidField {
+        field: field.into(),
+        message: message.into(),
+    }
+}
+
+fn expect_object<'j>(json: &'j Json, path: &str) -> Result<&'j [(String, Json)], StateJsonError> {
+    match json {
+        Json::Object(fields) => Ok(fields),
+        other => Err(invalid_field(
+            path,
+            format!("expected an object, got {}", other.kind()),
+        )),
+    }
+}
+
+fn json_field<'j>(
+    fields: &'j [(String, Json)],
+    name: &str,
+    path: &str,
+) -> Result<&'j Json, StateJsonError> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value)
+        .ok_or_else(|| invalid_field(field_path(path, name), "missing field"))
+}
+
+fn string_field<'j>(
+    fields: &'j [(String, Json)],
+    name: &str,
+    path: &str,
+) -> Result<&'j str, StateJsonError> {
+    match json_field(fields, name, path)? {
+        Json::String(s) => Ok(s),
+        other => Err(invalid_field(
+            field_path(path, name),
+            format!("expected a string, got {}", other.kind()),
+        )),
+    }
+}
+
+/// Decode an array of values written by `MettaValue::to_json_string`
+fn decode_values(json: &Json, path: &str) -> Result<Vec<MettaValue>, StateJsonError> {
+    match json {
+        Json::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| decode_value(item, &format!("{}[{}]", path, i)))
+            .collect(),
+        other => Err(invalid_field(
+            path,
+            format!("expected an array, got {}", other.kind()),
+        )),
+    }
+}
+
+/// Decode one value written by `MettaValue::to_json_string`
+fn decode_value(json: &Json, path: &str) -> Result<MettaValue, StateJsonError> {
+    let fields = expect_object(json, path)?;
+    let value_path = field_path(path, "value");
+    match string_field(fields, "type", path)? {
+        "atom" => Ok(MettaValue::Atom(
+            string_field(fields, "value", path)?.to_string(),
+        )),
+        "string" => Ok(MettaValue::String(
+            string_field(fields, "value", path)?.to_string(),
+        )),
+        "bool" => match json_field(fields, "value", path)? {
+            Json::Bool(b) => Ok(MettaValue::Bool(*b)),
+            other => Err(invalid_field(
+                value_path,
+                format!("expected a boolean, got {}", other.kind()),
+            )),
+        },
+        "number" => match json_field(fields, "value", path)? {
+            Json::Number(n) => n
+                .parse()
+                .map(MettaValue::Long)
+                .map_err(|_| invalid_field(value_path, format!("expected an integer, got {}", n))),
+            other => Err(invalid_field(
+                value_path,
+                format!("expected a number, got {}", other.kind()),
+            )),
+        },
+        "float" => match json_field(fields, "value", path)? {
+            Json::Number(n) => n
+                .parse()
+                .map(MettaValue::Float)
+                .map_err(|_| invalid_field(value_path, format!("invalid number {}", n))),
+            other => Err(invalid_field(
+                value_path,
+                format!("expected a number, got {}", other.kind()),
+            )),
+        },
+        "nil" => Ok(MettaValue::Nil),
+        "sexpr" => Ok(MettaValue::SExpr(decode_values(
+            json_field(fields, "items", path)?,
+            &field_path(path, "items"),
+        )?)),
+        "conjunction" => Ok(MettaValue::Conjunction(decode_values(
+            json_field(fields, "goals", path)?,
+            &field_path(path, "goals"),
+        )?)),
+        "error" => Ok(MettaValue::Error(
+            string_field(fields, "message", path)?.to_string(),
+            Arc::new(decode_value(
+                json_field(fields, "details", path)?,
+                &field_path(path, "details"),
+            )?),
+        )),
+        "metatype" => Ok(MettaValue::Type(Arc::new(decode_value(
+            json_field(fields, "value", path)?,
+            &value_path,
+        )?))),
+        other => Err(invalid_field(
+            field_path(path, "type"),
+            format!("unknown value type '{}'", other),
+        )),
+    }
+}
+
 /// Async version of run_state with parallel evaluation of independent expressions
 ///
 /// This function parallelizes evaluation of consecutive `!` (eval) expressions
@@ -371,7 +828,7 @@ async fn evaluate_batch_parallel(
     batch: Vec<(usize, MettaValue, bool)>,
     env: crate::backend::environment::Environment,
 ) -> Vec<(usize, Vec<MettaValue>, bool)> {
-    use crate::backend::eval::eval;
+    use crate::backend::eval::{eval, flatten_conjunction_results};
     use tokio::task;
 
     debug!(
@@ -386,7 +843,7 @@ async fn evaluate_batch_parallel(
             let env = env.clone(); // Arc clone is cheap
             task::spawn_blocking(move || {
                 let (results, _new_env) = eval(expr, env);
-                (idx, results, should_output)
+                (idx, flatten_conjunction_results(results), should_output)
             })
         })
         .collect();
@@ -570,6 +1027,163 @@ mod tests {
         assert_eq!(result.output[0], MettaValue::Long(42));
     }
 
+    #[test]
+    fn test_run_state_json_round_trip() {
+        let compiled = compile(
+            r#"
+            (= (double $x) (* $x 2))
+            !(double 21)
+            !(superpose ("a\"b" 1.5 True))
+            "#,
+        )
+        .unwrap();
+        let json = compiled.to_json_string();
+
+        let loaded = metta_state_from_json(&json).unwrap();
+        assert_eq!(loaded.source, compiled.source);
+        assert!(loaded.output.is_empty());
+
+        let result = run_state_json(MettaState::new_empty(), &json).unwrap();
+        assert_eq!(
+            result.output,
+            vec![
+                MettaValue::Long(42),
+                MettaValue::String("a\"b".to_string()),
+                MettaValue::Float(1.5),
+                MettaValue::Bool(true),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_state_json_rejects_malformed_state() {
+        let field_error = |json: &str| match run_state_json(MettaState::new_empty(), json) {
+            Err(StateJsonError::InvalidField { field, message }) => (field, message),
+            other => panic!("Expected a field error for {}, got {:?}", json, other),
+        };
+
+        // Not JSON at all
+        match run_state_json(MettaState::new_empty(), r#"{"source": [}"#) {
+            Err(StateJsonError::Syntax { offset, .. }) => assert_eq!(offset, 12),
+            other => panic!("Expected a syntax error, got {:?}", other),
+        }
+
+        // Wrong shapes, reported with the offending field
+        assert_eq!(field_error("[]").0, "");
+        assert_eq!(
+            field_error(r#"{"source":[],"environment":{}}"#),
+            ("output".to_string(), "missing field".to_string())
+        );
+        assert_eq!(
+            field_error(r#"{"source":{},"environment":{},"output":[]}"#),
+            (
+                "source".to_string(),
+                "expected an array, got an object".to_string()
+            )
+        );
+        assert_eq!(
+            field_error(r#"{"source":[],"environment":3,"output":[]}"#).0,
+            "environment"
+        );
+        assert_eq!(
+            field_error(
+                r#"{"source":[{"type":"sexpr","items":[{"type":"number","value":"1"}]}],
+                    "environment":{},"output":[]}"#
+            ),
+            (
+                "source[0].items[0].value".to_string(),
+                "expected a number, got a string".to_string()
+            )
+        );
+        assert_eq!(
+            field_error(
+                r#"{"source":[],"environment":{},"output":[{"type":"number","value":1.5}]}"#
+            )
+            .0,
+            "output[0].value"
+        );
+        assert_eq!(
+            field_error(r#"{"source":[{"type":"list"}],"environment":{},"output":[]}"#),
+            (
+                "source[0].type".to_string(),
+                "unknown value type 'list'".to_string()
+            )
+        );
+        assert_eq!(
+            field_error(r#"{"source":[{"value":"x"}],"environment":{},"output":[]}"#).0,
+            "source[0].type"
+        );
+    }
+
+    #[test]
+    fn test_json_reader_limits_nesting_depth() {
+        let deep = "[".repeat(100_000);
+        match JsonReader::parse(&deep) {
+            Err(StateJsonError::NestingTooDeep { offset, limit }) => {
+                assert_eq!(limit, MAX_JSON_DEPTH);
+                assert_eq!(offset, MAX_JSON_DEPTH);
+            }
+            _ => panic!("Expected a nesting error"),
+        }
+
+        // Nesting right at the limit is still accepted
+        let at_limit = format!(
+            "{}{}",
+            "[".repeat(MAX_JSON_DEPTH),
+            "]".repeat(MAX_JSON_DEPTH)
+        );
+        assert!(JsonReader::parse(&at_limit).is_ok());
+    }
+
+    #[test]
+    fn test_json_reader_decodes_surrogate_pairs() {
+        let decoded = |json: &str| match JsonReader::parse(json) {
+            Ok(Json::String(s)) => s,
+            _ => panic!("Expected a string for {}", json),
+        };
+        assert_eq!(decoded(r#""\ud83d\ude00""#), "\u{1F600}");
+        assert_eq!(decoded(r#""a\u00e9\uD834\uDD1Eb""#), "a\u{e9}\u{1D11E}b");
+
+        for unpaired in [
+            r#""\ud83d""#,
+            r#""\ud83dx""#,
+            r#""\ud83d\u0041""#,
+            r#""\ude00""#,
+        ] {
+            assert!(
+                matches!(
+                    JsonReader::parse(unpaired),
+                    Err(StateJsonError::Syntax { .. })
+                ),
+                "Expected {} to be rejected",
+                unpaired
+            );
+        }
+    }
+
+    #[test]
+    fn test_run_state_expands_conjunction_results() {
+        let accumulated = MettaState::new_empty();
+        let compiled = compile(
+            r#"
+            (pair x y)
+            !(match & self (pair $a $b) (, $a $b))
+            "#,
+        )
+        .unwrap();
+
+        let result = run_state(accumulated, compiled).unwrap();
+
+        // Two distinct outputs rather than one nested (, x y) value
+        assert_eq!(
+            result.output,
+            vec![
+                MettaValue::Atom("x".to_string()),
+                MettaValue::Atom("y".to_string())
+            ]
+        );
+    }
+
     // Async Parallel Evaluation Tests
     #[tokio::test]
     #[cfg(feature = "async")]
@@ -1198,6 +1812,7 @@ mod tests {
             line: 1,
             column: 7,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"));
@@ -1213,6 +1828,7 @@ mod tests {
             line: 1,
             column: 8,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"));
@@ -1228,6 +1844,7 @@ mod tests {
             line: 1,
             column: 10,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"));
@@ -1243,6 +1860,7 @@ mod tests {
             line: 1,
             column: 5,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"));
@@ -1258,6 +1876,7 @@ mod tests {
             line: 1,
             column: 1,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         // Generic errors now have a helpful hint
@@ -1278,6 +1897,7 @@ mod tests {
             line: 1,
             column: 5,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"));
@@ -1293,6 +1913,7 @@ mod tests {
             line: 1,
             column: 5,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"));
@@ -1309,6 +1930,7 @@ mod tests {
             line: 1,
             column: 1,
             text: "quota".to_string(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(
@@ -1329,6 +1951,7 @@ mod tests {
             line: 1,
             column: 1,
             text: "iff".to_string(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(
@@ -1349,6 +1972,7 @@ mod tests {
             line: 1,
             column: 1,
             text: "xyzzy".to_string(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         // Should not contain "Did you mean" when no similar keyword
@@ -1369,6 +1993,7 @@ mod tests {
             line: 1,
             column: 1,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(
@@ -1387,6 +2012,7 @@ mod tests {
             line: 1,
             column: 5,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"), "Expected 'Hint' in: {}", msg);
@@ -1411,6 +2037,7 @@ mod tests {
             line: 0,
             column: 0,
             text: String::new(),
+            file: None,
         };
         let msg = improve_error_message(&error);
         assert!(msg.contains("Hint"), "Expected 'Hint' in: {}", msg);
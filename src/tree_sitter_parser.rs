@@ -6,6 +6,9 @@
 use crate::ir::{Position, SExpr, Span};
 use tree_sitter::{Node, Parser};
 
+/// Head atom of the list produced for a brace list `{a b c}`
+pub const BRACE_LIST_MARKER: &str = "{}";
+
 /// Structured syntax error with location and type information
 #[derive(Debug, Clone)]
 pub struct SyntaxError {
@@ -13,6 +16,8 @@ pub struct SyntaxError {
     pub line: usize,
     pub column: usize,
     pub text: String,
+    /// Original source file named by a `;#line` directive, if any
+    pub file: Option<String>,
 }
 
 /// Categorized syntax error kinds for pattern matching
@@ -28,6 +33,12 @@ pub enum SyntaxErrorKind {
     UnclosedString,
     /// Invalid escape sequence in string
     InvalidEscape(String),
+    /// Malformed `` `uri` `` literal, with the reason
+    InvalidUri(String),
+    /// Malformed `#\c` character literal, with the reason
+    InvalidChar(String),
+    /// Lists nested deeper than the parser's limit, which is given
+    NestingTooDeep(usize),
     /// Unknown node kind from parser
     UnknownNodeKind(String),
     /// Parser initialization failed
@@ -38,17 +49,31 @@ pub enum SyntaxErrorKind {
 
 impl std::fmt::Display for SyntaxError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "Syntax error at line {}, column {}: ",
-            self.line, self.column
-        )?;
+        match &self.file {
+            Some(file) => write!(
+                f,
+                "Syntax error at {}:{}, column {}: ",
+                file, self.line, self.column
+            )?,
+            None => write!(
+                f,
+                "Syntax error at line {}, column {}: ",
+                self.line, self.column
+            )?,
+        }
         match &self.kind {
             SyntaxErrorKind::UnexpectedToken => write!(f, "unexpected '{}'", self.text),
             SyntaxErrorKind::UnclosedDelimiter(c) => write!(f, "unclosed '{}'", c),
             SyntaxErrorKind::ExtraClosingDelimiter(c) => write!(f, "unexpected closing '{}'", c),
             SyntaxErrorKind::UnclosedString => write!(f, "unclosed string literal"),
             SyntaxErrorKind::InvalidEscape(s) => write!(f, "invalid escape sequence '{}'", s),
+            SyntaxErrorKind::InvalidUri(reason) => write!(f, "invalid URI literal: {}", reason),
+            SyntaxErrorKind::InvalidChar(reason) => {
+                write!(f, "invalid character literal: {}", reason)
+            }
+            SyntaxErrorKind::NestingTooDeep(limit) => {
+                write!(f, "expressions nested more than {} levels deep", limit)
+            }
             SyntaxErrorKind::UnknownNodeKind(k) => write!(f, "unknown syntax '{}'", k),
             SyntaxErrorKind::ParserInit(msg) => write!(f, "parser initialization failed: {}", msg),
             SyntaxErrorKind::Generic => write!(f, "invalid syntax"),
@@ -58,6 +83,79 @@ impl std::fmt::Display for SyntaxError {
 
 impl std::error::Error for SyntaxError {}
 
+/// Check that `uri` is a well-formed URI reference and return its scheme
+///
+/// Absolute URIs return `Some(scheme)` (e.g. `http` for `http://x/y`); relative
+/// references such as `docs/a.md` or `//host/p` have no scheme and return `None`.
+/// Rejected: empty text, whitespace and characters that are never allowed in a URI,
+/// `%` not followed by two hex digits, an invalid scheme before the first `:`, and
+/// a scheme with nothing after it.
+pub fn validate_uri(uri: &str) -> Result<Option<&str>, String> {
+    if uri.is_empty() {
+        return Err("URI is empty".to_string());
+    }
+    if let Some(c) = uri
+        .chars()
+        .find(|c| c.is_whitespace() || c.is_control() || "<>\"{}|\\^`".contains(*c))
+    {
+        return Err(format!("character {:?} is not allowed in a URI", c));
+    }
+    let bytes = uri.as_bytes();
+    for (i, _) in uri.match_indices('%') {
+        let escape = bytes.get(i + 1..i + 3);
+        if !escape.is_some_and(|hex| hex.iter().all(u8::is_ascii_hexdigit)) {
+            return Err("'%' must be followed by two hex digits".to_string());
+        }
+    }
+
+    // A ':' before any '/', '?' or '#' ends the scheme; otherwise the URI is relative
+    let Some(colon) = uri.find([':', '/', '?', '#']).filter(|&i| bytes[i] == b':') else {
+        return Ok(None);
+    };
+    let scheme = &uri[..colon];
+    let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c));
+    if !valid_scheme {
+        return Err(format!("invalid scheme '{}'", scheme));
+    }
+    if colon + 1 == uri.len() {
+        return Err(format!("nothing follows the scheme '{}:'", scheme));
+    }
+    Ok(Some(scheme))
+}
+
+/// Decode the text of a `#\c` character literal into its character
+///
+/// A character literal reads as a one-character `String`, so `#\a` and `"a"`
+/// are the same value. After the `#\` prefix comes exactly one character or
+/// one of the escapes `\n`, `\t`, `\\` and `\'`; `#\\` alone is a backslash.
+pub fn parse_char_literal(text: &str) -> Result<char, String> {
+    let body = text
+        .strip_prefix("#\\")
+        .ok_or_else(|| format!("'{}' does not start with #\\", text))?;
+    let mut chars = body.chars();
+    let c = match (chars.next(), chars.next(), chars.next()) {
+        (None, _, _) => return Err("missing character after #\\".to_string()),
+        (Some(c), None, _) => c,
+        (Some('\\'), Some(escaped), None) => match escaped {
+            'n' => '\n',
+            't' => '\t',
+            '\\' => '\\',
+            '\'' => '\'',
+            other => return Err(format!("unknown escape '\\{}'", other)),
+        },
+        _ => {
+            return Err(format!(
+                "'{}' has more than one character; use a string for text",
+                body
+            ))
+        }
+    };
+    Ok(c)
+}
+
 /// Count delimiter balance in source (positive = unclosed, negative = extra close)
 fn count_delimiter_balance(source: &str, open: char, close: char) -> i32 {
     let mut depth = 0;
@@ -99,9 +197,63 @@ fn has_unclosed_string(source: &str) -> bool {
     in_string
 }
 
+/// Line directives of the form `;#line 42 "orig.metta"` found in a source
+///
+/// Tools that generate MeTTa can emit these comments so that errors point back at the
+/// original source: the line following a directive is reported as line 42 of
+/// `orig.metta`, and subsequent lines count up from there. The file name is optional
+/// and defaults to the file of the previous directive.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LineDirectives {
+    /// (first physical row the directive applies to, logical line of that row, file)
+    entries: Vec<(usize, usize, Option<String>)>,
+}
+
+impl LineDirectives {
+    /// Scan a source for `;#line` directives
+    pub fn scan(source: &str) -> Self {
+        let mut entries: Vec<(usize, usize, Option<String>)> = Vec::new();
+        for (row, line) in source.lines().enumerate() {
+            let Some(rest) = line.trim_start().strip_prefix(";#line") else {
+                continue;
+            };
+            let rest = rest.trim();
+            let (number, file) = match rest.split_once(char::is_whitespace) {
+                Some((number, file)) => (number, Some(file.trim().trim_matches('"').to_string())),
+                None => (rest, None),
+            };
+            let Ok(logical_line) = number.parse::<usize>() else {
+                continue;
+            };
+            let file = file
+                .filter(|f| !f.is_empty())
+                .or_else(|| entries.last().and_then(|(_, _, f)| f.clone()));
+            entries.push((row + 1, logical_line, file));
+        }
+        LineDirectives { entries }
+    }
+
+    /// Map a 0-indexed physical row to the reported (file, 1-indexed line)
+    pub fn resolve(&self, row: usize) -> (Option<String>, usize) {
+        match self
+            .entries
+            .iter()
+            .rev()
+            .find(|(start, _, _)| *start <= row)
+        {
+            Some((start, logical_line, file)) => (file.clone(), logical_line + (row - start)),
+            None => (None, row + 1),
+        }
+    }
+}
+
+/// Default limit on how deeply lists, brace lists and prefixed expressions may nest
+pub const DEFAULT_MAX_NESTING_DEPTH: usize = 1024;
+
 /// Parser that uses Tree-Sitter with semantic node type decomposition
 pub struct TreeSitterMettaParser {
     parser: Parser,
+    max_nesting_depth: usize,
 }
 
 impl TreeSitterMettaParser {
@@ -111,7 +263,19 @@ impl TreeSitterMettaParser {
         parser
             .set_language(&tree_sitter_metta::language())
             .map_err(|e| format!("Failed to set language: {}", e))?;
-        Ok(Self { parser })
+        Ok(Self {
+            parser,
+            max_nesting_depth: DEFAULT_MAX_NESTING_DEPTH,
+        })
+    }
+
+    /// Set how deeply expressions may nest before `parse` fails with
+    /// `SyntaxErrorKind::NestingTooDeep`
+    ///
+    /// Conversion and evaluation recurse once per level, so the limit keeps
+    /// hostile input such as thousands of `(` from overflowing the stack.
+    pub fn set_max_nesting_depth(&mut self, depth: usize) {
+        self.max_nesting_depth = depth;
     }
 
     /// Parse MeTTa source code into SExpr AST
@@ -121,14 +285,17 @@ impl TreeSitterMettaParser {
             line: 1,
             column: 1,
             text: "Failed to parse source".into(),
+            file: None,
         })?;
 
         let root = tree.root_node();
+        self.check_nesting(root, source)?;
 
         // Check for syntax errors in the parse tree
         if root.has_error() {
             return Err(self.create_syntax_error(&root, source));
         }
+        self.check_literals(root, source)?;
 
         self.convert_source_file(root, source)
             .map_err(|e| SyntaxError {
@@ -136,9 +303,89 @@ impl TreeSitterMettaParser {
                 line: 1,
                 column: 1,
                 text: e,
+                file: None,
             })
     }
 
+    /// Reject trees nested deeper than `max_nesting_depth`, reporting the first node
+    /// past the limit
+    /// Walks the tree with a cursor rather than recursion, since the tree is untrusted.
+    fn check_nesting(&self, root: Node, source: &str) -> Result<(), SyntaxError> {
+        let nests =
+            |node: Node| matches!(node.kind(), "list" | "brace_list" | "prefixed_expression");
+        let mut cursor = root.walk();
+        let mut depth = 0;
+
+        loop {
+            let node = cursor.node();
+            if nests(node) {
+                depth += 1;
+                if depth > self.max_nesting_depth {
+                    let start = node.start_position();
+                    let (file, line) = LineDirectives::scan(source).resolve(start.row);
+                    return Err(SyntaxError {
+                        kind: SyntaxErrorKind::NestingTooDeep(self.max_nesting_depth),
+                        line,
+                        column: start.column + 1,
+                        text: String::new(),
+                        file,
+                    });
+                }
+            }
+            if cursor.goto_first_child() {
+                continue;
+            }
+
+            // Leave finished nodes until one has a next sibling
+            loop {
+                if nests(cursor.node()) {
+                    depth -= 1;
+                }
+                if cursor.goto_next_sibling() {
+                    break;
+                }
+                if !cursor.goto_parent() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Reject malformed `` `uri` `` and `#\c` literals, reporting the first one's position
+    fn check_literals(&self, node: Node, source: &str) -> Result<(), SyntaxError> {
+        let text = &source[node.start_byte()..node.end_byte()];
+        let invalid = match node.kind() {
+            "uri_literal" => validate_uri(text.trim_matches('`'))
+                .err()
+                .map(SyntaxErrorKind::InvalidUri),
+            "char_literal" => parse_char_literal(text)
+                .err()
+                .map(SyntaxErrorKind::InvalidChar),
+            _ => {
+                let mut cursor = node.walk();
+                for child in node.children(&mut cursor) {
+                    self.check_literals(child, source)?;
+                }
+                return Ok(());
+            }
+        };
+
+        match invalid {
+            Some(kind) => {
+                let start = node.start_position();
+                let (file, line) = LineDirectives::scan(source).resolve(start.row);
+                Err(SyntaxError {
+                    kind,
+                    line,
+                    column: start.column + 1,
+                    text: text.to_string(),
+                    file,
+                })
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Check if a node should be processed (named and not extra)
     ///
     /// This filters out extras like comments and whitespace while preserving
@@ -176,6 +423,7 @@ impl TreeSitterMettaParser {
                 Ok(vec![])
             }
             "list" => self.convert_list(node, source),
+            "brace_list" => self.convert_brace_list(node, source),
             "prefixed_expression" => self.convert_prefixed_expression(node, source),
             "atom_expression" => self.convert_atom_expression(node, source),
             _ => Err(format!("Unknown expression kind: {}", node.kind())),
@@ -197,6 +445,30 @@ impl TreeSitterMettaParser {
         Ok(vec![SExpr::List(items, Some(span))])
     }
 
+    /// Convert brace_list: {expr expr ...}
+    ///
+    /// Produces a list headed by the `{}` marker atom, so `{a b}` becomes `({} a b)`
+    /// and stays distinguishable from the ordinary expression `(a b)`.
+    fn convert_brace_list(&self, node: Node, source: &str) -> Result<Vec<SExpr>, String> {
+        let span = self.node_span(node);
+        let mut items = Vec::new();
+        let mut cursor = node.walk();
+
+        for child in node.children(&mut cursor) {
+            if child.kind() == "{" {
+                let marker_span = self.node_span(child);
+                items.push(SExpr::Atom(
+                    BRACE_LIST_MARKER.to_string(),
+                    Some(marker_span),
+                ));
+            } else if self.should_process_node(child) {
+                items.extend(self.convert_expression(child, source)?);
+            }
+        }
+
+        Ok(vec![SExpr::List(items, Some(span))])
+    }
+
     /// Convert prefixed_expression: !expr, ?expr, 'expr
     /// Matches sexpr.rs behavior: convert !(expr) to (! expr)
     fn convert_prefixed_expression(&self, node: Node, source: &str) -> Result<Vec<SExpr>, String> {
@@ -288,6 +560,18 @@ impl TreeSitterMettaParser {
                 Ok(vec![SExpr::String(unquoted, Some(span))])
             }
 
+            // URI literal: remove backticks; the text was validated by check_literals
+            "uri_literal" => Ok(vec![SExpr::String(
+                text.trim_matches('`').to_string(),
+                Some(span),
+            )]),
+
+            // Character literal: a one-character string; validated by check_literals
+            "char_literal" => Ok(vec![SExpr::String(
+                parse_char_literal(&text)?.to_string(),
+                Some(span),
+            )]),
+
             // Float literal: parse to f64
             "float_literal" => {
                 let num = text
@@ -323,12 +607,14 @@ impl TreeSitterMettaParser {
             let start = error_node.start_position();
             let error_text = source[error_node.start_byte()..error_node.end_byte()].to_string();
             let kind = self.analyze_error_kind(source);
+            let (file, line) = LineDirectives::scan(source).resolve(start.row);
 
             SyntaxError {
                 kind,
-                line: start.row + 1,
+                line,
                 column: start.column + 1,
                 text: error_text,
+                file,
             }
         } else {
             SyntaxError {
@@ -336,6 +622,7 @@ impl TreeSitterMettaParser {
                 line: 1,
                 column: 1,
                 text: String::new(),
+                file: None,
             }
         }
     }
@@ -613,6 +900,170 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_uri_literals() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+
+        // Absolute and relative URIs read as their text without backticks
+        let result = strip_spans_vec(&parser.parse("(get `http://x/y?q=1` `a/b%20c`)").unwrap());
+        assert_eq!(
+            result,
+            vec![SExpr::List(
+                vec![
+                    SExpr::Atom("get".to_string(), None),
+                    SExpr::String("http://x/y?q=1".to_string(), None),
+                    SExpr::String("a/b%20c".to_string(), None),
+                ],
+                None
+            )]
+        );
+
+        // Malformed URIs are lexical errors at the literal
+        for bad in ["`1http://x`", "`a b`", "`x/%zz`", "`mailto:`", "``"] {
+            let err = parser.parse(&format!("(get\n  {})", bad)).unwrap_err();
+            assert!(
+                matches!(err.kind, SyntaxErrorKind::InvalidUri(_)),
+                "{}: {:?}",
+                bad,
+                err
+            );
+            assert_eq!((err.line, err.column), (2, 3), "{}", bad);
+            assert_eq!(err.text, bad);
+        }
+    }
+
+    #[test]
+    fn test_parse_char_literals() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+
+        // Plain and escaped characters read as one-character strings
+        let result = strip_spans_vec(&parser.parse("(chars #\\a #\\\\n #\\\\' #\\\\)").unwrap());
+        assert_eq!(
+            result,
+            vec![SExpr::List(
+                vec![
+                    SExpr::Atom("chars".to_string(), None),
+                    SExpr::String("a".to_string(), None),
+                    SExpr::String("\n".to_string(), None),
+                    SExpr::String("'".to_string(), None),
+                    SExpr::String("\\".to_string(), None),
+                ],
+                None
+            )]
+        );
+
+        // Multi-character literals are rejected at the literal
+        let err = parser.parse("(chars\n  #\\ab)").unwrap_err();
+        assert!(
+            matches!(err.kind, SyntaxErrorKind::InvalidChar(_)),
+            "{:?}",
+            err
+        );
+        assert_eq!((err.line, err.column), (2, 3));
+        assert!(
+            err.to_string().contains("more than one character"),
+            "{}",
+            err
+        );
+
+        assert!(parse_char_literal("#\\\\q").is_err());
+        assert!(parse_char_literal("#\\").is_err());
+    }
+
+    #[test]
+    fn test_nesting_depth_limit() {
+        let nested = |depth: usize| format!("{}x{}", "(".repeat(depth), ")".repeat(depth));
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+        parser.set_max_nesting_depth(16);
+
+        // Exactly at the limit parses
+        assert!(parser.parse(&nested(16)).is_ok());
+        assert!(parser.parse(&format!("!{}", nested(15))).is_ok());
+
+        // One level more is a syntax error at the offending list
+        let err = parser.parse(&nested(17)).unwrap_err();
+        assert_eq!(err.kind, SyntaxErrorKind::NestingTooDeep(16));
+        assert_eq!((err.line, err.column), (1, 17));
+        assert!(err.to_string().contains("more than 16 levels"), "{}", err);
+
+        // Hostile input far past the default limit fails without overflowing the stack
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+        let err = parser.parse(&nested(100_000)).unwrap_err();
+        assert_eq!(
+            err.kind,
+            SyntaxErrorKind::NestingTooDeep(DEFAULT_MAX_NESTING_DEPTH)
+        );
+    }
+
+    #[test]
+    fn test_parse_many_repeated_symbols() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+        let source = "(edge node-a node-b)\n".repeat(10_000);
+
+        let result = strip_spans_vec(&parser.parse(&source).unwrap());
+        let expected = SExpr::List(
+            vec![
+                SExpr::Atom("edge".to_string(), None),
+                SExpr::Atom("node-a".to_string(), None),
+                SExpr::Atom("node-b".to_string(), None),
+            ],
+            None,
+        );
+        assert_eq!(result.len(), 10_000);
+        assert!(result.iter().all(|expr| *expr == expected));
+    }
+
+    #[test]
+    fn test_validate_uri() {
+        assert_eq!(validate_uri("http://x/y"), Ok(Some("http")));
+        assert_eq!(validate_uri("git+ssh://host/repo"), Ok(Some("git+ssh")));
+        assert_eq!(validate_uri("docs/a:b"), Ok(None));
+        assert_eq!(validate_uri("//host/p"), Ok(None));
+        assert_eq!(validate_uri("#frag"), Ok(None));
+        assert!(validate_uri("ht tp://x").is_err());
+        assert!(validate_uri("-x:y").is_err());
+        assert!(validate_uri("x/%4").is_err());
+    }
+
+    #[test]
+    fn test_parse_brace_lists() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+
+        let marker = || SExpr::Atom(BRACE_LIST_MARKER.to_string(), None);
+
+        // A brace list is the equivalent paren expression headed by the marker atom
+        let braces = strip_spans_vec(&parser.parse("{a b c}").unwrap());
+        let parens = strip_spans_vec(&parser.parse("(a b c)").unwrap());
+        let mut tagged = vec![marker()];
+        match &parens[0] {
+            SExpr::List(items, _) => tagged.extend(items.iter().cloned()),
+            other => panic!("Expected list, got {:?}", other),
+        }
+        assert_eq!(braces, vec![SExpr::List(tagged, None)]);
+        assert_ne!(braces, parens);
+
+        // Nested braces and braces inside ordinary expressions
+        let result = strip_spans_vec(&parser.parse("(f {x {}} y)").unwrap());
+        assert_eq!(
+            result,
+            vec![SExpr::List(
+                vec![
+                    SExpr::Atom("f".to_string(), None),
+                    SExpr::List(
+                        vec![
+                            marker(),
+                            SExpr::Atom("x".to_string(), None),
+                            SExpr::List(vec![marker()], None),
+                        ],
+                        None
+                    ),
+                    SExpr::Atom("y".to_string(), None),
+                ],
+                None
+            )]
+        );
+    }
+
     #[test]
     fn test_parse_prefixed_expressions() {
         let mut parser = TreeSitterMettaParser::new().unwrap();
@@ -651,6 +1102,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_display_prefix_shorthand_round_trips() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+
+        for source in ["'foo", "!(+ 1 2)", "?(parent $x)", "(f 'a !(g '(b c)))"] {
+            let parsed = strip_spans_vec(&parser.parse(source).unwrap());
+            let printed = parsed
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            assert_eq!(printed, source);
+            let reparsed = strip_spans_vec(&parser.parse(&printed).unwrap());
+            assert_eq!(reparsed, parsed);
+        }
+
+        // Only the two-element prefix form uses the shorthand
+        let parsed = parser.parse("(! a b)").unwrap();
+        assert_eq!(parsed[0].to_string(), "(! a b)");
+    }
+
     #[test]
     fn test_parse_multiple_expressions() {
         let mut parser = TreeSitterMettaParser::new().unwrap();
@@ -711,6 +1183,37 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_negative_numbers_and_minus() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+        let mut parse = |src: &str| strip_spans_vec(&parser.parse(src).unwrap());
+        let minus = || SExpr::Atom("-".to_string(), None);
+
+        assert_eq!(parse("-5"), vec![SExpr::Integer(-5, None)]);
+        assert_eq!(
+            parse("(- 1 2)"),
+            vec![SExpr::List(
+                vec![minus(), SExpr::Integer(1, None), SExpr::Integer(2, None)],
+                None
+            )]
+        );
+        assert_eq!(
+            parse("(+ x -3)"),
+            vec![SExpr::List(
+                vec![
+                    SExpr::Atom("+".to_string(), None),
+                    SExpr::Atom("x".to_string(), None),
+                    SExpr::Integer(-3, None),
+                ],
+                None
+            )]
+        );
+        assert_eq!(
+            parse("(- 5)"),
+            vec![SExpr::List(vec![minus(), SExpr::Integer(5, None)], None)]
+        );
+    }
+
     #[test]
     fn test_parse_floats() {
         let mut parser = TreeSitterMettaParser::new().unwrap();
@@ -1112,6 +1615,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_line_directive_adjusts_error_location() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+        let source = "(ok 1)\n;#line 42 \"orig.metta\"\n(fine 2)\n)";
+        let error = parser.parse(source).unwrap_err();
+        assert_eq!(error.file.as_deref(), Some("orig.metta"));
+        // (fine 2) is line 42 of orig.metta, so the stray ')' is line 43
+        assert_eq!(error.line, 43);
+        assert!(error.to_string().contains("orig.metta:43"));
+    }
+
+    #[test]
+    fn test_line_directive_resolution() {
+        let directives =
+            LineDirectives::scan("a\n;#line 10 \"gen.metta\"\nb\nc\n;#line 100\nd\n;#line x\ne");
+        assert_eq!(directives.resolve(0), (None, 1));
+        assert_eq!(directives.resolve(2), (Some("gen.metta".to_string()), 10));
+        assert_eq!(directives.resolve(3), (Some("gen.metta".to_string()), 11));
+        // A directive without a file keeps the previous file
+        assert_eq!(directives.resolve(5), (Some("gen.metta".to_string()), 100));
+        // Malformed directives are ignored
+        assert_eq!(directives.resolve(7), (Some("gen.metta".to_string()), 102));
+    }
+
+    #[test]
+    fn test_no_line_directive_keeps_physical_lines() {
+        let mut parser = TreeSitterMettaParser::new().unwrap();
+        let error = parser.parse("(ok 1)\n)").unwrap_err();
+        assert_eq!(error.file, None);
+        assert_eq!(error.line, 2);
+    }
+
     #[test]
     fn test_syntax_error_display() {
         let error = SyntaxError {
@@ -1119,6 +1654,7 @@ mod tests {
             line: 1,
             column: 7,
             text: String::new(),
+            file: None,
         };
         let msg = error.to_string();
         assert!(msg.contains("line 1"));
@@ -1136,6 +1672,7 @@ mod tests {
                     line: 1,
                     column: 1,
                     text: "foo".to_string(),
+                    file: None,
                 },
                 "unexpected 'foo'",
             ),
@@ -1145,6 +1682,7 @@ mod tests {
                     line: 1,
                     column: 1,
                     text: String::new(),
+                    file: None,
                 },
                 "unclosed '('",
             ),
@@ -1154,6 +1692,7 @@ mod tests {
                     line: 1,
                     column: 1,
                     text: String::new(),
+                    file: None,
                 },
                 "unexpected closing ')'",
             ),
@@ -1163,6 +1702,7 @@ mod tests {
                     line: 1,
                     column: 1,
                     text: String::new(),
+                    file: None,
                 },
                 "unclosed string",
             ),
@@ -1172,6 +1712,7 @@ mod tests {
                     line: 1,
                     column: 1,
                     text: String::new(),
+                    file: None,
                 },
                 "invalid escape sequence 'z'",
             ),
@@ -1181,6 +1722,7 @@ mod tests {
                     line: 0,
                     column: 0,
                     text: String::new(),
+                    file: None,
                 },
                 "parser initialization failed",
             ),
@@ -1190,6 +1732,7 @@ mod tests {
                     line: 1,
                     column: 1,
                     text: String::new(),
+                    file: None,
                 },
                 "invalid syntax",
             ),
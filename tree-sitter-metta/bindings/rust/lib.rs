@@ -27,9 +27,12 @@ pub mod node_types {
     pub const WILDCARD: &str = "wildcard";
     pub const IDENTIFIER: &str = "identifier";
     pub const STRING_LITERAL: &str = "string_literal";
+    pub const URI_LITERAL: &str = "uri_literal";
+    pub const CHAR_LITERAL: &str = "char_literal";
     pub const FLOAT_LITERAL: &str = "float_literal";
     pub const INTEGER_LITERAL: &str = "integer_literal";
     pub const BOOLEAN_LITERAL: &str = "boolean_literal";
+    pub const SPECIAL_TYPE_SYMBOL: &str = "special_type_symbol";
 
     // Operator types
     pub const OPERATOR: &str = "operator";
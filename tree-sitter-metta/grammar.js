@@ -13,6 +13,7 @@ module.exports = grammar({
 
     expression: $ => choice(
       $.list,
+      $.brace_list,
       $.prefixed_expression,
       $.atom_expression,
     ),
@@ -24,6 +25,13 @@ module.exports = grammar({
       ')'
     ),
 
+    // Brace lists: {expr expr ...} - set-like literals, kept distinct from lists
+    brace_list: $ => seq(
+      '{',
+      repeat($.expression),
+      '}'
+    ),
+
     // Prefixed expressions: !expr, ?expr, 'expr
     prefixed_expression: $ => seq(
       field('prefix', choice(
@@ -47,13 +55,15 @@ module.exports = grammar({
       $.special_type_symbol,  // Must come before operator (contains %)
       $.operator,
       $.string_literal,
+      $.uri_literal,
+      $.char_literal,
       $.float_literal,
       $.integer_literal,
       $.identifier,
     ),
 
     // Variables: $var (for pattern variables)
-    // Note: & is an operator (space reference), not a variable prefix
+    // Note: &name is an identifier (space or token reference), not a variable
     // Note: 'var is handled by quote_prefix in prefixed_expression
     variable: $ => token(
       seq('$', /[a-zA-Z0-9_'\-+*/&]*/)
@@ -71,10 +81,13 @@ module.exports = grammar({
 
     // Regular identifiers (no special prefix)
     identifier: $ => token(prec(2, choice(
-      // Standard identifiers: letters, digits, allowed special chars
-      /[a-zA-Z][a-zA-Z0-9_'\-+*/]*/,
+      // Standard identifiers: letters, digits, allowed special chars,
+      // optionally ending in ! for side-effecting forms such as import!
+      /[a-zA-Z][a-zA-Z0-9_'\-+*/]*!?/,
       // Can start with some operators if followed by alphanumeric
       /[+\-*/][a-zA-Z0-9_'\-+*/]+/,
+      // Space and token references such as &self; a bare & stays an operator
+      /&[a-zA-Z][a-zA-Z0-9_'\-+*/]*/,
     ))),
 
     // Operators (decomposed by type)
@@ -155,6 +168,14 @@ module.exports = grammar({
       '"'
     )),
 
+    // URI literals: `http://example.org/x`, `relative/path`
+    // Anything up to the closing backtick; well-formedness is checked after parsing
+    uri_literal: $ => token(seq('`', /[^`\n]*/, '`')),
+
+    // Character literals: #\a, #\\n (escaped newline)
+    // Lexes everything up to a delimiter; single-character text is checked after parsing
+    char_literal: $ => token(prec(5, seq('#\\', /[^\s()]*/))),
+
     // Float literals (with optional minus) - highest precedence to match before integer
     // Supports: 3.14, -2.5, 1.0e10, -1.5e-3, 2.0E+5
     float_literal: $ => token(prec(4, seq(
@@ -5,6 +5,8 @@
 
 ; Literals
 (string_literal) @string
+(uri_literal) @string.special.url
+(char_literal) @character
 (integer_literal) @number
 (float_literal) @number.float
 (boolean_literal) @boolean
@@ -39,6 +41,8 @@
 ; Brackets
 "(" @punctuation.bracket
 ")" @punctuation.bracket
+"{" @punctuation.bracket
+"}" @punctuation.bracket
 
 ; Special keywords in identifier position
 ((identifier) @keyword
@@ -3,9 +3,11 @@
 
 ; Indent after opening parentheses
 (list "(" @indent)
+(brace_list "{" @indent)
 
 ; Dedent before closing bracket
 ")" @dedent
+"}" @dedent
 
 ; Prefixed expressions should indent their arguments
 (prefixed_expression
@@ -15,6 +17,8 @@
 ; Align expressions within lists
 (list
   (expression) @branch)
+(brace_list
+  (expression) @branch)
 
 ; Comments don't affect indentation
 (line_comment) @ignore